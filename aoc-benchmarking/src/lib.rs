@@ -1 +1,2 @@
-pub mod helper_macros;
+//! Everything here is in `benches/`, which benchmarks every day registered
+//! through `aoc`.
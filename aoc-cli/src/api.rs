@@ -0,0 +1,278 @@
+//! A minimal client for the Advent of Code website.
+use std::fmt::Display;
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+pub(crate) const BASE_URL: &str = "https://adventofcode.com";
+/// The default event year.
+pub(crate) const YEAR: usize = 2022;
+
+/// Session handling shared by every command that talks to the AoC API.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct Session {
+    /// The session cookie for adventofcode.com.
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: String,
+}
+
+impl Session {
+    pub fn client(&self, year: usize) -> Client {
+        Client::new(&self.session, year)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Client {
+    session: String,
+    year: usize,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(session: &str, year: usize) -> Self {
+        Self {
+            session: session.to_string(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!(
+                    "github.com/mattcl/aoc2022 aoc-cli/",
+                    env!("CARGO_PKG_VERSION")
+                ))
+                .build(),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Submit `answer` for the given `day` and `part`.
+    pub fn submit(&self, day: usize, part: usize, answer: &str) -> Result<SubmitOutcome> {
+        let url = format!("{}/{}/day/{}/answer", BASE_URL, self.year, day);
+        let level = part.to_string();
+        let body = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])
+            .context("Failed to submit answer")?
+            .into_string()
+            .context("Failed to read submission response")?;
+
+        Ok(SubmitOutcome::from_response(&body))
+    }
+
+    /// Fetch the input for the given `day`.
+    pub fn input(&self, day: usize) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", BASE_URL, self.year, day);
+        self.agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .context("Failed to fetch input")?
+            .into_string()
+            .context("Failed to read input response")
+    }
+
+    /// Fetch the completion times and ranks for every day we've solved.
+    pub fn personal_stats(&self) -> Result<Vec<DayStats>> {
+        let url = format!("{}/{}/leaderboard/self", BASE_URL, self.year);
+        let body = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .context("Failed to fetch personal stats")?
+            .into_string()
+            .context("Failed to read personal stats response")?;
+
+        Ok(DayStats::from_response(&body))
+    }
+}
+
+/// When a part was solved, relative to when it unlocked, and the global rank.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct PartStats {
+    /// Either `HH:MM:SS` or `>24h`.
+    pub time: String,
+    pub rank: usize,
+}
+
+/// The personal stats for a single day.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct DayStats {
+    pub day: usize,
+    pub part_one: Option<PartStats>,
+    pub part_two: Option<PartStats>,
+}
+
+impl DayStats {
+    pub fn stars(&self) -> usize {
+        self.part_one.is_some() as usize + self.part_two.is_some() as usize
+    }
+
+    /// Parse the table from the personal leaderboard page, which looks like
+    ///
+    /// ```text
+    /// Day       Time   Rank  Score       Time   Rank  Score
+    ///   2   00:05:39    981      0   00:09:48    938      0
+    ///   1       >24h  71364      0          -      -      -
+    /// ```
+    pub fn from_response(body: &str) -> Vec<Self> {
+        fn part(time: &str, rank: &str) -> Option<PartStats> {
+            if time == "-" {
+                return None;
+            }
+
+            Some(PartStats {
+                // the page is html, so `>24h` is escaped
+                time: time.replace("&gt;", ">"),
+                rank: rank.parse().ok()?,
+            })
+        }
+
+        let mut days = body
+            .lines()
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<_>>();
+                if fields.len() != 7 {
+                    return None;
+                }
+
+                let day = fields[0].parse().ok().filter(|d| (1..=25).contains(d))?;
+                Some(Self {
+                    day,
+                    part_one: part(fields[1], fields[2]),
+                    part_two: part(fields[4], fields[5]),
+                })
+            })
+            .collect::<Vec<_>>();
+        days.sort_by_key(|d| d.day);
+
+        days
+    }
+}
+
+/// The result of submitting an answer, as reported by the AoC website.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum SubmitOutcome {
+    Correct,
+    /// The answer was wrong, with an optional hint (too high/too low).
+    Incorrect(Option<&'static str>),
+    /// We submitted too recently, with the remaining wait time if known.
+    TooSoon(Option<String>),
+    /// The part was already solved (or is not yet available).
+    AlreadySolved,
+    /// We couldn't make sense of the response.
+    Unknown,
+}
+
+impl SubmitOutcome {
+    pub fn from_response(body: &str) -> Self {
+        if body.contains("That's the right answer") {
+            Self::Correct
+        } else if body.contains("That's not the right answer") {
+            let hint = if body.contains("your answer is too high") {
+                Some("too high")
+            } else if body.contains("your answer is too low") {
+                Some("too low")
+            } else {
+                None
+            };
+            Self::Incorrect(hint)
+        } else if body.contains("You gave an answer too recently") {
+            // "You have 39s left to wait."
+            let wait = body.split_once("You have ").and_then(|(_, rest)| {
+                rest.split_once(" left to wait")
+                    .map(|(wait, _)| wait.to_string())
+            });
+            Self::TooSoon(wait)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect(Some(hint)) => write!(f, "incorrect ({})", hint),
+            Self::Incorrect(None) => write!(f, "incorrect"),
+            Self::TooSoon(Some(wait)) => write!(f, "too soon ({} left to wait)", wait),
+            Self::TooSoon(None) => write!(f, "too soon"),
+            Self::AlreadySolved => write!(f, "already solved or not yet unlocked"),
+            Self::Unknown => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn submit_outcomes() {
+        assert_eq!(
+            SubmitOutcome::from_response(
+                "<p>That's the right answer! You are one gold star closer"
+            ),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(
+                "<p>That's not the right answer; your answer is too low. If you're stuck"
+            ),
+            SubmitOutcome::Incorrect(Some("too low"))
+        );
+        assert_eq!(
+            SubmitOutcome::from_response(
+                "<p>You gave an answer too recently; you have to wait. You have 39s left to wait."
+            ),
+            SubmitOutcome::TooSoon(Some("39s".into()))
+        );
+        assert_eq!(
+            SubmitOutcome::from_response("You don't seem to be solving the right level."),
+            SubmitOutcome::AlreadySolved
+        );
+        assert_eq!(SubmitOutcome::from_response(""), SubmitOutcome::Unknown);
+    }
+
+    #[test]
+    fn personal_stats() {
+        let body = "<article><pre>      <span class=\"leaderboard-daydesc-first\">--------Part 1--------</span>   <span class=\"leaderboard-daydesc-both\">--------Part 2--------</span>
+Day       <span class=\"leaderboard-daydesc-first\">Time   Rank  Score</span>       <span class=\"leaderboard-daydesc-both\">Time   Rank  Score</span>
+  2   00:05:39    981      0   00:09:48    938      0
+  1       &gt;24h  71364      0          -      -      -
+</pre></article>";
+
+        assert_eq!(
+            DayStats::from_response(body),
+            vec![
+                DayStats {
+                    day: 1,
+                    part_one: Some(PartStats {
+                        time: ">24h".into(),
+                        rank: 71364
+                    }),
+                    part_two: None,
+                },
+                DayStats {
+                    day: 2,
+                    part_one: Some(PartStats {
+                        time: "00:05:39".into(),
+                        rank: 981
+                    }),
+                    part_two: Some(PartStats {
+                        time: "00:09:48".into(),
+                        rank: 938
+                    }),
+                },
+            ]
+        );
+        assert!(DayStats::from_response("").is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+//! Running the criterion benchmarks without remembering the cargo invocation.
+use std::{path::PathBuf, process::Command};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+/// Run the criterion benchmarks in `aoc-benchmarking`, optionally only for a
+/// single day.
+///
+/// This is equivalent to `cargo bench -p aoc-benchmarking -- <DAY>`, so it must
+/// be run with access to the workspace sources.
+#[derive(Args)]
+pub(crate) struct Bench {
+    /// The day to benchmark. Every day (and the combined total) is
+    /// benchmarked if omitted.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// The root of the workspace.
+    #[clap(short, long, default_value = ".")]
+    root: PathBuf,
+
+    /// Features to enable in the benchmarks, e.g. `par`.
+    #[clap(short = 'F', long)]
+    features: Vec<String>,
+
+    /// Additional arguments for criterion, e.g. `-- --save-baseline main`.
+    #[arg(last = true)]
+    criterion_args: Vec<String>,
+}
+
+impl Bench {
+    pub fn run(&self) -> Result<()> {
+        // prefer the cargo we were (possibly) invoked via
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+        let args = self.cargo_args();
+        println!("running: cargo {}", args.join(" "));
+
+        let status = Command::new(cargo)
+            .args(&args)
+            .current_dir(&self.root)
+            .status()
+            .context("Failed to run cargo")?;
+
+        if !status.success() {
+            bail!("Benchmarks failed: {}", status);
+        }
+
+        Ok(())
+    }
+
+    fn cargo_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec!["bench".into(), "-p".into(), "aoc-benchmarking".into()];
+
+        if !self.features.is_empty() {
+            args.push("--features".into());
+            args.push(self.features.join(","));
+        }
+
+        args.push("--".into());
+
+        // the benchmark groups are named after the problem label, which starts
+        // with the padded day
+        if let Some(day) = self.day {
+            args.push(format!("{:03}", day));
+        }
+
+        args.extend(self.criterion_args.iter().cloned());
+
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_args() {
+        let bench = Bench {
+            day: Some(7),
+            root: ".".into(),
+            features: Vec::new(),
+            criterion_args: Vec::new(),
+        };
+        assert_eq!(
+            bench.cargo_args(),
+            ["bench", "-p", "aoc-benchmarking", "--", "007"]
+        );
+
+        let bench = Bench {
+            day: None,
+            root: ".".into(),
+            features: vec!["par".into(), "byte-parser".into()],
+            criterion_args: vec!["--save-baseline".into(), "main".into()],
+        };
+        assert_eq!(
+            bench.cargo_args(),
+            [
+                "bench",
+                "-p",
+                "aoc-benchmarking",
+                "--features",
+                "par,byte-parser",
+                "--",
+                "--save-baseline",
+                "main"
+            ]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Inputs fetched from the AoC website, cached outside of the workspace.
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Args, Subcommand};
+
+use crate::api::Session;
+
+/// Where fetched inputs are stored, as `<ROOT>/<YEAR>/day-XXX.txt`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct InputCache {
+    root: PathBuf,
+}
+
+/// A single cached input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Cached {
+    pub year: usize,
+    pub day: usize,
+    pub path: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// `$AOC_CACHE_DIR` if set, otherwise `aoc` under `$XDG_CACHE_HOME` (or
+    /// `~/.cache`).
+    pub fn from_env() -> Result<Self> {
+        if let Some(dir) = std::env::var_os("AOC_CACHE_DIR") {
+            return Ok(Self::new(dir));
+        }
+
+        let base = match std::env::var_os("XDG_CACHE_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => std::env::var_os("HOME")
+                .map(|home| Path::new(&home).join(".cache"))
+                .ok_or_else(|| anyhow!("Could not determine the cache directory"))?,
+        };
+
+        Ok(Self::new(base.join("aoc")))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the input for `day` of `year` is (or would be) stored.
+    pub fn path(&self, year: usize, day: usize) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day-{:03}.txt", day))
+    }
+
+    /// The path to the input for `day` of `year`, if it has been cached.
+    pub fn get(&self, year: usize, day: usize) -> Option<PathBuf> {
+        Some(self.path(year, day)).filter(|path| path.is_file())
+    }
+
+    pub fn store(&self, year: usize, day: usize, input: &str) -> Result<PathBuf> {
+        let path = self.path(year, day);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+        std::fs::write(&path, input)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+
+        Ok(path)
+    }
+
+    /// Every cached input, ordered by year then day.
+    pub fn list(&self) -> Result<Vec<Cached>> {
+        let mut cached = Vec::new();
+        if !self.root.is_dir() {
+            return Ok(cached);
+        }
+
+        for year_dir in read_dir(&self.root)? {
+            let year = match parse_name(&year_dir, "", "") {
+                Some(year) if year_dir.is_dir() => year,
+                _ => continue,
+            };
+
+            for path in read_dir(&year_dir)? {
+                if let Some(day) = parse_name(&path, "day-", ".txt") {
+                    cached.push(Cached { year, day, path });
+                }
+            }
+        }
+
+        cached.sort_by_key(|c| (c.year, c.day));
+
+        Ok(cached)
+    }
+
+    /// Remove the cached inputs for `year`, or every year if `None`,
+    /// returning how many were removed.
+    pub fn clean(&self, year: Option<usize>) -> Result<usize> {
+        let mut removed = 0;
+        for cached in self.list()? {
+            if year.map(|y| y == cached.year).unwrap_or(true) {
+                std::fs::remove_file(&cached.path)
+                    .with_context(|| format!("Could not remove {}", cached.path.display()))?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::read_dir(dir)
+        .with_context(|| format!("Could not read directory {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .collect()
+}
+
+/// The number in a file name like `<prefix>N<suffix>`.
+fn parse_name(path: &Path, prefix: &str, suffix: &str) -> Option<usize> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok()
+}
+
+/// Manage the inputs cached from the AoC website.
+///
+/// Cached inputs are used by every command when no input path is given and
+/// the input isn't in the workspace. The cache lives under `$AOC_CACHE_DIR`,
+/// or `aoc` in the XDG cache directory.
+#[derive(Args)]
+pub(crate) struct Cache {
+    #[command(subcommand)]
+    command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Download the input for a day into the cache.
+    Fetch {
+        /// The day to fetch.
+        day: usize,
+
+        /// Fetch the input even if it's already cached.
+        #[arg(short, long)]
+        force: bool,
+
+        #[command(flatten)]
+        session: Session,
+    },
+
+    /// List the cached inputs.
+    List,
+
+    /// Remove the cached inputs for the year.
+    Clean {
+        /// Remove the cached inputs for every year.
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Display the cache directory, or where the input for a day is cached.
+    Path {
+        /// The day to display the path for.
+        day: Option<usize>,
+    },
+}
+
+impl Cache {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let cache = InputCache::from_env()?;
+
+        match &self.command {
+            CacheCommand::Fetch {
+                day,
+                force,
+                session,
+            } => {
+                if let Some(path) = cache.get(year, *day).filter(|_| !force) {
+                    println!("already cached: {}", path.display());
+                    return Ok(());
+                }
+
+                let input = session.client(year).input(*day)?;
+                println!("cached: {}", cache.store(year, *day, &input)?.display());
+            }
+            CacheCommand::List => {
+                for cached in cache.list()? {
+                    println!(
+                        "{} {:03} {}",
+                        cached.year,
+                        cached.day,
+                        cached.path.display()
+                    );
+                }
+            }
+            CacheCommand::Clean { all } => {
+                let removed = cache.clean(if *all { None } else { Some(year) })?;
+                println!("removed {} cached input(s)", removed);
+            }
+            CacheCommand::Path { day } => match day {
+                Some(day) => println!("{}", cache.path(year, *day).display()),
+                None => println!("{}", cache.root().display()),
+            },
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_list_clean() {
+        let root = std::env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = InputCache::new(&root);
+
+        assert_eq!(cache.list().unwrap(), Vec::new());
+        assert_eq!(cache.get(2022, 3), None);
+
+        let path = cache.store(2022, 3, "abc").unwrap();
+        assert_eq!(path, root.join("2022").join("day-003.txt"));
+        assert_eq!(cache.get(2022, 3), Some(path.clone()));
+        cache.store(2022, 1, "1").unwrap();
+        cache.store(2021, 25, "25").unwrap();
+        // unrelated files are ignored
+        std::fs::write(root.join("2022").join("notes.md"), "").unwrap();
+
+        let listed = cache
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.year, c.day))
+            .collect::<Vec<_>>();
+        assert_eq!(listed, [(2021, 25), (2022, 1), (2022, 3)]);
+
+        assert_eq!(cache.clean(Some(2022)).unwrap(), 2);
+        assert_eq!(cache.list().unwrap().len(), 1);
+        assert_eq!(cache.clean(None).unwrap(), 1);
+        assert!(cache.list().unwrap().is_empty());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
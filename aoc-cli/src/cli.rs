@@ -1,192 +1,760 @@
 use std::{
-    marker::PhantomData,
     path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
-use anyhow::{anyhow, Context, Result};
-use aoc_plumbing::Problem;
-use beacon_exclusion_zone::BeaconExclusionZone;
-use blizzard_basin::BlizzardBasin;
-use boiling_boulders::BoilingBoulders;
-use calorie_counting::CalorieCounting;
-use camp_cleanup::CampCleanup;
-use cathode_ray_tube::CathodeRayTube;
-use clap::{Args, CommandFactory, Parser, Subcommand};
-use clap_complete::{generate, shells::Zsh};
-use distress_signal::DistressSignal;
-use full_of_hot_air::FullOfHotAir;
-use grove_positioning_system::GrovePositioningSystem;
-use hill_climbing_algorithm::HillClimbingAlgorithm;
-use monkey_in_the_middle::MonkeyInTheMiddle;
-use monkey_map::MonkeyMap;
-use monkey_math::MonkeyMath;
-use no_space_left_on_device::NoSpaceLeftOnDevice;
-use not_enough_minerals::NotEnoughMinerals;
-use proboscidea_volcanium::ProboscideaVolcanium;
-use pyroclastic_flow::PyroclasticFlow;
-use regolith_reservoir::RegolithReservoir;
-use rock_paper_scissors::RockPaperScissors;
-use rope_bridge::RopeBridge;
-use rucksack_reorganization::RucksackReorganization;
-use supply_stacks::SupplyStacks;
-use treetop_tree_house::TreetopTreeHouse;
-use tuning_trouble::TuningTrouble;
-use unstable_diffusion::UnstableDiffusion;
-// import_marker
-
-// I'm not proud
-macro_rules! generate_cli {
-    ($(($name:ident, $day:literal)),* $(,)?) => {
-        #[derive(Parser)]
-        pub(crate) struct Cli {
-            #[command(subcommand)]
-            pub command: Commands,
-        }
-
-        impl Cli {
-            pub fn run() -> Result<()> {
-                let command = Self::parse().command;
-                command.run()
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_plumbing::{
+    metadata::Metadata,
+    registry::{self, Day, ErasedProblem},
+    AocError, Check, Timings,
+};
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clap_complete::{generate, Shell};
+use rayon::prelude::*;
+use tracing::{info_span, Level};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+#[cfg(feature = "profile")]
+use crate::profile::Profile;
+#[cfg(feature = "tui")]
+use crate::tui::Tui;
+use crate::{
+    api::{Session, SubmitOutcome, YEAR},
+    bench::Bench,
+    cache::Cache,
+    compare::Compare,
+    lint::LintInput,
+    list::List,
+    output::{OutputArgs, OutputFormat, Render},
+    progress::with_progress_bar,
+    readme::Readme,
+    registry::{for_year, lookup},
+    report::{
+        answer_value, find_input, is_day_input, solve_timed, with_timeout, DayReport, Report,
+        RunOutput, Skipped,
+    },
+    scaffold::New,
+    serve::Serve,
+    stats::Stats,
+    time_all::TimeAll,
+    verify::{Answers, Expected, Verify},
+    visualize::Visualize,
+    watch::Watch,
+};
+
+#[derive(Parser)]
+pub(crate) struct Cli {
+    #[command(flatten)]
+    pub globals: Globals,
+
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+impl Cli {
+    pub fn run() -> Result<()> {
+        let matches = Self::with_days().get_matches();
+        let globals = Globals::from_arg_matches(&matches)?;
+        init_tracing(globals.verbose);
+        init_threads(globals.threads)?;
+
+        if let Some((name, matches)) = matches.subcommand() {
+            if let Some(day) = registry::days().into_iter().find(|d| d.crate_name == name) {
+                return Solver::from_arg_matches(matches)?.run(day);
             }
         }
 
-        #[derive(Subcommand)]
-        pub(crate) enum Commands {
-            $(
-            #[command(about = $name::problem_label(), long_about = $name::long_description(), display_order = $day)]
-            $name(Solver<$name>),
-            )*
+        Self::from_arg_matches(&matches)?.command.run(globals.year)
+    }
+
+    /// The command line, with a subcommand for every registered day. These
+    /// aren't part of [Commands], as the days are only known once they've
+    /// registered themselves.
+    fn with_days() -> clap::Command {
+        registry::days()
+            .into_iter()
+            .fold(Self::command(), |cli, day| {
+                cli.subcommand(
+                    Solver::augment_args(clap::Command::new(day.crate_name))
+                        .about(day.label())
+                        .long_about(format!("{:03} {}", day.day, day.readme))
+                        .display_order(day.day),
+                )
+            })
+    }
+}
+
+// not a doc comment, as that would become the about of the whole cli
+#[derive(Args)]
+pub(crate) struct Globals {
+    /// The event year, for the commands that aren't specific to a single
+    /// solution.
+    #[arg(short, long, global = true, env = "AOC_YEAR", default_value_t = YEAR)]
+    pub year: usize,
+
+    /// Log the progress and timing of the solutions to stderr. Repeat for
+    /// more detail.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The number of threads the parallel solutions (and `run-all
+    /// --parallel`) may use. Defaults to the number of cores.
+    #[arg(long, global = true, env = "AOC_THREADS", value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum Commands {
+    #[command(display_order = 30)]
+    Run(Run),
+
+    #[command(display_order = 31)]
+    GenerateCompletions(GenerateCompletions),
+
+    #[command(display_order = 32)]
+    Submit(Submit),
+
+    #[command(display_order = 33)]
+    New(New),
+
+    #[command(display_order = 34)]
+    RunAll(RunAll),
+
+    #[command(display_order = 35)]
+    Verify(Verify),
 
-            #[command(display_order = 30)]
-            Run(Run),
+    #[command(display_order = 36)]
+    Bench(Bench),
 
-            #[command(display_order = 31)]
-            GenerateCompletions(GenerateCompletions),
+    #[command(display_order = 37)]
+    Watch(Watch),
+
+    #[cfg(feature = "tui")]
+    #[command(display_order = 38)]
+    Tui(Tui),
+
+    #[command(display_order = 39)]
+    Readme(Readme),
+
+    #[command(display_order = 40)]
+    Stats(Stats),
+
+    #[command(display_order = 41)]
+    Visualize(Visualize),
+
+    #[command(display_order = 42)]
+    LintInput(LintInput),
+
+    #[command(display_order = 43)]
+    Compare(Compare),
+
+    #[cfg(feature = "profile")]
+    #[command(display_order = 44)]
+    Profile(Profile),
+
+    #[command(display_order = 45)]
+    Cache(Cache),
+
+    #[command(display_order = 46)]
+    List(List),
+
+    #[command(display_order = 47)]
+    Serve(Serve),
+
+    #[command(display_order = 48)]
+    TimeAll(TimeAll),
+}
+
+impl Commands {
+    pub fn run(&self, year: usize) -> Result<()> {
+        match self {
+            Self::GenerateCompletions(cmd) => cmd.run(),
+            Self::Run(cmd) => cmd.run(year),
+            Self::Submit(cmd) => cmd.run(year),
+            Self::New(cmd) => cmd.run(year),
+            Self::RunAll(cmd) => cmd.run(year),
+            Self::Verify(cmd) => cmd.run(year),
+            Self::Bench(cmd) => cmd.run(),
+            Self::Watch(cmd) => cmd.run(year),
+            #[cfg(feature = "tui")]
+            Self::Tui(cmd) => cmd.run(year),
+            Self::Readme(cmd) => cmd.run(year),
+            Self::Stats(cmd) => cmd.run(year),
+            Self::Visualize(cmd) => cmd.run(year),
+            Self::LintInput(cmd) => cmd.run(year),
+            Self::Compare(cmd) => cmd.run(year),
+            #[cfg(feature = "profile")]
+            Self::Profile(cmd) => cmd.run(year),
+            Self::Cache(cmd) => cmd.run(year),
+            Self::List(cmd) => cmd.run(year),
+            Self::Serve(cmd) => cmd.run(year),
+            Self::TimeAll(cmd) => cmd.run(year),
         }
+    }
+}
 
-        impl Commands {
-            pub fn run(&self) -> Result<()> {
-                match self {
-                    Self::GenerateCompletions(cmd) => cmd.run(),
-                    Self::Run(cmd) => cmd.run(),
-                    $(
-                    Self::$name(cmd) => cmd.run(),
-                    )*
-                }
-            }
+/// Run the solution for a specified day.
+///
+/// The day must be implemented and the input must exist. If no input is
+/// given, it is expected at `<INPUT_ROOT>/day-XXX-*/input.txt` or in the
+/// input cache.
+#[derive(Args)]
+pub(crate) struct Run {
+    /// The day to run.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    options: SolveOptions,
+}
+
+impl Run {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let day = lookup(year, self.day)?;
+        if self.input.example {
+            run_limited(&self.options, move |options| _run_examples(day, options))
+        } else {
+            let input = self.input.resolve(year, self.day)?;
+            run_limited(&self.options, move |options| _run(day, &input, options))
         }
+    }
+}
 
-        /// Run the solution for a specified day.
-        ///
-        /// The day must be implemented and the specified input must exist.
-        #[derive(Args)]
-        pub(crate) struct Run {
-            /// The day to run.
-            day: usize,
+// the subcommand for a single day, named after the day's crate. Its about is
+// the day's label, rather than this
+#[derive(Args)]
+pub(crate) struct Solver {
+    #[command(flatten)]
+    input: InputArgs,
 
-            /// The path to the input for this solution.
-            input: PathBuf,
+    #[command(flatten)]
+    options: SolveOptions,
+}
 
-            /// Display the output as json.
-            #[clap(short, long)]
-            json: bool,
+impl Solver {
+    pub fn run(&self, day: &'static Day) -> Result<()> {
+        if self.input.example {
+            run_limited(&self.options, move |options| _run_examples(day, options))
+        } else {
+            let input = self.input.resolve(day.year, day.day)?;
+            run_limited(&self.options, move |options| _run(day, &input, options))
         }
+    }
+}
 
-        impl Run {
-            pub fn run(&self) -> Result<()> {
-                match self.day {
-                    $(
-                    $day => _run::<$name>(&self.input, self.json),
-                    )*
-                    _ => Err(anyhow!("Unknown day: {}", self.day))
-                }
-            }
+/// Where to find the input for a single day.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct InputArgs {
+    /// The path to the input for this solution. Defaults to
+    /// `<INPUT_ROOT>/day-XXX-*/input.txt`, then the input cache.
+    input: Option<PathBuf>,
+
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+
+    /// Run against the examples embedded in the solution instead, checking
+    /// their answers.
+    #[clap(short, long, conflicts_with = "input")]
+    pub example: bool,
+}
+
+impl InputArgs {
+    pub fn resolve(&self, year: usize, day: usize) -> Result<PathBuf> {
+        match &self.input {
+            Some(input) => Ok(input.clone()),
+            None => find_input(&self.input_root, year, day),
         }
-    };
+    }
 }
 
-#[derive(Args)]
-pub(crate) struct Solver<T>
-where
-    T: Problem,
-{
-    /// The path to the input for this solution.
-    input: PathBuf,
+/// Which part(s) of a solution to run.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, ValueEnum)]
+pub(crate) enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+/// Options shared by the per-day subcommands and `run`.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct SolveOptions {
+    #[command(flatten)]
+    output: OutputArgs,
 
-    /// Display the output as json.
+    /// Only run the given part. Part one is still solved for part two, as
+    /// some days rely on it, but only part two's answer is displayed.
+    #[clap(short, long, value_enum, default_value_t)]
+    part: Part,
+
+    /// Display how long parsing and each part took.
     #[clap(short, long)]
-    json: bool,
+    time: bool,
+
+    /// Check the answers against the ones recorded in this file, exiting
+    /// nonzero if they differ. A day's own `input.txt` is checked against
+    /// the answers embedded in the day otherwise.
+    #[clap(long, env = "AOC_ANSWERS")]
+    answers: Option<PathBuf>,
+
+    /// Only parse the input, displaying a summary of it (sizes, counts,
+    /// bounds) instead of solving it.
+    #[clap(long, conflicts_with_all = ["part", "time", "answers", "json", "output", "metadata"])]
+    parse_only: bool,
+
+    /// Include the version, git commit, input hash, hostname, and time in
+    /// the output, for archiving it. Not displayed as text.
+    #[clap(long)]
+    metadata: bool,
+
+    /// Give up if the solution hasn't finished after this many seconds.
+    #[clap(long, value_name = "SECS")]
+    timeout: Option<u64>,
 
-    #[clap(skip)]
-    _phantom: PhantomData<T>,
+    /// Draw a progress bar on stderr for days that report their progress
+    /// (16, 19, and 24).
+    #[clap(long)]
+    progress: bool,
 }
 
-impl<T> Solver<T>
-where
-    T: Problem,
-    <T as Problem>::ProblemError: Into<anyhow::Error>,
-{
-    pub fn run(&self) -> Result<()> {
-        _run::<T>(&self.input, self.json)
+impl SolveOptions {
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
     }
 }
 
-fn _run<T>(input_file: &Path, json: bool) -> Result<()>
+/// Run a solution (against either an input or the examples), giving up after
+/// `--timeout`, with a progress bar if there should be one.
+pub(crate) fn run_limited<F>(options: &SolveOptions, run: F) -> Result<()>
 where
-    T: Problem,
-    <T as Problem>::ProblemError: Into<anyhow::Error>,
+    F: FnOnce(&SolveOptions) -> Result<()> + Send + 'static,
 {
+    let limit = options.timeout();
+    let progress = options.progress;
+    let options = options.clone();
+    let limited = move || with_timeout(limit, move || run(&options))?;
+
+    if progress {
+        with_progress_bar(limited)
+    } else {
+        limited()
+    }
+}
+
+/// Log spans (with their timings) and events to stderr, at a level depending on
+/// how many times `-v` was given. Nothing is logged by default.
+fn init_tracing(verbosity: u8) {
+    let level = match verbosity {
+        0 => return,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+/// Size the global rayon pool before anything uses it, otherwise it's created
+/// with one thread per core.
+fn init_threads(threads: Option<u16>) -> Result<()> {
+    if let Some(threads) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads as usize)
+            .build_global()
+            .context("Failed to configure the thread pool")?;
+    }
+
+    Ok(())
+}
+
+/// Solve `day` and print the solution.
+pub(crate) fn _run(day: &Day, input_file: &Path, options: &SolveOptions) -> Result<()> {
     let input = std::fs::read_to_string(input_file).context("Could not read input file")?;
+    let expected = match &options.answers {
+        Some(path) => Some(
+            Answers::load(path)?
+                .get(day.day)
+                .cloned()
+                .unwrap_or_default(),
+        ),
+        // the embedded answers are only for the day's own input
+        None if is_day_input(input_file, day.day) => day.expected.map(Expected::from),
+        None => None,
+    };
+    let mut checks = Vec::with_capacity(2);
 
-    let solution = T::solve(&input)
-        .map_err(Into::<anyhow::Error>::into)
-        .context("Failed to solve")?;
+    let start = Instant::now();
+    let mut inst = info_span!("parse", day = day.day)
+        .in_scope(|| (day.instance)(&input))
+        .map_err(AocError::into_anyhow)
+        .context("Failed to parse input")?;
+    let mut timings = Timings {
+        parse: start.elapsed(),
+        ..Default::default()
+    };
 
-    if json {
-        println!("{}", serde_json::to_string(&solution)?);
-    } else {
-        println!("{}", solution);
+    if options.parse_only {
+        print!("{}", summary(day, &*inst, Some(timings.parse)));
+        return Ok(());
+    }
+
+    let mut output = RunOutput::default();
+
+    // part one is solved even if only part two is wanted, as some days rely
+    // on state left behind by part one, but its answer isn't displayed
+    let start = Instant::now();
+    let answer = info_span!("part_one", day = day.day)
+        .in_scope(|| inst.part_one())
+        .map_err(AocError::into_anyhow)
+        .context("Failed to solve part one")?;
+    timings.part_one = start.elapsed();
+
+    if options.part != Part::Two {
+        output.part_one = answer_value(&answer);
+        if let Some(expected) = &expected {
+            checks.push((
+                1,
+                Check::new(expected.part_one().as_deref(), &answer.to_string()),
+            ));
+        }
+    }
+
+    if options.part != Part::One {
+        let start = Instant::now();
+        let answer = info_span!("part_two", day = day.day)
+            .in_scope(|| inst.part_two())
+            .map_err(AocError::into_anyhow)
+            .context("Failed to solve part two")?;
+        timings.part_two = start.elapsed();
+        output.part_two = answer_value(&answer);
+        if let Some(expected) = &expected {
+            checks.push((
+                2,
+                Check::new(expected.part_two().as_deref(), &answer.to_string()),
+            ));
+        }
+    }
+
+    if options.time {
+        output.timings = Some(timings);
+    }
+
+    if options.metadata {
+        output.metadata = Some(Metadata::collect(env!("CARGO_PKG_VERSION"), &input));
+    }
+
+    println!("{}", output.render(options.output.format())?);
+
+    // the diff goes to stderr so the output is still usable as-is
+    let mut failures = 0;
+    for (part, check) in checks {
+        match check {
+            Check::Fail { expected, actual } => {
+                failures += 1;
+                eprintln!(
+                    "\npart {} is wrong:\nexpected: {}\n     got: {}",
+                    part, expected, actual
+                );
+            }
+            Check::Unknown => eprintln!("no recorded answer for part {}", part),
+            Check::Pass => {}
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} answer(s) did not match", failures);
+    }
+
+    Ok(())
+}
+
+/// Solve `day` for each of its examples, checking their answers.
+pub(crate) fn _run_examples(day: &Day, options: &SolveOptions) -> Result<()> {
+    if day.examples.is_empty() {
+        bail!("Day {} has no embedded examples", day.day);
+    }
+
+    let mut failures = 0;
+
+    for (idx, example) in day.examples.iter().enumerate() {
+        println!("example {}:", idx + 1);
+
+        let mut inst = (day.instance)(example.input)
+            .map_err(AocError::into_anyhow)
+            .context("Failed to parse example")?;
+
+        if options.parse_only {
+            print!("{}", summary(day, &*inst, None));
+            continue;
+        }
+
+        // parts without an expected answer might not support the example, but
+        // part one is solved for part two even if it isn't displayed, and
+        // even without an answer if part two relies on it
+        let part_two = example.part_two.filter(|_| options.part != Part::One);
+        if example.part_one.is_some() || (day.linked_parts && part_two.is_some()) {
+            let answer = inst
+                .part_one()
+                .map_err(AocError::into_anyhow)
+                .context("Failed to solve part one")?;
+            if let Some(expected) = example.part_one.filter(|_| options.part != Part::Two) {
+                failures += !check_example(1, &answer.to_string(), expected) as usize;
+            }
+        }
+
+        if let Some(expected) = part_two {
+            let answer = inst
+                .part_two()
+                .map_err(AocError::into_anyhow)
+                .context("Failed to solve part two")?;
+            failures += !check_example(2, &answer.to_string(), expected) as usize;
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} example answer(s) did not match", failures);
     }
 
     Ok(())
 }
 
-/// Generate zsh completions
+/// Display the summary of a parsed instance, with the keys aligned.
+fn summary(day: &Day, inst: &dyn ErasedProblem, parse: Option<Duration>) -> String {
+    let mut out = String::new();
+    if let Some(parse) = parse {
+        out.push_str(&format!("parsed in {:.2?}\n", parse));
+    }
+
+    let facts = inst.summary();
+    if facts.is_empty() {
+        out.push_str(&format!("no summary available for day {}\n", day.day));
+    }
+
+    let width = facts.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    for (key, value) in facts {
+        out.push_str(&format!("{:>width$}: {}\n", key, value, width = width));
+    }
+
+    out
+}
+
+/// Display an example's answer, returning whether it was the expected one.
+fn check_example(part: usize, answer: &str, expected: &str) -> bool {
+    if answer == expected {
+        println!("part {} (ok): {}", part, answer);
+        true
+    } else {
+        println!("part {} (expected {}): {}", part, expected, answer);
+        false
+    }
+}
+
+/// Solve and submit the answer for a given day and part.
+#[derive(Args)]
+pub(crate) struct Submit {
+    /// The day to submit.
+    day: usize,
+
+    /// The part to submit (1 or 2).
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    session: Session,
+}
+
+impl Submit {
+    pub fn run(&self, year: usize) -> Result<()> {
+        if self.input.example {
+            bail!("Only the answers for the real input can be submitted");
+        }
+
+        let path = self.input.resolve(year, self.day)?;
+        let input = std::fs::read_to_string(path).context("Could not read input file")?;
+        let solution = lookup(year, self.day)?
+            .solve(&input)
+            .map_err(AocError::into_anyhow)
+            .context("Failed to solve")?;
+
+        let answer = match self.part {
+            1 => solution.part_one,
+            _ => solution.part_two,
+        };
+
+        // like the second part of the last day
+        if answer.is_empty() {
+            bail!("Day {} has no answer for part {}", self.day, self.part);
+        }
+
+        println!(
+            "submitting '{}' for day {} part {}",
+            answer, self.day, self.part
+        );
+        let outcome = self
+            .session
+            .client(year)
+            .submit(self.day, self.part as usize, &answer)?;
+        println!("{}", outcome);
+
+        match outcome {
+            SubmitOutcome::Correct | SubmitOutcome::AlreadySolved => Ok(()),
+            _ => Err(anyhow!("Submission was not accepted: {}", outcome)),
+        }
+    }
+}
+
+/// Run every implemented day against its input, displaying the answers and
+/// timings.
+///
+/// Inputs are expected at `<INPUT_ROOT>/day-XXX-*/input.txt` or in the input
+/// cache. Days without an input are skipped.
+#[derive(Args)]
+pub(crate) struct RunAll {
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+
+    /// Run the days concurrently across all available cores.
+    #[clap(short = 'P', long)]
+    parallel: bool,
+
+    /// Give up on a day if it hasn't finished after this many seconds,
+    /// reporting it as timed out.
+    #[clap(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl RunAll {
+    pub fn run(&self, year: usize) -> Result<()> {
+        // the inner error is for days we can't run at all
+        let run_day = |entry: &'static Day| -> Result<Result<DayReport, Skipped>> {
+            let day = entry.day;
+            let input = match find_input(&self.input_root, year, day) {
+                Ok(path) => std::fs::read_to_string(path).context("Could not read input file")?,
+                Err(e) => {
+                    return Ok(Err(Skipped {
+                        day,
+                        reason: e.to_string(),
+                    }))
+                }
+            };
+
+            match with_timeout(self.timeout.map(Duration::from_secs), move || {
+                solve_timed(entry, &input)
+            }) {
+                Ok(result) => result
+                    .with_context(|| format!("Failed to run day {}", day))
+                    .map(Ok),
+                Err(timed_out) => Ok(Err(Skipped {
+                    day,
+                    reason: timed_out.to_string(),
+                })),
+            }
+        };
+
+        let entries = for_year(year).collect::<Vec<_>>();
+
+        let start = Instant::now();
+        let results: Vec<_> = if self.parallel {
+            entries.into_par_iter().map(run_day).collect()
+        } else {
+            entries.into_iter().map(run_day).collect()
+        };
+        let wall = start.elapsed();
+
+        let mut days = Vec::with_capacity(results.len());
+        let mut skipped = Vec::new();
+        for result in results {
+            match result? {
+                Ok(mut report) => {
+                    // the peak is the whole process's, so it's another day's
+                    // as often as not
+                    if self.parallel {
+                        report.peak_rss = None;
+                    }
+                    days.push(report)
+                }
+                Err(skip) => skipped.push(skip),
+            }
+        }
+
+        let report = Report::new(days, skipped, wall);
+
+        match self.output.format() {
+            // the table already ends with a newline
+            OutputFormat::Text => print!("{}", report),
+            format => println!("{}", report.render(format)?),
+        }
+
+        Ok(())
+    }
+}
+
+/// Generate shell completions
 #[derive(Debug, Args)]
-pub struct GenerateCompletions;
+pub struct GenerateCompletions {
+    /// The shell to generate completions for.
+    #[arg(short, long, value_enum, default_value_t = Shell::Zsh)]
+    shell: Shell,
+}
 
 impl GenerateCompletions {
     fn run(&self) -> Result<()> {
-        generate(Zsh, &mut Cli::command(), "aoc", &mut std::io::stdout());
+        generate(
+            self.shell,
+            &mut Cli::with_days(),
+            "aoc",
+            &mut std::io::stdout(),
+        );
         Ok(())
     }
 }
 
-generate_cli! {
-    (CalorieCounting, 1),
-    (RockPaperScissors, 2),
-    (RucksackReorganization, 3),
-    (CampCleanup, 4),
-    (SupplyStacks, 5),
-    (TuningTrouble, 6),
-    (NoSpaceLeftOnDevice, 7),
-    (TreetopTreeHouse, 8),
-    (RopeBridge, 9),
-    (CathodeRayTube, 10),
-    (MonkeyInTheMiddle, 11),
-    (HillClimbingAlgorithm, 12),
-    (DistressSignal, 13),
-    (RegolithReservoir, 14),
-    (BeaconExclusionZone, 15),
-    (ProboscideaVolcanium, 16),
-    (PyroclasticFlow, 17),
-    (BoilingBoulders, 18),
-    (NotEnoughMinerals, 19),
-    (GrovePositioningSystem, 20),
-    (MonkeyMath, 21),
-    (MonkeyMap, 22),
-    (UnstableDiffusion, 23),
-    (BlizzardBasin, 24),
-    (FullOfHotAir, 25),
-    // command_marker
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answers_fail() {
+        let root = std::env::temp_dir().join(format!("aoc-run-test-{}", std::process::id()));
+        let dir = root.join("day-001-calorie-counting");
+        std::fs::create_dir_all(&dir).unwrap();
+        // the example, which doesn't have the embedded answers
+        let example = lookup(2022, 1).unwrap().examples[0].input;
+        std::fs::write(dir.join("input.txt"), example).unwrap();
+        std::fs::write(root.join("other.txt"), example).unwrap();
+        std::fs::write(root.join("answers.toml"), "[1]\npart_one = 24000\n").unwrap();
+
+        let run = |args: &[&str]| {
+            let root = root.to_string_lossy();
+            let cli =
+                Cli::try_parse_from(["aoc", "run", "1", "-i", &*root].iter().chain(args)).unwrap();
+            match cli.command {
+                Commands::Run(run) => run.run(2022),
+                _ => unreachable!(),
+            }
+        };
+
+        assert!(run(&[]).is_err());
+        assert!(run(&["--answers", &*root.join("answers.toml").to_string_lossy()]).is_ok());
+        assert!(run(&[&*root.join("other.txt").to_string_lossy()]).is_ok());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
 }
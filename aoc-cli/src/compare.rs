@@ -0,0 +1,239 @@
+//! Comparing the alternative implementations of a day.
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_plumbing::{Alternatives, Problem, Solution, SolutionDiff};
+use boiling_boulders::BoilingBoulders;
+use clap::Args;
+use grove_positioning_system::GrovePositioningSystem;
+
+use crate::{
+    cli::InputArgs,
+    report::{table_cell, write_table},
+};
+
+/// Solve a day against the given input with the named implementations, or
+/// every implementation if empty.
+type Run = fn(&str, &[String]) -> Result<Vec<Outcome>>;
+
+/// The days with alternative implementations, by year.
+const COMPARISONS: &[(usize, usize, Run)] = &[
+    (2022, 18, run::<BoilingBoulders>),
+    (2022, 20, run::<GrovePositioningSystem>),
+];
+
+/// Run the alternative implementations of a specified day against the same
+/// input, displaying their answers and timings side by side.
+///
+/// Exits nonzero if the implementations disagree on an answer.
+#[derive(Args)]
+pub(crate) struct Compare {
+    /// The day to compare the implementations of.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// The implementations to compare. Every implementation is compared if
+    /// omitted.
+    #[clap(long = "impl")]
+    implementations: Vec<String>,
+}
+
+impl Compare {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let (_, _, run) = COMPARISONS
+            .iter()
+            .find(|(y, d, _)| *y == year && *d == self.day)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Day {} of {} has no alternative implementations",
+                    self.day,
+                    year
+                )
+            })?;
+
+        if self.input.example {
+            bail!("Comparing against the examples is not supported, use `run --example`");
+        }
+
+        let path = self.input.resolve(year, self.day)?;
+        let input = std::fs::read_to_string(path).context("Could not read input file")?;
+
+        let comparison = Comparison {
+            outcomes: run(&input, &self.implementations)?,
+        };
+
+        print!("{}", comparison);
+
+        if !comparison.agrees() {
+            bail!("The implementations disagree");
+        }
+
+        Ok(())
+    }
+}
+
+/// The answers and timings of a single implementation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Outcome {
+    pub name: &'static str,
+    pub part_one: String,
+    pub part_two: String,
+    pub part_one_time: Duration,
+    pub part_two_time: Duration,
+}
+
+impl Outcome {
+    pub fn solution(&self) -> Solution<&str, &str> {
+        Solution::new(self.part_one.as_str(), self.part_two.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub(crate) struct Comparison {
+    pub outcomes: Vec<Outcome>,
+}
+
+impl Comparison {
+    pub fn agrees(&self) -> bool {
+        self.diffs().all(|(_, diff)| diff.is_empty())
+    }
+
+    /// How each implementation's answers differ from the first's.
+    fn diffs(&self) -> impl Iterator<Item = (&Outcome, SolutionDiff)> {
+        let first = self.outcomes.first().map(Outcome::solution);
+        self.outcomes
+            .iter()
+            .skip(1)
+            .filter_map(move |o| first.as_ref().map(|first| (o, first.diff(&o.solution()))))
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = ["impl", "part 1", "part 2", "part 1 time", "part 2 time"];
+        let rows = self
+            .outcomes
+            .iter()
+            .map(|o| {
+                [
+                    o.name.to_string(),
+                    table_cell(&o.part_one),
+                    table_cell(&o.part_two),
+                    format!("{:.2?}", o.part_one_time),
+                    format!("{:.2?}", o.part_two_time),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        write_table(f, &header, &rows)?;
+
+        for (o, diff) in self.diffs().filter(|(_, diff)| !diff.is_empty()) {
+            writeln!(
+                f,
+                "\n{} differs from {}:\n{}",
+                o.name, self.outcomes[0].name, diff
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn run<T>(input: &str, names: &[String]) -> Result<Vec<Outcome>>
+where
+    T: Alternatives,
+    <T as Problem>::ProblemError: Into<anyhow::Error>,
+{
+    if let Some(unknown) = names
+        .iter()
+        .find(|name| !T::IMPLEMENTATIONS.iter().any(|imp| imp.name == *name))
+    {
+        let known = T::IMPLEMENTATIONS
+            .iter()
+            .map(|imp| imp.name)
+            .collect::<Vec<_>>();
+        bail!(
+            "Unknown implementation '{}', expected one of: {}",
+            unknown,
+            known.join(", ")
+        );
+    }
+
+    T::IMPLEMENTATIONS
+        .iter()
+        .filter(|imp| names.is_empty() || names.iter().any(|name| name == imp.name))
+        .map(|imp| {
+            // parse separately for each implementation, so none of them
+            // benefit from state left behind by another
+            let mut inst = T::instance(input)
+                .map_err(T::ProblemError::from)
+                .map_err(Into::<anyhow::Error>::into)
+                .context("Failed to parse input")?;
+
+            let start = Instant::now();
+            let part_one = (imp.part_one)(&mut inst)
+                .map_err(Into::<anyhow::Error>::into)
+                .with_context(|| format!("{} failed to solve part one", imp.name))?
+                .to_string();
+            let part_one_time = start.elapsed();
+
+            let start = Instant::now();
+            let part_two = (imp.part_two)(&mut inst)
+                .map_err(Into::<anyhow::Error>::into)
+                .with_context(|| format!("{} failed to solve part two", imp.name))?
+                .to_string();
+            let part_two_time = start.elapsed();
+
+            Ok(Outcome {
+                name: imp.name,
+                part_one,
+                part_two,
+                part_one_time,
+                part_two_time,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(name: &'static str, part_two: &str) -> Outcome {
+        Outcome {
+            name,
+            part_one: "64".into(),
+            part_two: part_two.into(),
+            part_one_time: Duration::ZERO,
+            part_two_time: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn agreement() {
+        let mut comparison = Comparison {
+            outcomes: vec![outcome("a", "58"), outcome("b", "58")],
+        };
+        assert!(comparison.agrees());
+
+        comparison.outcomes.push(outcome("c", "59"));
+        assert!(!comparison.agrees());
+        assert!(comparison
+            .to_string()
+            .ends_with("c differs from a:\npart 2:\nexpected: 58\n     got: 59\n"));
+    }
+
+    #[test]
+    fn unknown_implementation() {
+        let err = run::<BoilingBoulders>("1,1,1", &["nope".into()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown implementation 'nope', expected one of: flood fill, recursive, iterative"
+        );
+    }
+}
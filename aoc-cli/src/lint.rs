@@ -0,0 +1,159 @@
+//! Checking that an input parses, with the location of any failure.
+use std::{fmt::Display, path::Path, time::Instant};
+
+use anyhow::{bail, Context, Result};
+use aoc_plumbing::{registry::Day, AocError};
+use clap::Args;
+
+use crate::{cli::InputArgs, registry::lookup};
+
+/// Parse the input for a specified day without solving it, reporting where
+/// parsing failed.
+///
+/// Locations are only available for the days that report an
+/// [AocError::Parse], which all the nom parsers do, otherwise just the error
+/// is displayed.
+#[derive(Args)]
+pub(crate) struct LintInput {
+    /// The day to check the input for.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+impl LintInput {
+    pub fn run(&self, year: usize) -> Result<()> {
+        if self.input.example {
+            bail!("The embedded examples are already checked by the tests");
+        }
+
+        let day = lookup(year, self.day)?;
+        let path = self.input.resolve(year, self.day)?;
+        let input = std::fs::read_to_string(&path).context("Could not read input file")?;
+
+        let start = Instant::now();
+        match parse(day, &input) {
+            Ok(()) => {
+                println!("{}: ok ({:.2?})", path.display(), start.elapsed());
+                Ok(())
+            }
+            Err(e) => {
+                match Diagnostic::from_error(&e) {
+                    Some(diagnostic) => eprint!("{}", diagnostic.with_path(&path)),
+                    None => eprintln!("{}: {:#}", path.display(), e),
+                }
+                bail!("Failed to parse {}", path.display());
+            }
+        }
+    }
+}
+
+/// Parse `input` for `day`, discarding the result.
+pub(crate) fn parse(day: &Day, input: &str) -> Result<()> {
+    (day.instance)(input)
+        .map_err(AocError::into_anyhow)
+        .map(|_| ())
+}
+
+/// Where in the input parsing failed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Diagnostic {
+    pub path: String,
+    /// 1-indexed.
+    pub line: usize,
+    /// 1-indexed, in chars.
+    pub column: usize,
+    /// The whitespace-delimited token at the location, if any.
+    pub token: Option<String>,
+    /// The whole line containing the location.
+    pub source: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Locate the failure described by `error`, if it was a parse error from
+    /// the plumbing.
+    pub fn from_error(error: &anyhow::Error) -> Option<Self> {
+        error
+            .chain()
+            .find_map(|e| match e.downcast_ref::<AocError>()? {
+                AocError::Parse {
+                    line,
+                    column,
+                    snippet,
+                    message,
+                } => Some(Self {
+                    path: "<input>".into(),
+                    line: *line,
+                    column: *column,
+                    token: snippet
+                        .chars()
+                        .skip(column - 1)
+                        .collect::<String>()
+                        .split_whitespace()
+                        .next()
+                        .map(String::from),
+                    source: snippet.clone(),
+                    message: message.clone(),
+                }),
+                _ => None,
+            })
+    }
+
+    fn with_path(mut self, path: &Path) -> Self {
+        self.path = path.display().to_string();
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let token = match &self.token {
+            Some(token) => format!("unexpected `{}`", token),
+            None => "unexpected end of input".into(),
+        };
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter, self.path, self.line, self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source)?;
+        writeln!(f, "{} | {}^ {}", gutter, " ".repeat(self.column - 1), token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_error() {
+        let input = "move 1 from 1 to 2\nmove x from 2 to 1\n";
+        let error: anyhow::Error = AocError::parse(input, &input[24..], "Digit")
+            .unwrap()
+            .into();
+        let error = error.context("Failed to parse");
+
+        let diagnostic = Diagnostic::from_error(&error).unwrap();
+        assert_eq!(diagnostic.line, 2);
+        assert_eq!(diagnostic.column, 6);
+        assert_eq!(diagnostic.token.as_deref(), Some("x"));
+        assert_eq!(diagnostic.source, "move x from 2 to 1");
+        assert_eq!(
+            diagnostic.to_string(),
+            "error: Digit\n --> <input>:2:6\n  |\n2 | move x from 2 to 1\n  |      ^ unexpected `x`\n"
+        );
+
+        let error = AocError::parse(input, "", "Tag").unwrap().into();
+        let diagnostic = Diagnostic::from_error(&error).unwrap();
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 1));
+        assert_eq!(diagnostic.token, None);
+
+        assert!(Diagnostic::from_error(&anyhow::anyhow!("nope")).is_none());
+    }
+}
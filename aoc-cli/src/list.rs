@@ -0,0 +1,130 @@
+//! Listing the implemented days and what is available for each of them.
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use aoc_plumbing::registry::Day;
+use clap::Args;
+use serde::Serialize;
+
+use crate::{
+    output::{OutputArgs, Render},
+    registry::for_year,
+    report::{find_input, write_table},
+    verify::Answers,
+};
+
+/// List every implemented day, with the crate implementing it and whether
+/// an input and recorded answers are available.
+///
+/// Use `--json` for a machine-readable listing.
+#[derive(Args)]
+pub(crate) struct List {
+    /// Use the answers in this file instead of the ones embedded in each
+    /// day, for inputs other than the ones the days were written against.
+    #[clap(short, long)]
+    answers: Option<PathBuf>,
+
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+impl List {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let answers = Answers::resolve(self.answers.as_deref(), year)?;
+
+        let listing = Listing {
+            days: for_year(year)
+                .map(|entry| DayInfo::new(entry, &answers, &self.input_root))
+                .collect(),
+        };
+
+        println!("{}", listing.render(self.output.format())?);
+
+        Ok(())
+    }
+}
+
+/// What is known about a single implemented day.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct DayInfo {
+    pub year: usize,
+    pub day: usize,
+    pub title: &'static str,
+    #[serde(rename = "crate")]
+    pub crate_name: &'static str,
+    /// Whether an input was found in the input root or the input cache.
+    pub input: bool,
+    /// Whether answers are recorded for the day.
+    pub answers: bool,
+}
+
+impl DayInfo {
+    fn new(entry: &Day, answers: &Answers, input_root: &Path) -> Self {
+        Self {
+            year: entry.year,
+            day: entry.day,
+            title: entry.title,
+            crate_name: entry.crate_name,
+            input: find_input(input_root, entry.year, entry.day).is_ok(),
+            answers: answers.get(entry.day).is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub(crate) struct Listing {
+    pub days: Vec<DayInfo>,
+}
+
+impl Listing {
+    pub const HEADER: [&'static str; 6] = ["year", "day", "title", "crate", "input", "answers"];
+
+    pub fn rows(&self) -> Vec<[String; 6]> {
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        self.days
+            .iter()
+            .map(|d| {
+                [
+                    d.year.to_string(),
+                    d.day.to_string(),
+                    d.title.to_string(),
+                    d.crate_name.to_string(),
+                    yes_no(d.input),
+                    yes_no(d.answers),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Display for Listing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_table(f, &Self::HEADER, &self.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_names() {
+        let entry = for_year(2022).find(|e| e.day == 17).unwrap();
+        assert_eq!(entry.crate_name, "pyroclastic-flow");
+
+        let info = DayInfo::new(entry, &Answers::default(), Path::new("nowhere"));
+        assert_eq!(info.title, entry.title);
+        assert!(!info.answers);
+        assert_eq!(
+            serde_json::to_value(&info).unwrap()["crate"],
+            "pyroclastic-flow"
+        );
+    }
+}
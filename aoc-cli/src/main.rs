@@ -1,4 +1,29 @@
+// links every day, so they're all registered
+extern crate aoc as _;
+
+mod api;
+mod bench;
+mod cache;
 mod cli;
+mod compare;
+mod lint;
+mod list;
+mod output;
+#[cfg(feature = "profile")]
+mod profile;
+mod progress;
+mod readme;
+mod registry;
+mod report;
+mod scaffold;
+mod serve;
+mod stats;
+mod time_all;
+#[cfg(feature = "tui")]
+mod tui;
+mod verify;
+mod visualize;
+mod watch;
 
 pub fn main() -> Result<(), anyhow::Error> {
     cli::Cli::run()
@@ -0,0 +1,230 @@
+//! Rendering solutions and reports in the various supported formats.
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+
+use crate::{
+    list::Listing,
+    report::{answer_str, Report, RunOutput},
+};
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+    Toml,
+}
+
+/// Output selection shared by every command that displays solutions.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct OutputArgs {
+    /// Display the output as json (shorthand for `--output json`).
+    #[clap(short, long, conflicts_with = "output")]
+    json: bool,
+
+    /// The format to display the output in.
+    #[clap(short, long, value_enum)]
+    output: Option<OutputFormat>,
+}
+
+impl OutputArgs {
+    pub fn format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.output.unwrap_or_default()
+        }
+    }
+}
+
+/// Something that can be rendered in every [OutputFormat].
+pub(crate) trait Render {
+    fn render(&self, format: OutputFormat) -> Result<String>;
+}
+
+impl Render for RunOutput {
+    fn render(&self, format: OutputFormat) -> Result<String> {
+        let mut header = Vec::with_capacity(6);
+        let mut row = Vec::with_capacity(6);
+        for (name, answer) in [("part_one", &self.part_one), ("part_two", &self.part_two)] {
+            if let Some(answer) = answer {
+                header.push(name.to_string());
+                row.push(answer_str(answer));
+            }
+        }
+
+        if let Some(timings) = &self.timings {
+            header.push("parse_ns".into());
+            row.push(timings.parse.as_nanos().to_string());
+            if self.part_one.is_some() {
+                header.push("part_one_ns".into());
+                row.push(timings.part_one.as_nanos().to_string());
+            }
+            if self.part_two.is_some() {
+                header.push("part_two_ns".into());
+                row.push(timings.part_two.as_nanos().to_string());
+            }
+        }
+
+        for (name, value) in self.metadata.iter().flat_map(|m| m.record()) {
+            header.push(name.to_string());
+            row.push(value);
+        }
+
+        Ok(match format {
+            OutputFormat::Text => self.to_string(),
+            OutputFormat::Json => serde_json::to_string(self)?,
+            OutputFormat::Toml => toml::to_string(self)?,
+            OutputFormat::Csv => csv(&header, &[row]),
+            OutputFormat::Markdown => markdown(&header, &[row]),
+        })
+    }
+}
+
+impl Render for Report {
+    fn render(&self, format: OutputFormat) -> Result<String> {
+        let header = [
+            "day",
+            "title",
+            "part_one",
+            "part_two",
+            "parse_ns",
+            "part_one_ns",
+            "part_two_ns",
+            "total_ns",
+        ]
+        .map(String::from);
+        let rows = self
+            .days
+            .iter()
+            .map(|d| {
+                vec![
+                    d.day.to_string(),
+                    d.title.to_string(),
+                    d.part_one.clone(),
+                    d.part_two.clone(),
+                    d.timings.parse.as_nanos().to_string(),
+                    d.timings.part_one.as_nanos().to_string(),
+                    d.timings.part_two.as_nanos().to_string(),
+                    d.timings.total().as_nanos().to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        Ok(match format {
+            OutputFormat::Text => self.to_string(),
+            OutputFormat::Json => serde_json::to_string(self)?,
+            OutputFormat::Toml => toml::to_string(self)?,
+            OutputFormat::Csv => csv(&header, &rows),
+            OutputFormat::Markdown => markdown(&header, &rows),
+        })
+    }
+}
+
+impl Render for Listing {
+    fn render(&self, format: OutputFormat) -> Result<String> {
+        let header = Self::HEADER.map(String::from);
+        let rows = self
+            .rows()
+            .into_iter()
+            .map(|row| row.to_vec())
+            .collect::<Vec<_>>();
+
+        Ok(match format {
+            OutputFormat::Text => self.to_string(),
+            OutputFormat::Json => serde_json::to_string(self)?,
+            OutputFormat::Toml => toml::to_string(self)?,
+            OutputFormat::Csv => csv(&header, &rows),
+            OutputFormat::Markdown => markdown(&header, &rows),
+        })
+    }
+}
+
+fn csv(header: &[String], rows: &[Vec<String>]) -> String {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    std::iter::once(header)
+        .chain(rows.iter().map(|r| r.as_slice()))
+        .map(|r| r.iter().map(|v| field(v)).collect::<Vec<_>>().join(","))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn markdown(header: &[String], rows: &[Vec<String>]) -> String {
+    fn cell(value: &str) -> String {
+        // multi-line answers (like day 10's) need to stay in their cell
+        if value.contains('\n') {
+            format!(
+                "<pre>{}</pre>",
+                value.trim_matches('\n').replace('\n', "<br>")
+            )
+        } else {
+            value.replace('|', "\\|")
+        }
+    }
+
+    let mut lines = Vec::with_capacity(rows.len() + 2);
+    lines.push(format!("| {} |", header.join(" | ")));
+    lines.push(format!(
+        "|{}|",
+        header.iter().map(|_| "---").collect::<Vec<_>>().join("|")
+    ));
+    for row in rows {
+        lines.push(format!(
+            "| {} |",
+            row.iter().map(|v| cell(v)).collect::<Vec<_>>().join(" | ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{registry::lookup, report::answer_value};
+
+    use super::*;
+
+    #[test]
+    fn day_without_a_part_two_as_toml() {
+        let day = lookup(2022, 25).unwrap();
+        let mut inst = (day.instance)(day.examples[0].input).unwrap();
+        let output = RunOutput {
+            part_one: answer_value(&inst.part_one().unwrap()),
+            part_two: answer_value(&inst.part_two().unwrap()),
+            ..Default::default()
+        };
+
+        assert_eq!(output.part_two, None);
+        assert_eq!(
+            output.render(OutputFormat::Toml).unwrap(),
+            "part_one = \"2=-1=0\"\n"
+        );
+    }
+
+    #[test]
+    fn tables() {
+        let header = ["a", "b"].map(String::from);
+        let rows = vec![
+            vec!["1".to_string(), "x,y".to_string()],
+            vec!["say \"hi\"".to_string(), "\n#.\n.#".to_string()],
+        ];
+
+        assert_eq!(
+            csv(&header, &rows),
+            "a,b\n1,\"x,y\"\n\"say \"\"hi\"\"\",\"\n#.\n.#\""
+        );
+        assert_eq!(
+            markdown(&header, &rows),
+            "| a | b |\n|---|---|\n| 1 | x,y |\n| say \"hi\" | <pre>#.<br>.#</pre> |"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Sampling a solution and rendering flamegraphs of where it spends its time.
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use aoc_plumbing::{
+    registry::{Day, ErasedProblem},
+    AocError,
+};
+use clap::Args;
+use pprof::ProfilerGuard;
+
+use crate::{cli::InputArgs, registry::lookup};
+
+/// Profile the solution for a specified day, writing a flamegraph for each
+/// part.
+///
+/// The flamegraphs are written to `<OUTPUT_DIR>/day-XXX-part-N.svg`. Parsing
+/// (and, for part two, solving part one) happens before sampling begins, so
+/// each flamegraph only contains the part itself.
+#[derive(Args)]
+pub(crate) struct Profile {
+    /// The day to profile.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    options: ProfileOptions,
+}
+
+impl Profile {
+    pub fn run(&self, year: usize) -> Result<()> {
+        if self.input.example {
+            bail!("The examples are too small to profile meaningfully");
+        }
+
+        let day = lookup(year, self.day)?;
+        let path = self.input.resolve(year, self.day)?;
+        let input = std::fs::read_to_string(path).context("Could not read input file")?;
+
+        std::fs::create_dir_all(&self.options.output_dir)
+            .with_context(|| format!("Could not create {}", self.options.output_dir.display()))?;
+
+        for written in profile(day, &input, &self.options)? {
+            println!("wrote {}", written.display());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ProfileOptions {
+    /// How many times to solve each part while sampling. Increase this for
+    /// the faster days, which may otherwise finish before many samples are
+    /// taken.
+    #[clap(short = 'n', long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// How many samples to take per second.
+    #[clap(long, default_value_t = 997)]
+    frequency: i32,
+
+    /// The directory to write the flamegraphs to.
+    #[clap(short, long, default_value = ".")]
+    output_dir: PathBuf,
+}
+
+/// Profile each part of `day`, returning the paths of the flamegraphs.
+pub(crate) fn profile(day: &Day, input: &str, options: &ProfileOptions) -> Result<Vec<PathBuf>> {
+    let instances = || -> Result<Vec<Box<dyn ErasedProblem + '_>>> {
+        (0..options.iterations)
+            .map(|_| {
+                (day.instance)(input)
+                    .map_err(AocError::into_anyhow)
+                    .context("Failed to parse input")
+            })
+            .collect()
+    };
+
+    let part_one = |insts: &mut [Box<dyn ErasedProblem + '_>]| -> Result<()> {
+        for inst in insts.iter_mut() {
+            inst.part_one()
+                .map_err(AocError::into_anyhow)
+                .context("Failed to solve part one")?;
+        }
+        Ok(())
+    };
+
+    let mut written = Vec::with_capacity(2);
+
+    let mut insts = instances()?;
+    let guard = start(options)?;
+    part_one(&mut insts)?;
+    written.push(flamegraph(guard, options, day.day, 1)?);
+
+    // some days rely on state left behind by part one
+    let mut insts = instances()?;
+    part_one(&mut insts)?;
+    let guard = start(options)?;
+    for inst in insts.iter_mut() {
+        inst.part_two()
+            .map_err(AocError::into_anyhow)
+            .context("Failed to solve part two")?;
+    }
+    written.push(flamegraph(guard, options, day.day, 2)?);
+
+    Ok(written)
+}
+
+fn start(options: &ProfileOptions) -> Result<ProfilerGuard<'static>> {
+    pprof::ProfilerGuardBuilder::default()
+        .frequency(options.frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .context("Failed to start the profiler")
+}
+
+fn flamegraph(
+    guard: ProfilerGuard<'_>,
+    options: &ProfileOptions,
+    day: usize,
+    part: usize,
+) -> Result<PathBuf> {
+    let report = guard
+        .report()
+        .build()
+        .context("Failed to build the profile")?;
+
+    let path = output_path(&options.output_dir, day, part);
+    let file =
+        File::create(&path).with_context(|| format!("Could not create {}", path.display()))?;
+    report
+        .flamegraph(file)
+        .context("Failed to write the flamegraph")?;
+
+    Ok(path)
+}
+
+fn output_path(dir: &Path, day: usize, part: usize) -> PathBuf {
+    dir.join(format!("day-{:03}-part-{}.svg", day, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_paths() {
+        assert_eq!(
+            output_path(Path::new("out"), 7, 2),
+            Path::new("out/day-007-part-2.svg")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Rendering the progress solvers report as a bar on stderr.
+use std::io::Write;
+
+use aoc_plumbing::progress::{with_progress, Progress};
+
+const WIDTH: usize = 30;
+
+/// Run `f`, drawing a progress bar for any ticks the solver makes. The bar is
+/// cleared once its stage is finished, and once `f` returns.
+pub(crate) fn with_progress_bar<R>(f: impl FnOnce() -> R) -> R {
+    let out = with_progress(
+        |p| {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r{}", bar(&p));
+            // a finished bar is cleared so it's not left under the answers
+            if p.done >= p.total {
+                let _ = write!(stderr, "{}", CLEAR);
+            }
+            let _ = stderr.flush();
+        },
+        f,
+    );
+
+    eprint!("{}", CLEAR);
+    out
+}
+
+/// Blank out the line a bar was drawn on.
+const CLEAR: &str = "\r\x1b[2K";
+
+fn bar(p: &Progress) -> String {
+    // nothing to do is as good as done
+    let filled = (p.done.min(p.total) * WIDTH)
+        .checked_div(p.total)
+        .unwrap_or(WIDTH);
+
+    format!(
+        "[{}{}] {}/{} {}",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        p.done,
+        p.total,
+        p.stage
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars() {
+        let p = |done, total| Progress {
+            stage: "blueprints",
+            done,
+            total,
+        };
+
+        assert_eq!(
+            bar(&p(1, 3)),
+            format!("[{}{}] 1/3 blueprints", "#".repeat(10), " ".repeat(20))
+        );
+        assert_eq!(
+            bar(&p(3, 3)),
+            format!("[{}] 3/3 blueprints", "#".repeat(30))
+        );
+        assert_eq!(
+            bar(&p(0, 0)),
+            format!("[{}] 0/0 blueprints", "#".repeat(30))
+        );
+    }
+}
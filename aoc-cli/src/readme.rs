@@ -0,0 +1,74 @@
+//! Displaying the embedded problem statements.
+use std::{
+    io::{ErrorKind, Write},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use termimad::crossterm::tty::IsTty;
+
+use crate::registry::lookup;
+
+/// Display the problem statement for a specified day.
+///
+/// The markdown is rendered for the terminal and sent through `$PAGER`
+/// (`less -R` if unset) when writing to a terminal.
+#[derive(Args)]
+pub(crate) struct Readme {
+    /// The day to display.
+    day: usize,
+
+    /// Print the raw markdown instead of rendering it.
+    #[clap(long)]
+    raw: bool,
+
+    /// Print directly instead of using a pager.
+    #[clap(long)]
+    no_pager: bool,
+}
+
+impl Readme {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let readme = lookup(year, self.day)?.readme;
+        let interactive = std::io::stdout().is_tty();
+
+        let text = if self.raw || !interactive {
+            readme.to_string()
+        } else {
+            termimad::term_text(readme).to_string()
+        };
+
+        if self.no_pager || !interactive || page(&text).is_err() {
+            // being piped into something like `head` is fine
+            match writeln!(std::io::stdout(), "{}", text) {
+                Err(e) if e.kind() != ErrorKind::BrokenPipe => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Send `text` through the user's pager.
+fn page(text: &str) -> Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().context("Empty PAGER")?;
+
+    let mut child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start pager: {}", pager))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager exiting early (e.g. quitting less) is not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    child.wait().context("Pager failed")?;
+
+    Ok(())
+}
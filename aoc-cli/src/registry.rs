@@ -0,0 +1,29 @@
+//! Looking up the registered days.
+//!
+//! Every day registered with `aoc_plumbing::register!` and linked into the
+//! binary is available, which `aoc` takes care of for every day it depends
+//! on. Each gets its own subcommand, named after its crate.
+use anyhow::{anyhow, Result};
+use aoc_plumbing::registry::{self, Day};
+
+/// Find `day` of `year`.
+pub(crate) fn lookup(year: usize, day: usize) -> Result<&'static Day> {
+    registry::find(year, day).ok_or_else(|| anyhow!("Unknown day {} for {}", day, year))
+}
+
+/// Every registered day for `year`, in order.
+pub(crate) fn for_year(year: usize) -> impl Iterator<Item = &'static Day> {
+    registry::days().into_iter().filter(move |d| d.year == year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_day_is_linked() {
+        let days = for_year(2022).map(|d| d.day).collect::<Vec<_>>();
+        assert_eq!(days, (1..=25).collect::<Vec<_>>());
+        assert!(lookup(2022, 26).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Timed results for one or more days and their presentation.
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use aoc_plumbing::{
+    cancellation::{with_cancellation, CancellationToken},
+    metadata::Metadata,
+    problem::display_part,
+    registry::{Answer, Day},
+    timing::as_nanos,
+    AocError, Timings,
+};
+use serde::Serialize;
+use tracing::info_span;
+
+use crate::cache::InputCache;
+
+/// The output of running a single day, possibly only one part of it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct RunOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl Display for RunOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = Vec::with_capacity(3);
+        for (part, answer) in [(1, &self.part_one), (2, &self.part_two)] {
+            if let Some(answer) = answer.as_ref().map(answer_str) {
+                if !answer.is_empty() {
+                    lines.push(display_part(part, &answer));
+                }
+            }
+        }
+
+        if let Some(timings) = &self.timings {
+            let mut times = vec![format!("parse {:.2?}", timings.parse)];
+            if self.part_one.is_some() {
+                times.push(format!("part 1 {:.2?}", timings.part_one));
+            }
+            if self.part_two.is_some() {
+                times.push(format!("part 2 {:.2?}", timings.part_two));
+            }
+            times.push(format!("total {:.2?}", timings.total()));
+            lines.push(format!("timings: {}", times.join(", ")));
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Strings are displayed without the quotes json would add, and a part
+/// without an answer isn't displayed at all.
+pub(crate) fn answer_str(answer: &serde_json::Value) -> String {
+    match answer {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// The value of `answer` to output, if there is one. A part without an answer
+/// is left out rather than output as null, which toml can't represent.
+pub(crate) fn answer_value(answer: &Answer) -> Option<serde_json::Value> {
+    match answer.value() {
+        serde_json::Value::Null => None,
+        value => Some(value.clone()),
+    }
+}
+
+/// The answers and timings for a single day.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct DayReport {
+    pub day: usize,
+    pub title: &'static str,
+    pub part_one: String,
+    pub part_two: String,
+    pub timings: Timings,
+    /// The peak resident set size of the process in bytes, where known. Left
+    /// out when days are run concurrently, as it would include the others.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_rss: Option<u64>,
+}
+
+/// Solve `day`, recording how long each stage takes.
+pub(crate) fn solve_timed(day: &Day, input: &str) -> Result<DayReport> {
+    let timed = info_span!("solve", day = day.day)
+        .in_scope(|| day.solve_timed(input))
+        .map_err(AocError::into_anyhow)
+        .context("Failed to solve")?;
+
+    Ok(DayReport {
+        day: day.day,
+        title: day.title,
+        part_one: timed.solution.part_one.to_string(),
+        part_two: timed.solution.part_two.to_string(),
+        timings: timed.timings,
+        peak_rss: timed.peak_rss,
+    })
+}
+
+/// The stack size of the threads solutions are run on when they have a time
+/// limit. This is the main thread's default on linux, which the recursive
+/// days rely on.
+pub(crate) const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// A solution that did not finish within its time limit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct TimedOut(pub Duration);
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "timed out after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Run `f` on its own thread, waiting at most `limit` for it to finish, or
+/// run it directly if there is no limit.
+///
+/// A solution that times out is cancelled, so it stops at its next
+/// checkpoint. Solutions without checkpoints can't be interrupted, and keep
+/// running in the background until the process exits.
+pub(crate) fn with_timeout<R, F>(limit: Option<Duration>, f: F) -> Result<R, TimedOut>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return Ok(f()),
+    };
+
+    let token = CancellationToken::new();
+    let solution_token = token.clone();
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // the receiver is gone if we already gave up
+            let _ = tx.send(with_cancellation(&solution_token, f));
+        })
+        .expect("failed to spawn solution thread");
+
+    match rx.recv_timeout(limit) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(TimedOut(limit))
+        }
+        // the sender was dropped without sending, so `f` panicked
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("the solution thread exited without a result"),
+        },
+    }
+}
+
+/// Find the `input.txt` for `day` in its `day-XXX-*` directory under `root`,
+/// falling back to the input cache.
+pub(crate) fn find_input(root: &Path, year: usize, day: usize) -> Result<PathBuf> {
+    let prefix = format!("day-{:03}-", day);
+    let entries = std::fs::read_dir(root)
+        .with_context(|| format!("Could not read directory {}", root.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            let input = entry.path().join("input.txt");
+            if input.is_file() {
+                return Ok(input);
+            }
+        }
+    }
+
+    if let Some(cached) = InputCache::from_env()
+        .ok()
+        .and_then(|cache| cache.get(year, day))
+    {
+        return Ok(cached);
+    }
+
+    anyhow::bail!(
+        "No input found for day {} in {} or the input cache",
+        day,
+        root.display()
+    )
+}
+
+/// Whether `path` is the `input.txt` in `day`'s own directory, which is the
+/// input the answers embedded in the day are for.
+pub(crate) fn is_day_input(path: &Path, day: usize) -> bool {
+    let dir = match path.parent().and_then(Path::file_name) {
+        Some(dir) => dir.to_string_lossy(),
+        None => return false,
+    };
+
+    path.file_name() == Some("input.txt".as_ref()) && dir.starts_with(&format!("day-{:03}-", day))
+}
+
+/// A day that could not be run.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct Skipped {
+    pub day: usize,
+    pub reason: String,
+}
+
+/// The outcome of running a collection of days.
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct Report {
+    // plain values need to come before the tables for toml
+    #[serde(rename = "total_ns", serialize_with = "as_nanos")]
+    pub total: Duration,
+    /// The elapsed time for the whole run, which is less than the total when
+    /// days are run in parallel.
+    #[serde(rename = "wall_ns", serialize_with = "as_nanos")]
+    pub wall: Duration,
+    pub days: Vec<DayReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<Skipped>,
+}
+
+impl Report {
+    pub fn new(days: Vec<DayReport>, skipped: Vec<Skipped>, wall: Duration) -> Self {
+        let total = days.iter().map(|d| d.timings.total()).sum();
+        Self {
+            days,
+            skipped,
+            total,
+            wall,
+        }
+    }
+}
+
+/// Multi-line answers (like day 10's) are displayed below the table.
+pub(crate) fn table_cell(answer: &str) -> String {
+    if answer.contains('\n') {
+        "(see below)".to_string()
+    } else {
+        answer.to_string()
+    }
+}
+
+/// Write `rows` as a plain text table with left-aligned columns.
+pub(crate) fn write_table<const N: usize>(
+    f: &mut std::fmt::Formatter<'_>,
+    header: &[&str; N],
+    rows: &[[String; N]],
+) -> std::fmt::Result {
+    let mut widths = header.map(|h| h.len());
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let write_row = |f: &mut std::fmt::Formatter<'_>, cells: &[&str]| -> std::fmt::Result {
+        let line = cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        writeln!(f, "{}", line.trim_end())
+    };
+
+    write_row(f, header)?;
+    let sep = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+    writeln!(f, "{}", sep.join("-+-"))?;
+    for row in rows.iter() {
+        write_row(f, &row.iter().map(|c| c.as_str()).collect::<Vec<_>>())?;
+    }
+
+    Ok(())
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = [
+            "day",
+            "title",
+            "part 1",
+            "part 2",
+            "parse",
+            "part 1 time",
+            "part 2 time",
+            "total",
+        ];
+        let mut rows: Vec<[String; 8]> = self
+            .days
+            .iter()
+            .map(|d| {
+                [
+                    format!("{:03}", d.day),
+                    d.title.to_string(),
+                    table_cell(&d.part_one),
+                    table_cell(&d.part_two),
+                    format!("{:.2?}", d.timings.parse),
+                    format!("{:.2?}", d.timings.part_one),
+                    format!("{:.2?}", d.timings.part_two),
+                    format!("{:.2?}", d.timings.total()),
+                ]
+            })
+            .collect();
+        rows.push([
+            "total".into(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            format!("{:.2?}", self.total),
+        ]);
+
+        write_table(f, &header, &rows)?;
+        writeln!(f, "wall time: {:.2?}", self.wall)?;
+
+        // answers that don't fit in a cell
+        for d in self.days.iter() {
+            for (part, answer) in [(1, &d.part_one), (2, &d.part_two)] {
+                if answer.contains('\n') {
+                    writeln!(f, "\nday {:03} part {}:\n{}", d.day, part, answer)?;
+                }
+            }
+        }
+
+        for skipped in self.skipped.iter() {
+            writeln!(f, "skipped day {}: {}", skipped.day, skipped.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_plumbing::cancellation::checkpoint;
+
+    use super::*;
+
+    #[test]
+    fn timeouts() {
+        assert_eq!(with_timeout(None, || 1), Ok(1));
+        assert_eq!(with_timeout(Some(Duration::from_secs(10)), || 2), Ok(2));
+
+        let limit = Duration::from_millis(10);
+        let timed_out = with_timeout(Some(limit), || thread::sleep(Duration::from_secs(1)));
+        assert_eq!(timed_out, Err(TimedOut(limit)));
+        assert_eq!(TimedOut(limit).to_string(), "timed out after 10.00ms");
+
+        // a solution that checks is told to stop
+        let (tx, rx) = mpsc::channel();
+        let timed_out = with_timeout(Some(limit), move || loop {
+            if checkpoint().is_err() {
+                let _ = tx.send(());
+                return;
+            }
+            thread::sleep(Duration::from_millis(1));
+        });
+        assert_eq!(timed_out, Err(TimedOut(limit)));
+        assert!(rx.recv_timeout(Duration::from_secs(10)).is_ok());
+    }
+}
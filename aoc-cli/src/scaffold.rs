@@ -0,0 +1,265 @@
+//! Generating new day crates and adding them to the workspace.
+//!
+//! This mirrors what `scripts/new.sh` does with `cargo-generate`, but without
+//! needing any external tools.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+// The built-in template is the same one `cargo-generate` uses.
+const TEMPLATE: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../../template/Cargo.toml")),
+    ("README.md", include_str!("../../template/README.md")),
+    ("input.txt", include_str!("../../template/input.txt")),
+    ("src/lib.rs", include_str!("../../template/src/lib.rs")),
+];
+
+/// Files in a template directory that are only used by `cargo-generate`, and
+/// aren't part of the generated crate.
+const IGNORED: &[&str] = &[".git", "cargo-generate.toml", "target"];
+
+/// Generate a new day crate from the template and add it to the export
+/// library.
+///
+/// The day registers itself, and the cli and the benchmarks find it through
+/// the export library.
+#[derive(Args)]
+pub(crate) struct New {
+    /// The day to generate.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// The kebab-case name of the problem, e.g. `full-of-hot-air`.
+    slug: String,
+
+    /// The root of the workspace.
+    #[clap(short, long, default_value = ".")]
+    root: PathBuf,
+
+    /// A directory to use as the template instead of the built-in one. It
+    /// uses the same placeholders as `template/`, e.g. `{{crate_name}}` and
+    /// `{{project-name|upper_camel_case}}`, in both file contents and paths.
+    #[clap(short, long)]
+    template: Option<PathBuf>,
+}
+
+impl New {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let names = Names::new(year, self.day as usize, &self.slug)?;
+        let crate_dir = self.root.join(&names.dir);
+
+        if crate_dir.exists() {
+            bail!("{} already exists", crate_dir.display());
+        }
+
+        let files = match &self.template {
+            Some(dir) => load_template(dir)?,
+            None => TEMPLATE
+                .iter()
+                .map(|(path, contents)| (path.to_string(), contents.to_string()))
+                .collect(),
+        };
+
+        println!("Generating {}", names.dir);
+        for (path, contents) in files {
+            let dest = crate_dir.join(names.render(&path));
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Could not create {}", parent.display()))?;
+            }
+            fs::write(&dest, names.render(&contents))
+                .with_context(|| format!("Could not write {}", dest.display()))?;
+        }
+
+        println!("Adding {} to the export library", names.slug);
+        append(
+            &self.root.join("aoc/Cargo.toml"),
+            &format!("{} = {{ path = \"../{}\" }}\n", names.slug, names.dir),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// The various forms of a problem's name used by the template.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Names {
+    year: usize,
+    day: usize,
+    slug: String,
+    dir: String,
+    crate_name: String,
+    struct_name: String,
+    title: String,
+}
+
+impl Names {
+    fn new(year: usize, day: usize, slug: &str) -> Result<Self> {
+        if slug.is_empty()
+            || slug.starts_with('-')
+            || slug.ends_with('-')
+            || !slug
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+        {
+            bail!(
+                "The name must be kebab-case, e.g. full-of-hot-air: {}",
+                slug
+            );
+        }
+
+        let struct_name = slug.split('-').map(capitalize).collect();
+
+        Ok(Self {
+            year,
+            day,
+            slug: slug.to_string(),
+            dir: format!("day-{:03}-{}", day, slug),
+            crate_name: slug.replace('-', "_"),
+            struct_name,
+            title: slug.replace('-', " "),
+        })
+    }
+
+    /// Fill in the placeholders used by the template.
+    fn render(&self, template: &str) -> String {
+        let title_case = self
+            .title
+            .split(' ')
+            .map(capitalize)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        template
+            .replace("{{project-name|upper_camel_case}}", &self.struct_name)
+            .replace("{{project-name|title_case|downcase}}", &self.title)
+            .replace("{{project-name|title_case}}", &title_case)
+            .replace("{{project-name}}", &self.slug)
+            .replace("{{crate_name}}", &self.crate_name)
+            .replace("{{day}}", &self.day.to_string())
+            .replace("{{year}}", &self.year.to_string())
+    }
+}
+
+/// Read every file in the template directory `dir`, keyed by its path
+/// relative to `dir` (with `/` separators).
+fn load_template(dir: &Path) -> Result<Vec<(String, String)>> {
+    fn walk(dir: &Path, prefix: &str, files: &mut Vec<(String, String)>) -> Result<()> {
+        let entries = fs::read_dir(dir)
+            .with_context(|| format!("Could not read template directory {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            let name = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) if !IGNORED.contains(&name) => name,
+                _ => continue,
+            };
+            let relative = format!("{}{}", prefix, name);
+
+            if path.is_dir() {
+                walk(&path, &format!("{}/", relative), files)?;
+            } else {
+                let contents = fs::read_to_string(&path)
+                    .with_context(|| format!("Could not read {}", path.display()))?;
+                files.push((relative, contents));
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(dir, "", &mut files)?;
+
+    if files.is_empty() {
+        bail!("The template directory {} is empty", dir.display());
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn append(path: &Path, contents: &str) -> Result<()> {
+    let mut existing =
+        fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))?;
+    if !existing.ends_with('\n') {
+        existing.push('\n');
+    }
+    existing.push_str(contents);
+    fs::write(path, existing).with_context(|| format!("Could not write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let names = Names::new(2022, 7, "no-space-left-on-device").unwrap();
+        assert_eq!(names.dir, "day-007-no-space-left-on-device");
+        assert_eq!(names.crate_name, "no_space_left_on_device");
+        assert_eq!(names.struct_name, "NoSpaceLeftOnDevice");
+        assert_eq!(
+            names.render("# Day {{day}}: {{project-name|title_case}}"),
+            "# Day 7: No Space Left On Device"
+        );
+        assert_eq!(
+            names.render("#[aoc_problem(day = {{day}}, year = {{year}})]"),
+            "#[aoc_problem(day = 7, year = 2022)]"
+        );
+        assert_eq!(
+            names.render("const TITLE: &'static str = \"{{project-name|title_case|downcase}}\";"),
+            "const TITLE: &'static str = \"no space left on device\";"
+        );
+
+        assert!(Names::new(2022, 1, "Not-Kebab").is_err());
+        assert!(Names::new(2022, 1, "trailing-").is_err());
+        assert!(Names::new(2022, 1, "").is_err());
+    }
+
+    #[test]
+    fn templates() {
+        let dir = std::env::temp_dir().join(format!("aoc-template-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("cargo-generate.toml"), "").unwrap();
+        fs::write(dir.join("Cargo.toml"), "name = \"{{project-name}}\"").unwrap();
+        fs::write(dir.join("src/{{crate_name}}.rs"), "").unwrap();
+
+        let files = load_template(&dir).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                (
+                    "Cargo.toml".to_string(),
+                    "name = \"{{project-name}}\"".to_string()
+                ),
+                ("src/{{crate_name}}.rs".to_string(), String::new()),
+            ]
+        );
+
+        // the built-in template is the one in the workspace
+        let builtin = load_template(Path::new("../template")).unwrap();
+        assert_eq!(
+            builtin
+                .iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>(),
+            TEMPLATE.iter().map(|(path, _)| *path).collect::<Vec<_>>()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(load_template(&dir).is_err());
+    }
+}
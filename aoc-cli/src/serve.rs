@@ -0,0 +1,146 @@
+//! Solving inputs posted over HTTP.
+use std::thread;
+
+use anyhow::{anyhow, Context, Result};
+use aoc_plumbing::{metadata::Metadata, registry::Day, AocError};
+use clap::Args;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{registry::lookup, report::STACK_SIZE};
+
+/// Serve the solutions over HTTP.
+///
+/// `POST /solve/<DAY>` with the raw input as the body responds with the
+/// solution and its timings as json, e.g.
+/// `{"part_one":24000,"part_two":45000,"timings":{...}}`.
+/// Failures respond with `{"error": "..."}`.
+#[derive(Args)]
+pub(crate) struct Serve {
+    /// The port to listen on.
+    #[clap(short, long, default_value_t = 8080)]
+    port: u16,
+
+    /// The address to listen on.
+    #[clap(long, default_value = "127.0.0.1")]
+    host: String,
+}
+
+impl Serve {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let addr = format!("{}:{}", self.host, self.port);
+        let server =
+            Server::http(&addr).map_err(|e| anyhow!("Could not listen on {}: {}", addr, e))?;
+        println!("listening on http://{}", addr);
+
+        for request in server.incoming_requests() {
+            // a slow day shouldn't hold up the other requests
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn(move || respond(year, request))
+                .context("Failed to spawn request thread")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn respond(year: usize, mut request: Request) {
+    let mut body = Vec::new();
+    let (status, value) = match request.as_reader().read_to_end(&mut body) {
+        Ok(_) => handle(year, request.method(), request.url(), body),
+        Err(e) => error(400, format!("Could not read request body: {}", e)),
+    };
+
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(
+            "Content-Type: application/json"
+                .parse::<Header>()
+                .expect("valid header"),
+        );
+
+    // the client may have gone away, and there's no one to tell
+    let _ = request.respond(response);
+}
+
+/// The status code and json body of the response to a request.
+fn handle(year: usize, method: &Method, url: &str, body: Vec<u8>) -> (u16, Value) {
+    let day = match url.strip_prefix("/solve/").map(str::parse::<usize>) {
+        Some(Ok(day)) => day,
+        _ => return error(404, format!("Not found: {}", url)),
+    };
+
+    if *method != Method::Post {
+        return error(405, format!("Use POST to solve, not {}", method));
+    }
+
+    let day = match lookup(year, day) {
+        Ok(day) => day,
+        Err(e) => return error(404, e.to_string()),
+    };
+
+    let input = match String::from_utf8(body) {
+        Ok(input) => input,
+        Err(_) => return error(400, "The input must be utf-8".into()),
+    };
+
+    match solve_json(day, &input) {
+        Ok(solution) => (200, solution),
+        Err(e) => error(422, format!("{:#}", e)),
+    }
+}
+
+fn error(status: u16, message: String) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+/// Solve `day`, with the solution, its timings, and its metadata serialized
+/// as json.
+pub(crate) fn solve_json(day: &Day, input: &str) -> Result<Value> {
+    let mut solution = day
+        .solve_timed(input)
+        .map_err(AocError::into_anyhow)
+        .context("Failed to solve")?
+        .with_metadata(Metadata::collect(env!("CARGO_PKG_VERSION"), input));
+    // requests are solved concurrently, so the process's peak may well be
+    // another request's
+    solution.peak_rss = None;
+
+    Ok(serde_json::to_value(solution)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handling() {
+        let example = b"1000\n2000\n\n3000".to_vec();
+        let (status, body) = handle(2022, &Method::Post, "/solve/1", example.clone());
+        assert_eq!(status, 200);
+        assert_eq!(body["part_one"], 3000);
+        assert_eq!(body["part_two"], 6000);
+        assert!(body["timings"]["parse_ns"].is_u64());
+        assert!(body.get("peak_rss").is_none());
+        assert_eq!(body["metadata"]["version"], env!("CARGO_PKG_VERSION"));
+
+        assert_eq!(
+            handle(2022, &Method::Get, "/solve/1", example.clone()).0,
+            405
+        );
+        assert_eq!(
+            handle(2022, &Method::Post, "/solve/26", example.clone()).0,
+            404
+        );
+        assert_eq!(handle(2022, &Method::Post, "/solve", example).0, 404);
+        assert_eq!(handle(2022, &Method::Post, "/solve/1", vec![0xff]).0, 400);
+
+        let (status, body) = handle(2022, &Method::Post, "/solve/1", b"abc".to_vec());
+        assert_eq!(status, 422);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to solve"));
+    }
+}
@@ -0,0 +1,125 @@
+//! Personal progress from the AoC website alongside the local solutions.
+use std::{collections::HashSet, fmt::Display};
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::{
+    api::{DayStats, PartStats, Session},
+    registry::for_year,
+    report::write_table,
+};
+
+/// Display the stars, completion times, and ranks from the AoC website next to
+/// which days have been implemented locally.
+///
+/// Days with stars but no local solution are marked as missing.
+#[derive(Args)]
+pub(crate) struct Stats {
+    #[command(flatten)]
+    session: Session,
+}
+
+impl Stats {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let stats = self.session.client(year).personal_stats()?;
+        let implemented = for_year(year).map(|e| e.day).collect();
+
+        print!("{}", Progress::new(stats, implemented));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Progress {
+    days: Vec<DayStats>,
+    implemented: HashSet<usize>,
+}
+
+impl Progress {
+    fn new(days: Vec<DayStats>, implemented: HashSet<usize>) -> Self {
+        Self { days, implemented }
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = [
+            "day",
+            "stars",
+            "part 1 time",
+            "part 1 rank",
+            "part 2 time",
+            "part 2 rank",
+            "local",
+        ];
+
+        let rows = (1..=25)
+            .map(|day| {
+                let stats = self.days.iter().find(|d| d.day == day);
+                let stars = stats.map(|s| s.stars()).unwrap_or_default();
+                let part = |p: Option<&PartStats>| match p {
+                    Some(p) => [p.time.clone(), p.rank.to_string()],
+                    None => [String::from("-"), String::from("-")],
+                };
+                let [one_time, one_rank] = part(stats.and_then(|s| s.part_one.as_ref()));
+                let [two_time, two_rank] = part(stats.and_then(|s| s.part_two.as_ref()));
+
+                let local = if self.implemented.contains(&day) {
+                    "yes"
+                } else if stars > 0 {
+                    "MISSING"
+                } else {
+                    "no"
+                };
+
+                [
+                    format!("{:03}", day),
+                    "*".repeat(stars),
+                    one_time,
+                    one_rank,
+                    two_time,
+                    two_rank,
+                    local.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        write_table(f, &header, &rows)?;
+
+        let stars: usize = self.days.iter().map(|d| d.stars()).sum();
+        writeln!(
+            f,
+            "{} stars, {} of 25 days implemented",
+            stars,
+            self.implemented.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_days() {
+        let stats = vec![DayStats {
+            day: 2,
+            part_one: Some(PartStats {
+                time: "00:05:39".into(),
+                rank: 981,
+            }),
+            part_two: None,
+        }];
+        let progress = Progress::new(stats, [1].into_iter().collect()).to_string();
+        let lines = progress.lines().collect::<Vec<_>>();
+
+        assert!(lines[2].starts_with("001 |       | -"));
+        assert!(lines[2].ends_with("| yes"));
+        assert!(lines[3].starts_with("002 | *     | 00:05:39"));
+        assert!(lines[3].ends_with("| MISSING"));
+        assert!(lines[4].ends_with("| no"));
+        assert_eq!(lines[27], "1 stars, 1 of 25 days implemented");
+    }
+}
@@ -0,0 +1,247 @@
+//! Repeatedly timing every day, for publishing to the benchmark site.
+use std::{fmt::Display, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use aoc_plumbing::{metadata::content_hash, registry::Day};
+use clap::Args;
+use serde::Serialize;
+
+use crate::{
+    registry::for_year,
+    report::{find_input, solve_timed, write_table, DayReport, Skipped},
+};
+
+/// Bumped whenever a field of [TimingReport] is renamed, removed, or changes
+/// meaning. Adding fields does not require a bump.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Solve every implemented day several times, reporting the mean time of each
+/// part along with a hash of its answer.
+///
+/// Inputs are expected at `<INPUT_ROOT>/day-XXX-*/input.txt` or in the input
+/// cache. Days without an input are skipped. Use `--json` to write the report
+/// in the format expected by the external benchmarking pipeline.
+#[derive(Args)]
+pub(crate) struct TimeAll {
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+
+    /// How many times to solve each day.
+    #[clap(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Write the report to this file as json.
+    #[clap(long, value_name = "PATH")]
+    json: Option<PathBuf>,
+}
+
+impl TimeAll {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let mut report = TimingReport {
+            schema_version: SCHEMA_VERSION,
+            year,
+            iterations: self.iterations,
+            machine: Machine::detect(),
+            results: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for entry in for_year(year) {
+            let input = match find_input(&self.input_root, year, entry.day) {
+                Ok(path) => std::fs::read_to_string(path).context("Could not read input file")?,
+                Err(e) => {
+                    report.skipped.push(Skipped {
+                        day: entry.day,
+                        reason: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let runs = self.time(entry, &input)?;
+            report.results.extend(PartTiming::from_runs(&runs));
+        }
+
+        print!("{}", report);
+
+        if let Some(path) = &self.json {
+            let json = serde_json::to_string_pretty(&report)?;
+            std::fs::write(path, json)
+                .with_context(|| format!("Could not write {}", path.display()))?;
+            println!("wrote {}", path.display());
+        }
+
+        Ok(())
+    }
+
+    fn time(&self, entry: &Day, input: &str) -> Result<Vec<DayReport>> {
+        let runs = (0..self.iterations)
+            .map(|_| {
+                solve_timed(entry, input)
+                    .with_context(|| format!("Failed to run day {}", entry.day))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // a hash is meaningless if the answer isn't deterministic
+        if let Some(run) = runs
+            .iter()
+            .find(|r| r.part_one != runs[0].part_one || r.part_two != runs[0].part_two)
+        {
+            bail!(
+                "Day {} gave different answers across runs: ({}, {}) and ({}, {})",
+                entry.day,
+                runs[0].part_one,
+                runs[0].part_two,
+                run.part_one,
+                run.part_two
+            );
+        }
+
+        Ok(runs)
+    }
+}
+
+/// The machine the timings were recorded on.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct Machine {
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub cpus: usize,
+    /// The cpu model, where it can be determined.
+    pub cpu: Option<String>,
+    pub cli_version: &'static str,
+}
+
+impl Machine {
+    pub fn detect() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            cpus: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            cpu: cpu_model(),
+            cli_version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))?
+        .split_once(':')
+        .map(|(_, model)| model.trim().to_string())
+}
+
+/// The mean time for a single part of a day.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct PartTiming {
+    pub day: usize,
+    pub part: usize,
+    pub mean_ns: u64,
+    /// The 64-bit FNV-1a hash of the answer as displayed, in hex, so answers
+    /// can be compared without being published.
+    pub answer_hash: String,
+}
+
+impl PartTiming {
+    /// The timings for both parts of the (non-empty) `runs` of a day.
+    fn from_runs(runs: &[DayReport]) -> [Self; 2] {
+        let mean = |part: fn(&DayReport) -> Duration| {
+            (runs.iter().map(part).sum::<Duration>() / runs.len() as u32).as_nanos() as u64
+        };
+
+        [
+            Self {
+                day: runs[0].day,
+                part: 1,
+                mean_ns: mean(|r| r.timings.part_one),
+                answer_hash: answer_hash(&runs[0].part_one),
+            },
+            Self {
+                day: runs[0].day,
+                part: 2,
+                mean_ns: mean(|r| r.timings.part_two),
+                answer_hash: answer_hash(&runs[0].part_two),
+            },
+        ]
+    }
+}
+
+fn answer_hash(answer: &str) -> String {
+    content_hash(answer.as_bytes())
+}
+
+/// Every field is always present, so the pipeline can rely on the shape.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub(crate) struct TimingReport {
+    pub schema_version: u32,
+    pub year: usize,
+    pub iterations: u32,
+    pub machine: Machine,
+    pub results: Vec<PartTiming>,
+    pub skipped: Vec<Skipped>,
+}
+
+impl Display for TimingReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = ["day", "part", "mean", "answer hash"];
+        let rows = self
+            .results
+            .iter()
+            .map(|r| {
+                [
+                    format!("{:03}", r.day),
+                    r.part.to_string(),
+                    format!("{:.2?}", Duration::from_nanos(r.mean_ns)),
+                    r.answer_hash.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        write_table(f, &header, &rows)?;
+
+        for skipped in self.skipped.iter() {
+            writeln!(f, "skipped day {}: {}", skipped.day, skipped.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_plumbing::Timings;
+
+    use super::*;
+
+    #[test]
+    fn hashes() {
+        assert_eq!(answer_hash(""), "cbf29ce484222325");
+        assert_eq!(answer_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn means() {
+        let run = |part_one: u64, part_two: u64| DayReport {
+            day: 3,
+            title: "rucksack reorganization",
+            part_one: "157".into(),
+            part_two: "70".into(),
+            timings: Timings {
+                parse: Duration::from_nanos(1),
+                part_one: Duration::from_nanos(part_one),
+                part_two: Duration::from_nanos(part_two),
+            },
+            peak_rss: None,
+        };
+
+        let [one, two] = PartTiming::from_runs(&[run(10, 100), run(20, 300)]);
+        assert_eq!((one.day, one.part, one.mean_ns), (3, 1, 15));
+        assert_eq!((two.day, two.part, two.mean_ns), (3, 2, 200));
+        assert_eq!(one.answer_hash, answer_hash("157"));
+    }
+}
@@ -0,0 +1,389 @@
+//! An interactive dashboard for every registered day.
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+use aoc_plumbing::{registry, Check};
+use clap::Args;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend},
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame, Terminal,
+};
+
+use crate::{
+    registry::for_year,
+    report::{find_input, solve_timed, table_cell, DayReport},
+    verify::Answers,
+};
+
+const WIDTHS: [Constraint; 9] = [
+    Constraint::Length(3),
+    Constraint::Length(24),
+    Constraint::Min(10),
+    Constraint::Min(10),
+    Constraint::Length(10),
+    Constraint::Length(11),
+    Constraint::Length(11),
+    Constraint::Length(10),
+    Constraint::Length(7),
+];
+
+/// Display every implemented day with its answers, timings, and whether the
+/// answers match the recorded ones.
+///
+/// Days are solved in the background of the dashboard as it starts up. Use
+/// the arrow keys (or j/k) to select a day, enter (or r) to re-run it, a to
+/// re-run every day, and q to quit.
+#[derive(Args)]
+pub(crate) struct Tui {
+    /// Use the answers in this file instead of the ones embedded in each
+    /// day, for inputs other than the ones the days were written against.
+    #[clap(short, long)]
+    answers: Option<PathBuf>,
+
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+}
+
+impl Tui {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let answers = Answers::resolve(self.answers.as_deref(), year)?;
+
+        let mut app = App::new(year, Worker::spawn(answers, self.input_root.clone()));
+
+        let _guard = TerminalGuard::new()?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+        loop {
+            app.update();
+            terminal.draw(|f| app.draw(f))?;
+
+            // wake up now and then to display the days solved in the meantime
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => break,
+                    KeyCode::Down | KeyCode::Char('j') => app.select(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.select(-1),
+                    KeyCode::Enter | KeyCode::Char('r') => app.rerun_selected(),
+                    KeyCode::Char('a') => app.rerun_all(),
+                    _ => {}
+                }
+            }
+        }
+
+        terminal.show_cursor()?;
+
+        Ok(())
+    }
+}
+
+/// Restores the terminal when dropped, even if we bail early.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Status {
+    /// Waiting to be sent to the worker.
+    Pending,
+    Solving,
+    Solved {
+        report: Box<DayReport>,
+        part_one: Check,
+        part_two: Check,
+    },
+    Skipped(String),
+    Failed(String),
+}
+
+struct Day {
+    entry: &'static registry::Day,
+    status: Status,
+    /// Bumped every time the day is re-run, so the results of earlier runs
+    /// still in flight can be told apart and dropped.
+    run: usize,
+}
+
+/// A day for the worker to solve.
+struct Job {
+    idx: usize,
+    run: usize,
+    entry: &'static registry::Day,
+}
+
+/// Solves days on a background thread, one at a time so their timings aren't
+/// skewed by each other, in the order they were sent.
+struct Worker {
+    jobs: Sender<Job>,
+    results: Receiver<(usize, usize, Status)>,
+}
+
+impl Worker {
+    fn spawn(answers: Answers, input_root: PathBuf) -> Self {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        let (result_tx, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            for job in job_rx {
+                let status = solve(job.entry, &answers, &input_root);
+                if result_tx.send((job.idx, job.run, status)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { jobs, results }
+    }
+}
+
+fn solve(entry: &registry::Day, answers: &Answers, input_root: &Path) -> Status {
+    let input = match find_input(input_root, entry.year, entry.day) {
+        Ok(path) => match std::fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(e) => return Status::Failed(format!("Could not read input file: {}", e)),
+        },
+        Err(e) => return Status::Skipped(e.to_string()),
+    };
+
+    match solve_timed(entry, &input) {
+        Ok(report) => {
+            let expected = answers.get(entry.day).cloned().unwrap_or_default();
+            Status::Solved {
+                part_one: Check::new(expected.part_one().as_deref(), &report.part_one),
+                part_two: Check::new(expected.part_two().as_deref(), &report.part_two),
+                report: Box::new(report),
+            }
+        }
+        Err(e) => Status::Failed(format!("{:#}", e)),
+    }
+}
+
+struct App {
+    days: Vec<Day>,
+    state: TableState,
+    worker: Worker,
+}
+
+impl App {
+    fn new(year: usize, worker: Worker) -> Self {
+        let mut state = TableState::default();
+        state.select(Some(0));
+
+        Self {
+            days: for_year(year)
+                .map(|entry| Day {
+                    entry,
+                    status: Status::Pending,
+                    run: 0,
+                })
+                .collect(),
+            state,
+            worker,
+        }
+    }
+
+    /// Send the pending days to the worker, and record the days it's solved
+    /// since the last update.
+    fn update(&mut self) {
+        for (idx, day) in self.days.iter_mut().enumerate() {
+            if matches!(day.status, Status::Pending) {
+                day.run += 1;
+                let job = Job {
+                    idx,
+                    run: day.run,
+                    entry: day.entry,
+                };
+
+                day.status = match self.worker.jobs.send(job) {
+                    Ok(_) => Status::Solving,
+                    Err(_) => Status::Failed("The solver thread exited".into()),
+                };
+            }
+        }
+
+        while let Ok((idx, run, status)) = self.worker.results.try_recv() {
+            if let Some(day) = self.days.get_mut(idx).filter(|d| d.run == run) {
+                day.status = status;
+            }
+        }
+    }
+
+    fn select(&mut self, delta: isize) {
+        let len = self.days.len() as isize;
+        if len == 0 {
+            return;
+        }
+
+        let current = self.state.selected().unwrap_or(0) as isize;
+        self.state
+            .select(Some((current + delta).rem_euclid(len) as usize));
+    }
+
+    fn rerun_selected(&mut self) {
+        if let Some(day) = self.state.selected().and_then(|i| self.days.get_mut(i)) {
+            day.status = Status::Pending;
+        }
+    }
+
+    fn rerun_all(&mut self) {
+        for day in self.days.iter_mut() {
+            day.status = Status::Pending;
+        }
+    }
+
+    fn draw<B: Backend>(&mut self, f: &mut Frame<B>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Min(5),
+                    Constraint::Length(10),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(f.size());
+
+        let header = Row::new([
+            "day",
+            "title",
+            "part 1",
+            "part 2",
+            "parse",
+            "part 1 time",
+            "part 2 time",
+            "total",
+            "status",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = self.days.iter().map(|d| {
+            let day = format!("{:03}", d.entry.day);
+            let cells = match &d.status {
+                Status::Solved {
+                    report,
+                    part_one,
+                    part_two,
+                } => {
+                    let (status, color) = if part_one.failed() || part_two.failed() {
+                        ("FAIL", Color::Red)
+                    } else if *part_one == Check::Pass && *part_two == Check::Pass {
+                        ("pass", Color::Green)
+                    } else {
+                        ("-", Color::Reset)
+                    };
+
+                    vec![
+                        Cell::from(day),
+                        Cell::from(d.entry.title),
+                        Cell::from(table_cell(&report.part_one)),
+                        Cell::from(table_cell(&report.part_two)),
+                        Cell::from(format!("{:.2?}", report.timings.parse)),
+                        Cell::from(format!("{:.2?}", report.timings.part_one)),
+                        Cell::from(format!("{:.2?}", report.timings.part_two)),
+                        Cell::from(format!("{:.2?}", report.timings.total())),
+                        Cell::from(status).style(Style::default().fg(color)),
+                    ]
+                }
+                other => {
+                    let (status, color) = match other {
+                        Status::Skipped(_) => ("skipped", Color::Reset),
+                        Status::Failed(_) => ("error", Color::Red),
+                        _ => ("...", Color::Reset),
+                    };
+
+                    let mut cells = vec![Cell::from(day), Cell::from(d.entry.title)];
+                    cells.extend((0..6).map(|_| Cell::from("")));
+                    cells.push(Cell::from(status).style(Style::default().fg(color)));
+                    cells
+                }
+            };
+
+            Row::new(cells)
+        });
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Advent of Code"),
+            )
+            .widths(&WIDTHS)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        f.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        let details = Paragraph::new(self.details())
+            .block(Block::default().borders(Borders::ALL).title("Details"));
+        f.render_widget(details, chunks[1]);
+
+        let help = Paragraph::new("j/k: select | enter/r: re-run | a: re-run all | q: quit");
+        f.render_widget(help, chunks[2]);
+    }
+
+    /// The full answers and any errors for the selected day.
+    fn details(&self) -> String {
+        let day = match self.state.selected().and_then(|i| self.days.get(i)) {
+            Some(day) => day,
+            None => return String::new(),
+        };
+
+        match &day.status {
+            Status::Pending | Status::Solving => "solving...".into(),
+            Status::Skipped(reason) => format!("skipped: {}", reason),
+            Status::Failed(reason) => format!("error: {}", reason),
+            Status::Solved {
+                report,
+                part_one,
+                part_two,
+            } => {
+                let mut lines = Vec::new();
+                for (part, answer, check) in [
+                    (1, &report.part_one, part_one),
+                    (2, &report.part_two, part_two),
+                ] {
+                    lines.push(format!("part {}: {}", part, answer));
+                    if let Check::Fail { expected, .. } = check {
+                        lines.push(format!("expected: {}", expected));
+                    }
+                }
+                lines.join("\n")
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//! Checking solutions against their known answers.
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use aoc_plumbing::{Check, Validation};
+use clap::Args;
+use serde::Deserialize;
+
+use crate::{
+    registry::for_year,
+    report::{find_input, solve_timed, write_table, Skipped},
+};
+
+/// The recorded answers for a single day.
+///
+/// Answers may be written as either strings or integers.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub(crate) struct Expected {
+    pub part_one: Option<toml::Value>,
+    pub part_two: Option<toml::Value>,
+}
+
+impl From<aoc_plumbing::Expected> for Expected {
+    fn from(value: aoc_plumbing::Expected) -> Self {
+        let part = |answer: Option<&str>| answer.map(|a| toml::Value::String(a.to_string()));
+        Self {
+            part_one: part(value.part_one),
+            part_two: part(value.part_two),
+        }
+    }
+}
+
+impl Expected {
+    fn part(value: &Option<toml::Value>) -> Option<String> {
+        value.as_ref().map(|v| match v {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    pub fn part_one(&self) -> Option<String> {
+        Self::part(&self.part_one)
+    }
+
+    pub fn part_two(&self) -> Option<String> {
+        Self::part(&self.part_two)
+    }
+}
+
+/// The contents of an answers file, keyed by day.
+///
+/// ```toml
+/// [1]
+/// part_one = 69795
+/// part_two = "208437"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Answers(BTreeMap<usize, Expected>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let raw: BTreeMap<String, Expected> = toml::from_str(contents)?;
+        let mut answers = BTreeMap::new();
+        for (day, expected) in raw {
+            let day = day
+                .parse()
+                .with_context(|| format!("Invalid day in answers: {}", day))?;
+            answers.insert(day, expected);
+        }
+
+        Ok(Self(answers))
+    }
+
+    /// The answers embedded in each day of `year`.
+    pub fn embedded(year: usize) -> Self {
+        Self(
+            for_year(year)
+                .filter_map(|entry| Some((entry.day, entry.expected?.into())))
+                .collect(),
+        )
+    }
+
+    /// The answers in the file at `path`, if given, otherwise the embedded
+    /// ones.
+    pub fn resolve(path: Option<&Path>, year: usize) -> Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None => Ok(Self::embedded(year)),
+        }
+    }
+
+    pub fn get(&self, day: usize) -> Option<&Expected> {
+        self.0.get(&day)
+    }
+}
+
+/// The checks for a single day.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct DayCheck {
+    pub day: usize,
+    pub title: &'static str,
+    pub validation: Validation,
+}
+
+/// The outcome of verifying every day.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub(crate) struct Verification {
+    pub days: Vec<DayCheck>,
+    /// Days that could not be solved at all, with the error.
+    pub errors: Vec<Skipped>,
+    pub skipped: Vec<Skipped>,
+}
+
+impl Verification {
+    pub fn failures(&self) -> usize {
+        self.days
+            .iter()
+            .map(|d| d.validation.failures())
+            .sum::<usize>()
+            + self.errors.len()
+    }
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = ["day", "title", "part 1", "part 2"];
+        let rows = self
+            .days
+            .iter()
+            .map(|d| {
+                [
+                    format!("{:03}", d.day),
+                    d.title.to_string(),
+                    d.validation.part_one.to_string(),
+                    d.validation.part_two.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        write_table(f, &header, &rows)?;
+
+        for d in self.days.iter() {
+            let diff = d.validation.diff();
+            if !diff.is_empty() {
+                writeln!(f, "\nday {:03}:\n{}", d.day, diff)?;
+            }
+        }
+
+        for error in self.errors.iter() {
+            writeln!(f, "\nday {:03} failed: {}", error.day, error.reason)?;
+        }
+
+        for skipped in self.skipped.iter() {
+            writeln!(f, "skipped day {}: {}", skipped.day, skipped.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run every implemented day against its input and compare the answers to
+/// the ones embedded in each day, exiting nonzero if any of them differ.
+///
+/// Inputs are expected at `<INPUT_ROOT>/day-XXX-*/input.txt` or in the input
+/// cache. Days without an input or known answers are skipped.
+#[derive(Args)]
+pub(crate) struct Verify {
+    /// Check against the answers in this file instead, for inputs other than
+    /// the ones the days were written against.
+    #[clap(short, long)]
+    answers: Option<PathBuf>,
+
+    /// The directory containing the day directories.
+    #[clap(short, long, default_value = ".", env = "AOC_INPUT_ROOT")]
+    input_root: PathBuf,
+}
+
+impl Verify {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let answers = Answers::resolve(self.answers.as_deref(), year)?;
+
+        let mut verification = Verification::default();
+
+        for entry in for_year(year) {
+            let day = entry.day;
+            let expected = match answers.get(day) {
+                Some(expected) => expected,
+                None => {
+                    verification.skipped.push(Skipped {
+                        day,
+                        reason: "no known answers".into(),
+                    });
+                    continue;
+                }
+            };
+
+            let input = match find_input(&self.input_root, year, day) {
+                Ok(path) => std::fs::read_to_string(path).context("Could not read input file")?,
+                Err(e) => {
+                    verification.skipped.push(Skipped {
+                        day,
+                        reason: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            match solve_timed(entry, &input) {
+                Ok(report) => verification.days.push(DayCheck {
+                    day,
+                    title: entry.title,
+                    validation: Validation {
+                        part_one: Check::new(expected.part_one().as_deref(), &report.part_one),
+                        part_two: Check::new(expected.part_two().as_deref(), &report.part_two),
+                    },
+                }),
+                Err(e) => verification.errors.push(Skipped {
+                    day,
+                    reason: format!("{:#}", e),
+                }),
+            }
+        }
+
+        print!("{}", verification);
+
+        let failures = verification.failures();
+        if failures > 0 {
+            bail!("{} check(s) failed", failures);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers() {
+        let answers = Answers::parse(
+            "[1]\npart_one = 69795\npart_two = \"208437\"\n\n[10]\npart_two = \"\\n#.\\n.#\"\n",
+        )
+        .unwrap();
+
+        let one = answers.get(1).unwrap();
+        assert_eq!(one.part_one(), Some("69795".to_string()));
+        assert_eq!(one.part_two(), Some("208437".to_string()));
+
+        let ten = answers.get(10).unwrap();
+        assert_eq!(ten.part_one(), None);
+        assert_eq!(ten.part_two(), Some("\n#.\n.#".to_string()));
+
+        assert!(answers.get(2).is_none());
+        assert!(Answers::parse("[one]\npart_one = 1\n").is_err());
+    }
+
+    #[test]
+    fn embedded() {
+        let answers = Answers::embedded(2022);
+        let one = answers.get(1).unwrap();
+        assert_eq!(one.part_one(), Some("69795".to_string()));
+        assert_eq!(answers.get(25).unwrap().part_two(), Some(String::new()));
+    }
+}
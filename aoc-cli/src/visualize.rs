@@ -0,0 +1,125 @@
+//! Animating the solutions that can render their intermediate state.
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_plumbing::{Problem, Visualize as Frames};
+use blizzard_basin::BlizzardBasin;
+use clap::Args;
+use monkey_map::MonkeyMap;
+use pyroclastic_flow::PyroclasticFlow;
+use regolith_reservoir::RegolithReservoir;
+use termimad::crossterm::{
+    cursor::MoveTo,
+    execute,
+    terminal::{Clear, ClearType},
+};
+use unstable_diffusion::UnstableDiffusion;
+
+use crate::cli::InputArgs;
+
+/// Animate a day against the given input, or its first example if `None`.
+type Animate = fn(Option<&str>, &Player) -> Result<()>;
+
+/// The days that can be visualized, by year.
+const ANIMATIONS: &[(usize, usize, Animate)] = &[
+    (2022, 14, animate::<RegolithReservoir>),
+    (2022, 17, animate::<PyroclasticFlow>),
+    (2022, 22, animate::<MonkeyMap>),
+    (2022, 23, animate::<UnstableDiffusion>),
+    (2022, 24, animate::<BlizzardBasin>),
+];
+
+/// Animate the solution for a specified day in the terminal.
+///
+/// Only some days can be visualized: 14 (falling sand), 17 (falling rocks),
+/// 22 (the walk around the map), 23 (spreading elves), and 24 (blizzards).
+#[derive(Args)]
+pub(crate) struct Visualize {
+    /// The day to visualize.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    player: Player,
+}
+
+impl Visualize {
+    pub fn run(&self, year: usize) -> Result<()> {
+        let (_, _, animate) = ANIMATIONS
+            .iter()
+            .find(|(y, d, _)| *y == year && *d == self.day)
+            .ok_or_else(|| anyhow!("Day {} of {} has no visualization", self.day, year))?;
+
+        if self.input.example {
+            return animate(None, &self.player);
+        }
+
+        let path = self.input.resolve(year, self.day)?;
+        let input = std::fs::read_to_string(path).context("Could not read input file")?;
+
+        animate(Some(&input), &self.player)
+    }
+}
+
+/// How the frames are displayed.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct Player {
+    /// Frames per second.
+    #[clap(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    fps: u32,
+
+    /// Only display every Nth frame. The final frame is always displayed.
+    #[clap(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    skip: u32,
+}
+
+impl Player {
+    fn play(&self, frames: impl Iterator<Item = String>) -> Result<()> {
+        let delay = Duration::from_secs(1) / self.fps;
+        let mut stdout = io::stdout();
+        let mut frames = frames.enumerate().peekable();
+
+        while let Some((idx, frame)) = frames.next() {
+            if idx % self.skip as usize != 0 && frames.peek().is_some() {
+                continue;
+            }
+
+            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+            write!(stdout, "{}", frame)?;
+            writeln!(stdout, "frame {}", idx + 1)?;
+            stdout.flush()?;
+
+            thread::sleep(delay);
+        }
+
+        Ok(())
+    }
+}
+
+fn animate<T>(input: Option<&str>, player: &Player) -> Result<()>
+where
+    T: Frames,
+    <T as Problem>::ProblemError: Into<anyhow::Error>,
+{
+    let input = match input {
+        Some(input) => input,
+        None => {
+            T::EXAMPLES
+                .first()
+                .ok_or_else(|| anyhow!("Day {} has no examples", T::DAY))?
+                .input
+        }
+    };
+
+    let instance = T::instance(input)
+        .map_err(|e| Into::<anyhow::Error>::into(T::ProblemError::from(e)))
+        .context("Failed to parse input")?;
+
+    player.play(instance.frames())
+}
@@ -0,0 +1,76 @@
+//! Re-running a solution whenever its input changes.
+use std::{
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Result};
+use clap::Args;
+
+use crate::{
+    cli::{_run, run_limited, InputArgs, SolveOptions},
+    registry::lookup,
+};
+
+/// Run the solution for a specified day, then run it again every time the
+/// input changes.
+///
+/// Errors (including running past `--timeout`) are displayed without exiting,
+/// so the input can be fixed up in place. Source changes require a rebuild,
+/// so use something like
+/// `cargo watch -x 'run -p aoc-cli -- run <DAY> <INPUT>'` for those.
+#[derive(Args)]
+pub(crate) struct Watch {
+    /// The day to run.
+    day: usize,
+
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// How often to check the input for changes, in milliseconds.
+    #[clap(long, default_value_t = 250)]
+    interval: u64,
+
+    #[command(flatten)]
+    options: SolveOptions,
+}
+
+impl Watch {
+    pub fn run(&self, year: usize) -> Result<()> {
+        if self.input.example {
+            bail!("The embedded examples can't change, so there's nothing to watch");
+        }
+
+        let entry = lookup(year, self.day)?;
+        let input = self.input.resolve(year, self.day)?;
+        let interval = Duration::from_millis(self.interval);
+        // doubly optional so that we always run at least once
+        let mut last_modified = None;
+
+        loop {
+            let modified = Some(modified(&input));
+            if modified != last_modified {
+                last_modified = modified;
+
+                println!("==> {}", input.display());
+                let input_file = input.clone();
+                let result = run_limited(&self.options, move |options| {
+                    _run(entry, &input_file, options)
+                });
+                if let Err(e) = result {
+                    println!("error: {:#}", e);
+                }
+                println!();
+            }
+
+            thread::sleep(interval);
+        }
+    }
+}
+
+/// The modification time of `path`, or `None` if it can't be determined (if
+/// the file is missing while an editor replaces it, for instance).
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,184 @@
+//! Macros for the boilerplate shared by every day. These are re-exported by
+//! `aoc-plumbing`, and the generated code expects it to be available as
+//! `aoc_plumbing`.
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Error, Expr, ExprLit, ExprTuple, ImplItem, ItemImpl, Lit,
+    LitInt, LitStr,
+};
+
+#[derive(Default)]
+struct Args {
+    day: Option<LitInt>,
+    title: Option<LitStr>,
+    year: Option<LitInt>,
+    full_dataset: Option<ExprTuple>,
+}
+
+/// Fill in the boilerplate for an `impl Problem` (or `impl BorrowedProblem`)
+/// block.
+///
+/// ```ignore
+/// #[aoc_problem(day = 25, title = "full of hot air", year = 2022, full_dataset = ("2=112--220-=-00=-=20", ""))]
+/// impl Problem for FullOfHotAir {
+///     const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "2=-1=0", "0")];
+///
+///     type ProblemError = anyhow::Error;
+///     // ...
+/// }
+/// ```
+///
+/// This defines `DAY`, `TITLE`, and `README` (from the crate's `README.md`)
+/// and generates a `problem_tests` module checking every one of the `EXAMPLES`
+/// with `aoc_plumbing::example_tests!`.
+///
+/// * `year` also registers the problem with `aoc_plumbing::register!`.
+/// * `full_dataset` gives the answers for the day's `input.txt` as integer or
+///   string literals, as they would be displayed. They become the `EXPECTED`
+///   answers, and an ignored test validates `input.txt` against them.
+///
+/// Neither `year` nor the tests are supported for generic impls, which should
+/// register (and test, with `example_tests!`) the concrete type separately.
+#[proc_macro_attribute]
+pub fn aoc_problem(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("day") {
+            args.day = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("title") {
+            args.title = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("year") {
+            args.year = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("full_dataset") {
+            args.full_dataset = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("expected one of: day, title, year, full_dataset"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(attr with parser);
+    let item = parse_macro_input!(item as ItemImpl);
+
+    expand(args, item)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(args: Args, mut item: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+    let missing = |name: &str| Error::new(Span::call_site(), format!("missing `{}`", name));
+    let day = args.day.ok_or_else(|| missing("day"))?;
+    let title = args.title.ok_or_else(|| missing("title"))?;
+
+    let generic = !item.generics.params.is_empty();
+    if generic && (args.year.is_some() || args.full_dataset.is_some()) {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "`year` and `full_dataset` require a concrete type, register the type alias instead",
+        ));
+    }
+
+    let expected = args.full_dataset.as_ref().map(answers).transpose()?;
+
+    let mut defined = vec!["DAY", "TITLE", "README"];
+    if expected.is_some() {
+        defined.push("EXPECTED");
+    }
+
+    for item in item.items.iter() {
+        if let ImplItem::Const(c) = item {
+            if defined.iter().any(|name| c.ident == name) {
+                return Err(Error::new_spanned(
+                    &c.ident,
+                    format!("`{}` is already defined by #[aoc_problem]", c.ident),
+                ));
+            }
+        }
+    }
+
+    item.items.splice(
+        0..0,
+        [
+            parse_quote!(const DAY: usize = #day;),
+            parse_quote!(const TITLE: &'static str = #title;),
+            parse_quote!(
+                const README: &'static str = include_str!("../README.md");
+            ),
+        ],
+    );
+
+    if let Some((part_one, part_two)) = &expected {
+        item.items.insert(
+            3,
+            parse_quote!(
+                const EXPECTED: Option<aoc_plumbing::Expected> =
+                    Some(aoc_plumbing::Expected::new(#part_one, #part_two));
+            ),
+        );
+    }
+
+    let self_ty = &item.self_ty;
+
+    let register = args
+        .year
+        .map(|year| quote!(aoc_plumbing::register!(#self_ty, #year);));
+
+    let full_dataset = expected.is_some().then(|| {
+        quote! {
+            #[test]
+            #[ignore]
+            fn full_dataset() {
+                let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
+                let validation = <#self_ty as aoc_plumbing::BorrowedProblem>::validate(&input)
+                    .unwrap_or_else(|_| panic!("Failed to solve"));
+                let diff = validation.diff();
+                assert!(diff.is_empty(), "wrong answer(s) for input.txt\n{}", diff);
+            }
+        }
+    });
+
+    let tests = (!generic).then(|| {
+        quote! {
+            #[cfg(test)]
+            mod problem_tests {
+                use super::*;
+
+                aoc_plumbing::example_tests!(#self_ty);
+
+                #full_dataset
+            }
+        }
+    });
+
+    Ok(quote! {
+        #item
+        #register
+        #tests
+    })
+}
+
+/// The displayed answers in a `full_dataset` tuple, e.g. `(11720, "####...")`.
+fn answers(tuple: &ExprTuple) -> syn::Result<(LitStr, LitStr)> {
+    if tuple.elems.len() != 2 {
+        return Err(Error::new_spanned(
+            tuple,
+            "expected the answers to both parts, e.g. `(4536, 2606)`",
+        ));
+    }
+
+    let answer = |expr: &Expr| match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(i), ..
+        }) => Ok(LitStr::new(i.base10_digits(), i.span())),
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => Ok(s.clone()),
+        _ => Err(Error::new_spanned(
+            expr,
+            "expected an integer or string literal, as the answer would be displayed",
+        )),
+    };
+
+    Ok((answer(&tuple.elems[0])?, answer(&tuple.elems[1])?))
+}
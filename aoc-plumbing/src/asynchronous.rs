@@ -0,0 +1,124 @@
+//! Solving from async code, behind the `async` feature.
+//!
+//! The solvers are blocking, so they run on tokio's blocking pool. Dropping
+//! the future (say, because a request went away or a timeout elapsed) cancels
+//! the solution, which stops at its next checkpoint if it has any.
+use std::{future::Future, pin::Pin};
+
+use crate::{cancellation::CancellationToken, Problem, Solution};
+
+/// What [Problem::solve_async] returns.
+pub type SolveFuture<T> = Pin<
+    Box<
+        dyn Future<
+                Output = Result<
+                    Solution<<T as Problem>::P1, <T as Problem>::P2>,
+                    <T as Problem>::ProblemError,
+                >,
+            > + Send,
+    >,
+>;
+
+/// Cancels its token when dropped, unless the solution already finished.
+struct CancelOnDrop(Option<CancellationToken>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel();
+        }
+    }
+}
+
+pub(crate) async fn solve<T>(
+    raw_input: String,
+    token: CancellationToken,
+) -> Result<Solution<T::P1, T::P2>, T::ProblemError>
+where
+    T: Problem + 'static,
+    T::P1: Send + 'static,
+    T::P2: Send + 'static,
+{
+    let mut guard = CancelOnDrop(Some(token.clone()));
+    let res = tokio::task::spawn_blocking(move || T::solve_cancellable(&raw_input, &token)).await;
+    guard.0 = None;
+
+    match res {
+        Ok(solution) => solution,
+        // the same as if it had been solved synchronously
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => panic!("Solver task failed: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use crate::cancellation::{checkpoint, Cancelled};
+
+    use super::*;
+
+    /// Spins until it's cancelled, if the input says to.
+    struct Spinner(bool);
+
+    impl FromStr for Spinner {
+        type Err = Cancelled;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(s == "spin"))
+        }
+    }
+
+    impl Problem for Spinner {
+        const DAY: usize = 1;
+        const TITLE: &'static str = "spinner";
+        const README: &'static str = "";
+
+        type ProblemError = Cancelled;
+        type P1 = usize;
+        type P2 = usize;
+
+        fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+            if self.0 {
+                loop {
+                    checkpoint()?;
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            }
+            Ok(1)
+        }
+
+        fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+            Ok(2)
+        }
+    }
+
+    #[tokio::test]
+    async fn solved() {
+        let token = CancellationToken::new();
+        let solution = Spinner::solve_async("stop".into(), token.clone()).await;
+
+        assert_eq!(solution, Ok(Solution::new(1, 2)));
+        assert!(!token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancelled_on_drop() {
+        let token = CancellationToken::new();
+        let solving = Spinner::solve_async("spin".into(), token.clone());
+
+        let res = tokio::time::timeout(Duration::from_millis(20), solving).await;
+        assert!(res.is_err());
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancelled_by_token() {
+        let token = CancellationToken::new();
+        let solving = tokio::spawn(Spinner::solve_async("spin".into(), token.clone()));
+
+        token.cancel();
+        assert_eq!(solving.await.unwrap(), Err(Cancelled));
+    }
+}
@@ -1,9 +1,14 @@
+//! Bitmask helpers, for sets of small integers (or chars).
+use thiserror::Error;
+
+/// The bit for a letter, with `a-z` then `A-Z`. Anything else gives garbage,
+/// see [try_char_to_mask] for a checked version.
 #[inline]
 pub fn char_to_mask(ch: char) -> u64 {
     let v = if ch.is_lowercase() {
-        ((ch as u8) - ('a' as u8)) as usize
+        ((ch as u8) - b'a') as usize
     } else {
-        ((ch as u8) - ('A' as u8) + 26) as usize
+        ((ch as u8) - b'A' + 26) as usize
     };
     mask(v)
 }
@@ -13,11 +18,286 @@ pub fn mask(shift: usize) -> u64 {
     1 << shift
 }
 
+/// The number of a letter, with `a-z` as `0..26` then `A-Z` as `26..52`.
+/// Anything else gives garbage, see [try_char_to_num] for a checked version.
 #[inline]
 pub fn char_to_num(ch: char) -> u8 {
     if ch.is_lowercase() {
-        (ch as u8) - ('a' as u8)
+        (ch as u8) - b'a'
     } else {
-        (ch as u8) - ('A' as u8) + 26
+        (ch as u8) - b'A' + 26
+    }
+}
+
+/// A char outside of the range a mask covers.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Error)]
+#[error("no mask for {0:?}")]
+pub struct InvalidChar(pub char);
+
+/// [char_to_num], failing for anything other than an ascii letter.
+///
+/// ```
+/// use aoc_plumbing::bits::{try_char_to_num, InvalidChar};
+/// assert_eq!(try_char_to_num('c'), Ok(2));
+/// assert_eq!(try_char_to_num('C'), Ok(28));
+/// assert_eq!(try_char_to_num('\n'), Err(InvalidChar('\n')));
+/// assert_eq!(try_char_to_num('é'), Err(InvalidChar('é')));
+/// ```
+#[inline]
+pub fn try_char_to_num(ch: char) -> Result<u8, InvalidChar> {
+    match ch {
+        'a'..='z' => Ok(ch as u8 - b'a'),
+        'A'..='Z' => Ok(ch as u8 - b'A' + 26),
+        _ => Err(InvalidChar(ch)),
+    }
+}
+
+/// [char_to_mask], failing for anything other than an ascii letter.
+///
+/// ```
+/// use aoc_plumbing::bits::{try_char_to_mask, InvalidChar};
+/// assert_eq!(try_char_to_mask('b'), Ok(0b10));
+/// assert_eq!(try_char_to_mask('1').unwrap_err().to_string(), "no mask for '1'");
+/// ```
+#[inline]
+pub fn try_char_to_mask(ch: char) -> Result<u64, InvalidChar> {
+    try_char_to_num(ch).map(|n| mask(n as usize))
+}
+
+/// The number of any printable ascii char (including space), which is its
+/// offset from space, so `0..95`. This covers digits and punctuation as well
+/// as letters, for days with wider alphabets.
+///
+/// ```
+/// use aoc_plumbing::bits::{char_to_wide_num, InvalidChar};
+/// assert_eq!(char_to_wide_num(' '), Ok(0));
+/// assert_eq!(char_to_wide_num('0'), Ok(16));
+/// assert_eq!(char_to_wide_num('~'), Ok(94));
+/// assert_eq!(char_to_wide_num('\t'), Err(InvalidChar('\t')));
+/// ```
+#[inline]
+pub fn char_to_wide_num(ch: char) -> Result<u8, InvalidChar> {
+    match ch {
+        ' '..='~' => Ok(ch as u8 - b' '),
+        _ => Err(InvalidChar(ch)),
     }
 }
+
+/// The bit for any printable ascii char, see [char_to_wide_num].
+///
+/// ```
+/// use aoc_plumbing::bits::char_to_wide_mask;
+/// let seen = "a1!a".chars().map(char_to_wide_mask).try_fold(0, |acc, m| m.map(|m| acc | m));
+///
+/// assert_eq!(seen.unwrap().count_ones(), 3);
+/// ```
+#[inline]
+pub fn char_to_wide_mask(ch: char) -> Result<u128, InvalidChar> {
+    char_to_wide_num(ch).map(|n| 1 << n)
+}
+
+const WORD: usize = u64::BITS as usize;
+
+/// A growable set of small integers, stored as a bitmask.
+///
+/// Trailing empty words are never kept, so sets with the same members are
+/// equal (and hash the same) however they got there.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::bits::BitSet;
+/// let mut open = BitSet::new();
+/// assert!(open.set(3));
+/// assert!(open.set(130));
+/// assert!(!open.set(3));
+///
+/// assert!(open.test(130));
+/// assert!(!open.test(4));
+/// assert_eq!(open.count(), 2);
+/// assert_eq!(open.iter().collect::<Vec<_>>(), vec![3, 130]);
+///
+/// assert!(open.clear(130));
+/// assert_eq!(open, BitSet::from_iter([3]));
+/// ```
+///
+/// Sets combine with the usual operators, by reference:
+///
+/// ```
+/// use aoc_plumbing::bits::BitSet;
+/// let a = BitSet::from_iter([1, 2, 100]);
+/// let b = BitSet::from_iter([2, 3]);
+///
+/// assert_eq!(&a | &b, BitSet::from_iter([1, 2, 3, 100]));
+/// assert_eq!(&a & &b, BitSet::from_iter([2]));
+/// assert_eq!(&a ^ &b, BitSet::from_iter([1, 3, 100]));
+/// assert_eq!(&a - &b, BitSet::from_iter([1, 100]));
+///
+/// let mut c = a.clone();
+/// c -= &BitSet::from_iter([100]);
+/// assert_eq!(c, BitSet::from_iter([1, 2]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set that can hold members below `bits` without growing.
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(bits / WORD + 1),
+        }
+    }
+
+    /// Add `idx`, returning whether it was newly added.
+    pub fn set(&mut self, idx: usize) -> bool {
+        let (word, bit) = (idx / WORD, mask(idx % WORD));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Remove `idx`, returning whether it was present.
+    pub fn clear(&mut self, idx: usize) -> bool {
+        let (word, bit) = (idx / WORD, mask(idx % WORD));
+        match self.words.get_mut(word) {
+            Some(w) if *w & bit != 0 => {
+                *w &= !bit;
+                self.trim();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn test(&self, idx: usize) -> bool {
+        self.words
+            .get(idx / WORD)
+            .map(|w| w & mask(idx % WORD) != 0)
+            .unwrap_or(false)
+    }
+
+    /// Remove every member.
+    pub fn reset(&mut self) {
+        self.words.clear();
+    }
+
+    /// The number of members.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The members, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            let mut remaining = word;
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                Some(idx * WORD + bit)
+            })
+        })
+    }
+
+    /// Whether every member of this is also in `other`.
+    ///
+    /// ```
+    /// use aoc_plumbing::bits::BitSet;
+    /// let small = BitSet::from_iter([1, 70]);
+    /// let big = BitSet::from_iter([1, 2, 70]);
+    ///
+    /// assert!(small.is_subset(&big));
+    /// assert!(!big.is_subset(&small));
+    /// assert!(small.is_disjoint(&BitSet::from_iter([2, 3])));
+    /// ```
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words.len() <= other.words.len()
+            && self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .all(|(a, b)| a & b == 0)
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+
+        for (idx, word) in self.words.iter_mut().enumerate() {
+            *word = op(*word, other.words.get(idx).copied().unwrap_or_default());
+        }
+
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for idx in iter {
+            self.set(idx);
+        }
+    }
+}
+
+macro_rules! bitset_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $f:expr) => {
+        impl std::ops::$assign<&BitSet> for BitSet {
+            fn $assign_method(&mut self, rhs: &BitSet) {
+                self.combine(rhs, $f);
+            }
+        }
+
+        impl std::ops::$op<&BitSet> for &BitSet {
+            type Output = BitSet;
+
+            fn $method(self, rhs: &BitSet) -> BitSet {
+                let mut out = self.clone();
+                out.combine(rhs, $f);
+                out
+            }
+        }
+    };
+}
+
+bitset_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| a | b);
+bitset_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| a & b);
+bitset_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| a ^ b);
+bitset_op!(Sub, sub, SubAssign, sub_assign, |a, b| a & !b);
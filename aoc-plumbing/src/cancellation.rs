@@ -0,0 +1,105 @@
+//! Cooperative cancellation of long-running solvers.
+//!
+//! A frontend runs a solution with [with_cancellation], and the solver calls
+//! [checkpoint] every so often, which fails once the token is cancelled. The
+//! token is per thread, so solvers using rayon should grab the [current] token
+//! before going parallel and check that instead.
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A flag shared between a solver and whatever wants it to stop.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the solver to stop at its next checkpoint.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail if this has been cancelled.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The error from a cancelled solver.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `token` as the current thread's token, restoring the previous
+/// one afterwards.
+///
+/// ```
+/// use aoc_plumbing::cancellation::{checkpoint, with_cancellation, Cancelled, CancellationToken};
+///
+/// let token = CancellationToken::new();
+/// assert_eq!(with_cancellation(&token, checkpoint), Ok(()));
+///
+/// token.cancel();
+/// assert_eq!(with_cancellation(&token, checkpoint), Err(Cancelled));
+///
+/// // nothing to cancel outside of one
+/// assert_eq!(checkpoint(), Ok(()));
+/// ```
+pub fn with_cancellation<F, R>(token: &CancellationToken, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<CancellationToken>);
+
+    // so a panicking solver doesn't leave its token behind
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT.with(|current| current.replace(Some(token.clone())));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// The current thread's token, if there is one.
+pub fn current() -> Option<CancellationToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Fail if the current thread's token has been cancelled.
+pub fn checkpoint() -> Result<(), Cancelled> {
+    CURRENT.with(|current| match current.borrow().as_ref() {
+        Some(token) => token.check(),
+        None => Ok(()),
+    })
+}
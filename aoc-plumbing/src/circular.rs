@@ -0,0 +1,289 @@
+//! A circle of values that can be walked, rearranged, and added to around
+//! any of its elements, for puzzles that shuffle things around a ring.
+use std::ops::{Index, IndexMut};
+
+/// A stable reference to a value in a [CircularList]. It keeps referring to
+/// the same value however the list is rearranged around it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Handle(usize);
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: Option<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// A doubly linked ring of values. Stepping around it, and inserting,
+/// removing, or moving a value relative to a [Handle], are all O(1), other
+/// than the walking needed to find where to move to.
+///
+/// Indexing with the [Handle] of a removed value panics, see
+/// [CircularList::get] for a checked version.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::circular::CircularList;
+/// let mut list: CircularList<_> = ['a', 'b', 'c', 'd'].into_iter().collect();
+/// let b = list.handles().nth(1).unwrap();
+///
+/// // move b two places forward, past c and d
+/// list.shift(b, 2);
+/// assert_eq!(list.iter().collect::<String>(), "acdb");
+///
+/// // and everything is still connected in a circle
+/// assert_eq!(list[list.next(b)], 'a');
+/// assert_eq!(list[list.nth(b, -2)], 'c');
+///
+/// list.insert_after(b, 'e');
+/// assert_eq!(list.remove(b), Some('b'));
+/// assert_eq!(list.iter().collect::<String>(), "acde");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircularList<T> {
+    nodes: Vec<Node<T>>,
+    head: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            head: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+            ..Default::default()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The first value, where iterating starts, unless the list is empty.
+    pub fn front(&self) -> Option<Handle> {
+        self.head.map(Handle)
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.get(handle.0)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        self.nodes.get_mut(handle.0)?.value.as_mut()
+    }
+
+    /// Add `value` to the end, just before the [CircularList::front].
+    pub fn push_back(&mut self, value: T) -> Handle {
+        match self.head {
+            Some(head) => self.insert_after(Handle(self.nodes[head].prev), value),
+            None => {
+                let idx = self.nodes.len();
+                self.nodes.push(Node {
+                    value: Some(value),
+                    prev: idx,
+                    next: idx,
+                });
+                self.head = Some(idx);
+                self.len = 1;
+                Handle(idx)
+            }
+        }
+    }
+
+    /// Add `value` just after the one at `anchor`, which must be in the list.
+    pub fn insert_after(&mut self, anchor: Handle, value: T) -> Handle {
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            value: Some(value),
+            prev: idx,
+            next: idx,
+        });
+        self.link_after(anchor.0, idx);
+        Handle(idx)
+    }
+
+    /// Take the value at `handle` out of the list, if it's still there.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let value = self.nodes.get_mut(handle.0)?.value.take()?;
+        self.unlink(handle.0);
+        Some(value)
+    }
+
+    /// The handle of the value after the one at `handle`.
+    pub fn next(&self, handle: Handle) -> Handle {
+        Handle(self.nodes[handle.0].next)
+    }
+
+    /// The handle of the value before the one at `handle`.
+    pub fn prev(&self, handle: Handle) -> Handle {
+        Handle(self.nodes[handle.0].prev)
+    }
+
+    /// The handle `n` places after the one at `handle`, or before it if `n`
+    /// is negative, walking whichever way around the circle is shorter.
+    pub fn nth(&self, handle: Handle, n: i64) -> Handle {
+        Handle(self.walk(handle.0, n, self.len))
+    }
+
+    /// Move the value at `handle` `n` places forward, or backward if `n` is
+    /// negative, past the other values. Moving past all of them brings it
+    /// back to where it started.
+    pub fn shift(&mut self, handle: Handle, n: i64) {
+        let others = self.len as i64 - 1;
+        if others < 1 || n.rem_euclid(others) == 0 {
+            return;
+        }
+
+        let prev = self.nodes[handle.0].prev;
+        self.unlink(handle.0);
+        let anchor = self.walk(prev, n, others as usize);
+        self.link_after(anchor, handle.0);
+    }
+
+    /// The handles of every value still in the list, in the order they were
+    /// added.
+    pub fn handles(&self) -> impl Iterator<Item = Handle> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.value.is_some())
+            .map(|(idx, _)| Handle(idx))
+    }
+
+    /// The values in order around the circle, from the
+    /// [CircularList::front].
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut cur = self.head;
+        (0..self.len).filter_map(move |_| {
+            let node = &self.nodes[cur?];
+            cur = Some(node.next);
+            node.value.as_ref()
+        })
+    }
+
+    /// Walk `n` steps from `idx` in a circle of `len`, the shorter way round.
+    fn walk(&self, mut idx: usize, n: i64, len: usize) -> usize {
+        if len == 0 {
+            return idx;
+        }
+
+        let len = len as i64;
+        let forward = n.rem_euclid(len);
+        if forward <= len / 2 {
+            for _ in 0..forward {
+                idx = self.nodes[idx].next;
+            }
+        } else {
+            for _ in 0..(len - forward) {
+                idx = self.nodes[idx].prev;
+            }
+        }
+
+        idx
+    }
+
+    fn link_after(&mut self, anchor: usize, idx: usize) {
+        let next = self.nodes[anchor].next;
+        self.nodes[idx].prev = anchor;
+        self.nodes[idx].next = next;
+        self.nodes[anchor].next = idx;
+        self.nodes[next].prev = idx;
+        self.len += 1;
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let Node { prev, next, .. } = self.nodes[idx];
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+        self.nodes[idx].prev = idx;
+        self.nodes[idx].next = idx;
+        self.len -= 1;
+
+        if self.head == Some(idx) {
+            self.head = (self.len > 0).then_some(next);
+        }
+    }
+}
+
+impl<T> Index<Handle> for CircularList<T> {
+    type Output = T;
+
+    fn index(&self, handle: Handle) -> &Self::Output {
+        self.get(handle).expect("value was removed")
+    }
+}
+
+impl<T> IndexMut<Handle> for CircularList<T> {
+    fn index_mut(&mut self, handle: Handle) -> &mut Self::Output {
+        self.get_mut(handle).expect("value was removed")
+    }
+}
+
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut list = Self::with_capacity(iter.size_hint().0);
+        for value in iter {
+            list.push_back(value);
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixing() {
+        // the example from 2022 day 20, where each number moves by itself
+        let mut list: CircularList<i64> = [1, 2, -3, 3, -2, 0, 4].into_iter().collect();
+        for handle in list.handles().collect::<Vec<_>>() {
+            list.shift(handle, list[handle]);
+        }
+
+        let zero = list.handles().find(|h| list[*h] == 0).unwrap();
+        let mixed = (0..7).map(|n| list[list.nth(zero, n)]).collect::<Vec<_>>();
+        assert_eq!(mixed, vec![0, 3, -2, 1, 2, -3, 4]);
+    }
+
+    #[test]
+    fn emptying() {
+        let mut list: CircularList<_> = (0..3).collect();
+        let handles = list.handles().collect::<Vec<_>>();
+        for handle in handles.iter() {
+            list.shift(*handle, 100);
+        }
+        assert_eq!(list.len(), 3);
+
+        for handle in handles.iter() {
+            assert!(list.remove(*handle).is_some());
+        }
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+        assert_eq!(list.remove(handles[0]), None);
+        assert_eq!(list.iter().count(), 0);
+
+        let only = list.push_back(7);
+        assert_eq!(list.front(), Some(only));
+        assert_eq!(list.next(only), only);
+    }
+}
@@ -0,0 +1,132 @@
+//! Finding where a simulation starts repeating, so its value at some
+//! absurdly distant step can be worked out without running that far.
+use std::hash::Hash;
+
+use num::PrimInt;
+use rustc_hash::FxHashMap;
+
+/// Remembers the step and value at which each state was first seen.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::cycle::CycleDetector;
+/// // a counter that goes up by 3 every step, with a state that repeats every 4
+/// let mut detector = CycleDetector::new();
+/// let mut found = None;
+/// for step in 0.. {
+///     if let Some(cycle) = detector.observe(step % 4, step, step as u64 * 3) {
+///         found = cycle.value_at(1_000_000_000);
+///         if found.is_some() {
+///             break;
+///         }
+///     }
+/// }
+///
+/// assert_eq!(found, Some(3_000_000_000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CycleDetector<S, V> {
+    seen: FxHashMap<S, (usize, V)>,
+}
+
+impl<S, V> Default for CycleDetector<S, V> {
+    fn default() -> Self {
+        Self {
+            seen: FxHashMap::default(),
+        }
+    }
+}
+
+impl<S, V> CycleDetector<S, V>
+where
+    S: Hash + Eq,
+    V: PrimInt,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `state` was reached at `step` with `value`, returning the
+    /// cycle back to the first time it was reached, if it has been before.
+    pub fn observe(&mut self, state: S, step: usize, value: V) -> Option<Cycle<V>> {
+        let (start, start_value) = *self.seen.entry(state).or_insert((step, value));
+        if start == step {
+            None
+        } else {
+            Some(Cycle {
+                start,
+                start_value,
+                step,
+                value,
+            })
+        }
+    }
+}
+
+/// The same state seen at two steps, along with the value at each.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Cycle<V> {
+    pub start: usize,
+    pub start_value: V,
+    pub step: usize,
+    pub value: V,
+}
+
+impl<V: PrimInt> Cycle<V> {
+    /// The number of steps before the state repeats.
+    pub fn period(&self) -> usize {
+        self.step - self.start
+    }
+
+    /// How much the value changes over each period.
+    pub fn delta(&self) -> V {
+        self.value - self.start_value
+    }
+
+    /// The value at `target`, if `target` is a whole number of periods on
+    /// from [Cycle::step]. Otherwise keep going until it is.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::cycle::Cycle;
+    /// let cycle = Cycle { start: 3, start_value: 10, step: 8, value: 17 };
+    ///
+    /// assert_eq!(cycle.period(), 5);
+    /// assert_eq!(cycle.value_at(18), Some(31));
+    /// assert_eq!(cycle.value_at(19), None);
+    /// assert_eq!(cycle.value_at(3), None);
+    /// ```
+    pub fn value_at(&self, target: usize) -> Option<V> {
+        extrapolate(self.value, self.step, self.period(), self.delta(), target)
+    }
+}
+
+/// The value at step `target` of something that was `value` at `step`, and
+/// changes by `delta` every `period` steps, if `target` is a whole number of
+/// periods after `step`.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::cycle::extrapolate;
+/// assert_eq!(extrapolate(100_i64, 10, 7, -3, 24), Some(94));
+/// assert_eq!(extrapolate(100_i64, 10, 7, -3, 25), None);
+/// ```
+pub fn extrapolate<V: PrimInt>(
+    value: V,
+    step: usize,
+    period: usize,
+    delta: V,
+    target: usize,
+) -> Option<V> {
+    let elapsed = target.checked_sub(step)?;
+    let periods = elapsed.checked_div(period)?;
+    if periods * period != elapsed {
+        return None;
+    }
+
+    let periods = V::from(periods)?;
+    delta
+        .checked_mul(&periods)
+        .and_then(|gain| value.checked_add(&gain))
+}
@@ -0,0 +1,149 @@
+//! The four compass directions, for the many puzzles that walk around a grid.
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::geometry::Point;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// The direction after turning right.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::direction::Direction;
+    /// assert_eq!(Direction::North.right(), Direction::East);
+    /// assert_eq!(Direction::West.right(), Direction::North);
+    /// ```
+    pub fn right(&self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    /// The direction after turning left.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::direction::Direction;
+    /// assert_eq!(Direction::North.left(), Direction::West);
+    /// assert_eq!(Direction::East.left().right(), Direction::East);
+    /// ```
+    pub fn left(&self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    /// The direction after turning around.
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    /// A single step in this direction, with y increasing northwards.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::{direction::Direction, geometry::Point};
+    /// assert_eq!(Direction::North.delta(), Point::new(0, 1));
+    /// assert_eq!(Direction::West.delta(), Point::new(-1, 0));
+    /// ```
+    pub fn delta(&self) -> Point {
+        match self {
+            Self::North => Point::new(0, 1),
+            Self::East => Point::new(1, 0),
+            Self::South => Point::new(0, -1),
+            Self::West => Point::new(-1, 0),
+        }
+    }
+
+    /// A single step in this direction, with y increasing southwards, as it
+    /// does for the rows of a grid read from the input.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::{direction::Direction, geometry::Point};
+    /// assert_eq!(Direction::North.screen_delta(), Point::new(0, -1));
+    /// assert_eq!(Direction::West.screen_delta(), Point::new(-1, 0));
+    /// ```
+    pub fn screen_delta(&self) -> Point {
+        self.delta().reflect_x()
+    }
+
+    /// A single step in this direction as `(rows, cols)`, for moving around
+    /// a grid with [offset](crate::grid::offset).
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::direction::Direction;
+    /// assert_eq!(Direction::North.grid_delta(), (-1, 0));
+    /// assert_eq!(Direction::East.grid_delta(), (0, 1));
+    /// ```
+    pub fn grid_delta(&self) -> (i64, i64) {
+        let delta = self.screen_delta();
+        (delta.y, delta.x)
+    }
+}
+
+/// The error for something that isn't a direction.
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("invalid direction: {0:?}")]
+pub struct InvalidDirection(pub String);
+
+impl TryFrom<char> for Direction {
+    type Error = InvalidDirection;
+
+    /// Accepts compass points (`N`), relative directions (`U`), and arrows
+    /// (`^`).
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::direction::{Direction, InvalidDirection};
+    /// assert_eq!(Direction::try_from('R'), Ok(Direction::East));
+    /// assert_eq!(Direction::try_from('v'), Ok(Direction::South));
+    /// assert_eq!(Direction::try_from('x'), Err(InvalidDirection("x".into())));
+    /// ```
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' | 'U' | '^' => Ok(Self::North),
+            'E' | 'R' | '>' => Ok(Self::East),
+            'S' | 'D' | 'v' => Ok(Self::South),
+            'W' | 'L' | '<' => Ok(Self::West),
+            _ => Err(InvalidDirection(value.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = InvalidDirection;
+
+    /// Parses a single character, as [Direction::try_from] does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => ch.try_into(),
+            _ => Err(InvalidDirection(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Errors with enough context for frontends to say what was wrong with an
+//! input, rather than just that something was.
+//!
+//! Days are free to keep using [anyhow::Error]: an [AocError] converts into
+//! one with `?`, and converting back recovers the original [AocError].
+use thiserror::Error;
+
+use crate::cancellation::Cancelled;
+
+/// What the days' nom parsers fail with.
+pub type NomError<'a> = nom::Err<nom::error::Error<&'a str>>;
+
+#[derive(Debug, Error)]
+pub enum AocError {
+    /// The input could not be parsed.
+    #[error("failed to parse line {line}, column {column} ({message}): `{snippet}`")]
+    Parse {
+        /// 1-indexed.
+        line: usize,
+        /// 1-indexed, in chars.
+        column: usize,
+        /// The whole line containing the failure.
+        snippet: String,
+        message: String,
+    },
+
+    /// The input parsed, but has no answer.
+    #[error("unsolvable input: {0}")]
+    UnsolvableInput(String),
+
+    /// The input is larger than the solution can handle.
+    #[error("{what} of {actual} exceeds the limit of {limit}")]
+    LimitExceeded {
+        what: &'static str,
+        limit: usize,
+        actual: usize,
+    },
+
+    #[error(transparent)]
+    Cancelled(#[from] Cancelled),
+
+    /// The input could not be read.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl AocError {
+    /// A parse error for the point in `input` at which `remaining` was left
+    /// unparsed, if that can be found.
+    ///
+    /// ```
+    /// use aoc_plumbing::AocError;
+    /// let input = "move 1 from 1 to 2\nmove x from 2 to 1\n";
+    ///
+    /// assert_eq!(
+    ///     AocError::parse(input, &input[24..], "Digit").unwrap().to_string(),
+    ///     "failed to parse line 2, column 6 (Digit): `move x from 2 to 1`"
+    /// );
+    /// ```
+    pub fn parse(input: &str, remaining: &str, message: impl Into<String>) -> Option<Self> {
+        let offset = Self::offset(input, remaining)?;
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+
+        Some(Self::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: input[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            message: message.into(),
+        })
+    }
+
+    /// Convert a nom error for `input` into a parse error at the location nom
+    /// stopped at, falling back to nom's own message if it can't be located.
+    pub fn from_nom(input: &str, error: NomError<'_>) -> Self {
+        match &error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::parse(input, e.input, e.code.description())
+            }
+            nom::Err::Incomplete(_) => None,
+        }
+        .unwrap_or_else(|| Self::Other(error.to_owned().into()))
+    }
+
+    /// Move a parse error of a single line to where that line is in the
+    /// whole input, for parsers that go a line at a time. Other errors are
+    /// left as they are.
+    ///
+    /// ```
+    /// use aoc_plumbing::AocError;
+    /// let error = AocError::parse("1,x", "x", "Digit").unwrap().on_line(12);
+    ///
+    /// assert_eq!(error.to_string(), "failed to parse line 12, column 3 (Digit): `1,x`");
+    /// ```
+    pub fn on_line(mut self, line_number: usize) -> Self {
+        if let Self::Parse { line, .. } = &mut self {
+            *line += line_number - 1;
+        }
+        self
+    }
+
+    /// Convert into an [anyhow::Error], unwrapping [AocError::Other] so the
+    /// error it came from is recovered as it was.
+    pub fn into_anyhow(self) -> anyhow::Error {
+        match self {
+            Self::Other(error) => error,
+            error => error.into(),
+        }
+    }
+
+    fn offset(input: &str, remaining: &str) -> Option<usize> {
+        // nom hands back a slice of what it was given, which is usually some
+        // trimmed or split part of the raw input
+        let start = input.as_ptr() as usize;
+        let pos = remaining.as_ptr() as usize;
+        if pos >= start && pos + remaining.len() <= start + input.len() {
+            return Some(pos - start);
+        }
+
+        // otherwise it's been copied, so look for it instead
+        [input, input.trim_end()]
+            .iter()
+            .find(|candidate| candidate.ends_with(remaining))
+            .map(|candidate| candidate.len() - remaining.len())
+            .or_else(|| input.find(remaining))
+    }
+}
+
+impl From<anyhow::Error> for AocError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<Self>() {
+            Ok(error) => error,
+            Err(error) => match error.downcast::<Cancelled>() {
+                Ok(cancelled) => Self::Cancelled(cancelled),
+                Err(error) => Self::Other(error),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::digit1, sequence::preceded, IResult};
+
+    use super::*;
+
+    fn count(s: &str) -> IResult<&str, &str> {
+        preceded(tag("move "), digit1)(s)
+    }
+
+    #[test]
+    fn parse() {
+        let input = "move 1 from 1 to 2\nmove x from 2 to 1\n";
+
+        // nom was given a line of the trimmed input
+        let line = input.trim().lines().nth(1).unwrap();
+        match AocError::from_nom(input, count(line).unwrap_err()) {
+            AocError::Parse {
+                line,
+                column,
+                snippet,
+                message,
+            } => {
+                assert_eq!((line, column), (2, 6));
+                assert_eq!(snippet, "move x from 2 to 1");
+                assert_eq!(message, "Digit");
+            }
+            e => panic!("unexpected {:?}", e),
+        }
+
+        // copies are found by searching for them
+        assert!(matches!(
+            AocError::parse(input, "", "Tag"),
+            Some(AocError::Parse {
+                line: 3,
+                column: 1,
+                ..
+            })
+        ));
+        assert!(AocError::parse(input, "nowhere", "Tag").is_none());
+    }
+
+    #[test]
+    fn anyhow_interop() {
+        let error: anyhow::Error = AocError::UnsolvableInput("no marker".into()).into();
+        let error = AocError::from(error);
+        assert_eq!(error.to_string(), "unsolvable input: no marker");
+
+        let error = AocError::from(anyhow::Error::from(Cancelled));
+        assert!(matches!(error, AocError::Cancelled(_)));
+
+        let error = AocError::from(anyhow::anyhow!("nope"));
+        assert!(matches!(error, AocError::Other(_)));
+        assert_eq!(error.to_string(), "nope");
+
+        let error = AocError::from(anyhow::anyhow!("nope").context("while solving"));
+        assert_eq!(error.into_anyhow().root_cause().to_string(), "nope");
+    }
+}
@@ -0,0 +1,220 @@
+//! The known-correct answers for a day's input, so the tests, `aoc verify`,
+//! and CI all check against the same ones.
+use std::fmt::Display;
+
+use crate::Solution;
+
+/// The answers for the `input.txt` a day was written against, as they would
+/// be displayed. A part without an answer (like the last day's second part)
+/// is expected to be empty.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{Check, Expected, Solution};
+/// let expected = Expected::new("24000", "45000");
+/// let validation = expected.check(&Solution::new(24000, 41000));
+///
+/// assert_eq!(validation.part_one, Check::Pass);
+/// assert_eq!(validation.failures(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Expected {
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Expected {
+    pub const fn new(part_one: &'static str, part_two: &'static str) -> Self {
+        Self {
+            part_one: Some(part_one),
+            part_two: Some(part_two),
+        }
+    }
+
+    /// Compare the displayed answers of `solution` to these.
+    pub fn check<T, G>(&self, solution: &Solution<T, G>) -> Validation
+    where
+        T: Display + serde::Serialize + PartialEq,
+        G: Display + serde::Serialize + PartialEq,
+    {
+        Validation {
+            part_one: Check::new(self.part_one, &solution.part_one.to_string()),
+            part_two: Check::new(self.part_two, &solution.part_two.to_string()),
+        }
+    }
+}
+
+/// The outcome of checking one part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There was no known answer for this part.
+    Unknown,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+            None => Self::Unknown,
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self, Self::Fail { .. })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { .. } => write!(f, "FAIL"),
+            Self::Unknown => write!(f, "-"),
+        }
+    }
+}
+
+/// The checks for both parts of a solution.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Validation {
+    pub part_one: Check,
+    pub part_two: Check,
+}
+
+impl Validation {
+    pub fn failures(&self) -> usize {
+        self.part_one.failed() as usize + self.part_two.failed() as usize
+    }
+
+    /// The parts that failed, as a [SolutionDiff].
+    pub fn diff(&self) -> SolutionDiff {
+        SolutionDiff {
+            part_one: PartDiff::from_check(&self.part_one),
+            part_two: PartDiff::from_check(&self.part_two),
+        }
+    }
+}
+
+/// A part whose displayed answers differ.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PartDiff {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl PartDiff {
+    /// The difference between two displayed answers, if there is one.
+    pub fn new(expected: &str, actual: &str) -> Option<Self> {
+        Self::from_check(&Check::new(Some(expected), actual))
+    }
+
+    fn from_check(check: &Check) -> Option<Self> {
+        match check {
+            Check::Fail { expected, actual } => Some(Self {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of two solutions whose displayed answers differ, from
+/// [Solution::diff]. It displays as a report of each part that differs, or as
+/// nothing if none do.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::Solution;
+/// let diff = Solution::new(24000, 45000).diff(&Solution::new(24000, 41000));
+///
+/// assert!(diff.part_one.is_none());
+/// assert_eq!(diff.differences(), 1);
+/// assert_eq!(diff.to_string(), "part 2:\nexpected: 45000\n     got: 41000");
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SolutionDiff {
+    pub part_one: Option<PartDiff>,
+    pub part_two: Option<PartDiff>,
+}
+
+impl SolutionDiff {
+    /// Whether the solutions were the same.
+    pub fn is_empty(&self) -> bool {
+        self.differences() == 0
+    }
+
+    /// The number of parts that differ.
+    pub fn differences(&self) -> usize {
+        self.part_one.is_some() as usize + self.part_two.is_some() as usize
+    }
+}
+
+impl Display for SolutionDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts = [(1, &self.part_one), (2, &self.part_two)]
+            .into_iter()
+            .filter_map(|(part, diff)| {
+                diff.as_ref().map(|diff| {
+                    format!(
+                        "part {}:\nexpected: {}\n     got: {}",
+                        part, diff.expected, diff.actual
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", parts.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks() {
+        assert_eq!(Check::new(Some("12"), "12"), Check::Pass);
+        assert_eq!(
+            Check::new(Some("12"), "13"),
+            Check::Fail {
+                expected: "12".into(),
+                actual: "13".into()
+            }
+        );
+        assert_eq!(Check::new(None, "13"), Check::Unknown);
+    }
+
+    #[test]
+    fn diffs() {
+        let validation = Expected::new("10", "#.\n.#").check(&Solution::new(10, "#.\n##"));
+        let diff = validation.diff();
+        assert_eq!(diff.differences(), 1);
+        assert_eq!(
+            diff.part_two,
+            Some(PartDiff {
+                expected: "#.\n.#".into(),
+                actual: "#.\n##".into()
+            })
+        );
+
+        let unknown = Expected {
+            part_one: None,
+            part_two: Some("4"),
+        };
+        assert!(unknown.check(&Solution::new(1, 4)).diff().is_empty());
+        assert_eq!(PartDiff::new("1", "1"), None);
+        assert_eq!(SolutionDiff::default().to_string(), "");
+    }
+}
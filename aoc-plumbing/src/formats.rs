@@ -0,0 +1,155 @@
+//! Serializing solutions in formats other than json, each behind a feature of
+//! the same name: `csv`, `toml`, and `msgpack`.
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{metadata::Metadata, Solution, TimedSolution};
+
+/// Something that can be serialized in every supported format.
+pub trait Formats: Serialize {
+    /// The names and displayed values of each field, for formats that can't
+    /// represent nesting. A part without an answer has an empty value.
+    fn record(&self) -> Vec<(&'static str, String)>;
+
+    /// A header row followed by a single row of values.
+    ///
+    /// ```
+    /// use aoc_plumbing::{formats::Formats, NoAnswer, Solution};
+    /// let s = Solution::new("#.\n.#", NoAnswer);
+    ///
+    /// assert_eq!(s.to_csv().unwrap(), "part_one,part_two\n\"#.\n.#\",\n");
+    /// ```
+    #[cfg(feature = "csv")]
+    fn to_csv(&self) -> Result<String, csv::Error> {
+        let (header, values): (Vec<_>, Vec<_>) = self.record().into_iter().unzip();
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(header)?;
+        writer.write_record(values)?;
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+        // everything written was a str
+        Ok(String::from_utf8(bytes).expect("csv should be utf-8"))
+    }
+
+    /// Parts without an answer are left out.
+    ///
+    /// ```
+    /// use aoc_plumbing::{formats::Formats, NoAnswer, Solution};
+    /// let s = Solution::new(24000, NoAnswer);
+    ///
+    /// assert_eq!(s.to_toml().unwrap(), "part_one = 24000\n");
+    /// ```
+    #[cfg(feature = "toml")]
+    fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Structs are encoded as maps, so fields are named.
+    ///
+    /// ```
+    /// use aoc_plumbing::{formats::Formats, Solution};
+    /// let s = Solution::new(1, 2);
+    ///
+    /// assert_eq!(
+    ///     s.to_msgpack().unwrap(),
+    ///     b"\x82\xa8part_one\x01\xa8part_two\x02"
+    /// );
+    /// ```
+    #[cfg(feature = "msgpack")]
+    fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+}
+
+impl<T, G> Formats for Solution<T, G>
+where
+    T: Display + Serialize + PartialEq,
+    G: Display + Serialize + PartialEq,
+{
+    fn record(&self) -> Vec<(&'static str, String)> {
+        let mut record = vec![
+            ("part_one", self.part_one.to_string()),
+            ("part_two", self.part_two.to_string()),
+        ];
+        record.extend(self.metadata.iter().flat_map(Metadata::record));
+        record
+    }
+}
+
+impl<T, G> Formats for TimedSolution<T, G>
+where
+    T: Display + Serialize + PartialEq,
+    G: Display + Serialize + PartialEq,
+{
+    fn record(&self) -> Vec<(&'static str, String)> {
+        let mut record = self.solution.record();
+        record.extend([
+            (
+                "peak_rss",
+                self.peak_rss.map(|b| b.to_string()).unwrap_or_default(),
+            ),
+            ("parse_ns", self.timings.parse.as_nanos().to_string()),
+            ("part_one_ns", self.timings.part_one.as_nanos().to_string()),
+            ("part_two_ns", self.timings.part_two.as_nanos().to_string()),
+        ]);
+        record.extend(self.metadata.iter().flat_map(Metadata::record));
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{NoAnswer, Timings};
+
+    use super::*;
+
+    fn timed() -> TimedSolution<usize, NoAnswer> {
+        TimedSolution {
+            solution: Solution::new(4, NoAnswer),
+            peak_rss: Some(2048),
+            timings: Timings {
+                parse: Duration::from_nanos(1),
+                part_one: Duration::from_nanos(2),
+                part_two: Duration::from_nanos(3),
+            },
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn records() {
+        assert_eq!(
+            timed().record(),
+            vec![
+                ("part_one", "4".to_string()),
+                ("part_two", String::new()),
+                ("peak_rss", "2048".to_string()),
+                ("parse_ns", "1".to_string()),
+                ("part_one_ns", "2".to_string()),
+                ("part_two_ns", "3".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn timed_toml() {
+        assert_eq!(
+            timed().to_toml().unwrap(),
+            "part_one = 4\npeak_rss = 2048\n\n[timings]\nparse_ns = 1\npart_one_ns = 2\npart_two_ns = 3\n"
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn timed_msgpack() {
+        let bytes = timed().to_msgpack().unwrap();
+        assert_eq!(
+            rmp_serde::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            serde_json::to_value(timed()).unwrap()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::math::gcd;
+
 /// A 2D Point of (i64, i64)
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct Point {
@@ -33,7 +35,6 @@ impl Point {
     pub fn neighbors(&self) -> impl Iterator<Item = Point> + '_ {
         (-1..=1)
             .cartesian_product(-1..=1)
-            .into_iter()
             .filter_map(move |(x, y)| {
                 if x == 0 && y == 0 {
                     None
@@ -42,6 +43,73 @@ impl Point {
                 }
             })
     }
+
+    /// This point rotated a quarter turn clockwise about the origin, with y
+    /// increasing upwards. With y increasing downwards, as it does for grid
+    /// rows, the turn is counterclockwise instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point;
+    /// let p = Point::new(3, 1);
+    ///
+    /// assert_eq!(p.rotate90(), Point::new(1, -3));
+    /// assert_eq!(p.rotate90().rotate90().rotate90().rotate90(), p);
+    /// ```
+    pub fn rotate90(&self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// This point rotated a quarter turn counterclockwise about the origin,
+    /// with the same caveat as [Point::rotate90].
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point;
+    /// let p = Point::new(3, 1);
+    ///
+    /// assert_eq!(p.rotate_ccw(), Point::new(-1, 3));
+    /// assert_eq!(p.rotate_ccw().rotate90(), p);
+    /// ```
+    pub fn rotate_ccw(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// This point rotated a quarter turn clockwise about `center`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point;
+    /// let center = Point::new(2, 2);
+    ///
+    /// assert_eq!(Point::new(2, 5).rotate90_about(&center), Point::new(5, 2));
+    /// ```
+    pub fn rotate90_about(&self, center: &Self) -> Self {
+        let offset = Self::new(self.x - center.x, self.y - center.y).rotate90();
+        Self::new(center.x + offset.x, center.y + offset.y)
+    }
+
+    /// This point mirrored across the x axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point;
+    /// assert_eq!(Point::new(3, 1).reflect_x(), Point::new(3, -1));
+    /// ```
+    pub fn reflect_x(&self) -> Self {
+        Self::new(self.x, -self.y)
+    }
+
+    /// This point mirrored across the y axis.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point;
+    /// assert_eq!(Point::new(3, 1).reflect_y(), Point::new(-3, 1));
+    /// ```
+    pub fn reflect_y(&self) -> Self {
+        Self::new(-self.x, self.y)
+    }
 }
 
 impl From<(i64, i64)> for Point {
@@ -52,3 +120,422 @@ impl From<(i64, i64)> for Point {
         }
     }
 }
+
+/// A 3D Point of (i64, i64, i64)
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// The offsets to the points sharing a face with a point.
+const ORTHOGONAL: [(i64, i64, i64); 6] = [
+    (0, 0, 1),
+    (0, 1, 0),
+    (1, 0, 0),
+    (0, 0, -1),
+    (0, -1, 0),
+    (-1, 0, 0),
+];
+
+impl Point3 {
+    /// Make a new point.
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The manhattan_distance between this point and another point.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point3;
+    /// let p1 = Point3::new(-5, 2, 0);
+    /// let p2 = Point3::new(6, 3, -4);
+    ///
+    /// assert_eq!(p1.manhattan_distance(&p2), 16);
+    /// assert_eq!(p2.manhattan_distance(&p1), 16);
+    /// ```
+    pub fn manhattan_distance(&self, other: &Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The 6 points sharing a face with this one.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point3;
+    /// let p = Point3::new(1, 1, 1);
+    ///
+    /// assert_eq!(p.orthogonal_neighbors().count(), 6);
+    /// assert!(p.orthogonal_neighbors().all(|n| n.manhattan_distance(&p) == 1));
+    /// ```
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Point3> + '_ {
+        ORTHOGONAL
+            .iter()
+            .map(move |(dx, dy, dz)| Point3::new(self.x + dx, self.y + dy, self.z + dz))
+    }
+
+    /// All 26 points surrounding this one, including diagonally.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::Point3;
+    /// let p = Point3::from((0, 0, 0));
+    ///
+    /// assert_eq!(p.neighbors().count(), 26);
+    /// assert!(p.neighbors().any(|n| n == Point3::new(-1, 1, -1)));
+    /// assert!(p.neighbors().all(|n| n != p));
+    /// ```
+    pub fn neighbors(&self) -> impl Iterator<Item = Point3> + '_ {
+        (-1..=1)
+            .cartesian_product(-1..=1)
+            .cartesian_product(-1..=1)
+            .filter_map(move |((x, y), z)| {
+                if x == 0 && y == 0 && z == 0 {
+                    None
+                } else {
+                    Some((self.x + x, self.y + y, self.z + z).into())
+                }
+            })
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from(value: (i64, i64, i64)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+            z: value.2,
+        }
+    }
+}
+
+/// An infinite line through the integer plane, as `a * x + b * y = c`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Line {
+    pub a: i64,
+    pub b: i64,
+    pub c: i64,
+}
+
+impl Line {
+    /// The line passing through `p` and `q`, which should be distinct.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Line, Point};
+    /// let line = Line::through(&Point::new(0, 1), &Point::new(2, 3));
+    ///
+    /// assert!(line.contains(&Point::new(-4, -3)));
+    /// assert!(!line.contains(&Point::new(1, 1)));
+    /// ```
+    pub fn through(p: &Point, q: &Point) -> Self {
+        // keeping the coefficients as small as possible keeps the products
+        // in `intersection` small too
+        let divisor = gcd(q.y - p.y, p.x - q.x).max(1);
+        let a = (q.y - p.y) / divisor;
+        let b = (p.x - q.x) / divisor;
+        Self {
+            a,
+            b,
+            c: a * p.x + b * p.y,
+        }
+    }
+
+    /// Whether `point` lies on this line.
+    pub fn contains(&self, point: &Point) -> bool {
+        self.a * point.x + self.b * point.y == self.c
+    }
+
+    /// The point at which this line crosses `other`, if it does so at integer
+    /// coordinates. Parallel (and identical) lines have no intersection.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Line, Point};
+    /// let rising = Line::through(&Point::new(0, 0), &Point::new(1, 1));
+    /// let falling = Line::through(&Point::new(0, 4), &Point::new(1, 3));
+    /// let off_grid = Line::through(&Point::new(0, 3), &Point::new(1, 2));
+    ///
+    /// assert_eq!(rising.intersection(&falling), Some(Point::new(2, 2)));
+    /// // these cross at (1.5, 1.5)
+    /// assert_eq!(rising.intersection(&off_grid), None);
+    /// assert_eq!(rising.intersection(&rising), None);
+    ///
+    /// // the diagonal edges of two of 2022 day 15's sensors
+    /// let edge = Line::through(
+    ///     &Point::new(2_302_110, 2_237_242),
+    ///     &Point::new(3_316_042, 3_251_174),
+    /// );
+    /// let other = Line::through(
+    ///     &Point::new(1_000_000, 4_000_000),
+    ///     &Point::new(4_000_000, 1_000_000),
+    /// );
+    /// assert_eq!(edge.intersection(&other), Some(Point::new(2_532_434, 2_467_566)));
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Point> {
+        // lines that aren't through small points can still have coefficients
+        // big enough for these products to overflow an i64
+        let (a1, b1, c1) = (self.a as i128, self.b as i128, self.c as i128);
+        let (a2, b2, c2) = (other.a as i128, other.b as i128, other.c as i128);
+        let det = a1 * b2 - a2 * b1;
+        if det == 0 {
+            return None;
+        }
+
+        let x = c1 * b2 - c2 * b1;
+        let y = a1 * c2 - a2 * c1;
+        if x % det == 0 && y % det == 0 {
+            Some(Point::new((x / det) as i64, (y / det) as i64))
+        } else {
+            None
+        }
+    }
+}
+
+/// The segment of a line between two points, inclusive of both.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    /// Make a new segment.
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+
+    /// The four edges of the diamond of points exactly `radius` (manhattan
+    /// distance) away from `center`, going counterclockwise from the right.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Point, Segment};
+    /// let center = Point::new(3, -2);
+    /// let border = Segment::diamond_border(&center, 4);
+    ///
+    /// assert_eq!(border[0].start, Point::new(7, -2));
+    /// assert!(border.iter().any(|s| s.contains(&Point::new(1, 0))));
+    /// assert!(border.iter().all(|s| s.manhattan_len() == 8));
+    /// ```
+    pub fn diamond_border(center: &Point, radius: i64) -> [Segment; 4] {
+        let right = Point::new(center.x + radius, center.y);
+        let top = Point::new(center.x, center.y + radius);
+        let left = Point::new(center.x - radius, center.y);
+        let bottom = Point::new(center.x, center.y - radius);
+
+        [
+            Self::new(right, top),
+            Self::new(top, left),
+            Self::new(left, bottom),
+            Self::new(bottom, right),
+        ]
+    }
+
+    /// The manhattan distance from one end of this segment to the other.
+    pub fn manhattan_len(&self) -> i64 {
+        self.start.manhattan_distance(&self.end)
+    }
+
+    /// The line this segment lies on.
+    pub fn line(&self) -> Line {
+        Line::through(&self.start, &self.end)
+    }
+
+    /// Whether `point` lies on this segment.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Point, Segment};
+    /// let s = Segment::new(Point::new(0, 0), Point::new(4, 2));
+    ///
+    /// assert!(s.contains(&Point::new(2, 1)));
+    /// assert!(s.contains(&Point::new(4, 2)));
+    /// assert!(!s.contains(&Point::new(6, 3)));
+    /// ```
+    pub fn contains(&self, point: &Point) -> bool {
+        self.line().contains(point)
+            && self.start.x.min(self.end.x) <= point.x
+            && point.x <= self.start.x.max(self.end.x)
+            && self.start.y.min(self.end.y) <= point.y
+            && point.y <= self.start.y.max(self.end.y)
+    }
+
+    /// The point at which this segment crosses `other`, if it does so at
+    /// integer coordinates. Overlapping parallel segments share more than one
+    /// point, so they have no intersection either.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Point, Segment};
+    /// let s = Segment::new(Point::new(0, 0), Point::new(4, 4));
+    ///
+    /// assert_eq!(
+    ///     s.intersection(&Segment::new(Point::new(0, 4), Point::new(4, 0))),
+    ///     Some(Point::new(2, 2))
+    /// );
+    /// // the lines meet at (5, 5), but this one stops short of it
+    /// assert_eq!(
+    ///     s.intersection(&Segment::new(Point::new(5, 5), Point::new(6, 4))),
+    ///     None
+    /// );
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Point> {
+        self.line()
+            .intersection(&other.line())
+            .filter(|p| self.contains(p) && other.contains(p))
+    }
+}
+
+/// The axis-aligned rectangle between two corners, inclusive of both.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// Make new bounds from the corners with the smallest and largest
+    /// coordinates.
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// The bounds of just `point`.
+    pub fn around(point: &Point) -> Self {
+        Self::new(*point, *point)
+    }
+
+    /// The smallest bounds containing every one of `points`, if there are
+    /// any.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Bounds, Point};
+    /// let points = [Point::new(3, -1), Point::new(0, 2), Point::new(1, 1)];
+    ///
+    /// assert_eq!(
+    ///     Bounds::from_points(points),
+    ///     Some(Bounds::new(Point::new(0, -1), Point::new(3, 2)))
+    /// );
+    /// assert_eq!(Bounds::from_points([]), None);
+    /// ```
+    pub fn from_points<I: IntoIterator<Item = Point>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::around(&first), |mut bounds, point| {
+            bounds.include(&point);
+            bounds
+        }))
+    }
+
+    /// Grow these bounds to include `point`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Bounds, Point};
+    /// let mut bounds = Bounds::around(&Point::new(2, 3));
+    /// bounds.include(&Point::new(-1, 5));
+    ///
+    /// assert_eq!(bounds, Bounds::new(Point::new(-1, 3), Point::new(2, 5)));
+    /// assert_eq!((bounds.width(), bounds.height(), bounds.area()), (4, 3, 12));
+    /// ```
+    pub fn include(&mut self, point: &Point) {
+        self.min.x = self.min.x.min(point.x);
+        self.min.y = self.min.y.min(point.y);
+        self.max.x = self.max.x.max(point.x);
+        self.max.y = self.max.y.max(point.y);
+    }
+
+    /// Whether `point` lies within these bounds, including on their edge.
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The smallest bounds containing both these and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Bounds, Point};
+    /// let a = Bounds::new(Point::new(0, 0), Point::new(2, 2));
+    /// let b = Bounds::new(Point::new(1, -3), Point::new(4, 1));
+    ///
+    /// assert_eq!(a.union(&b), Bounds::new(Point::new(0, -3), Point::new(4, 2)));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// The bounds shared by these and `other`, if they overlap at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Bounds, Point};
+    /// let a = Bounds::new(Point::new(0, 0), Point::new(2, 2));
+    /// let b = Bounds::new(Point::new(1, -3), Point::new(4, 1));
+    ///
+    /// assert_eq!(
+    ///     a.intersection(&b),
+    ///     Some(Bounds::new(Point::new(1, 0), Point::new(2, 1)))
+    /// );
+    /// assert_eq!(a.intersection(&Bounds::around(&Point::new(3, 3))), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Self::new(min, max))
+    }
+
+    /// These bounds grown by `n` on every side, or shrunk if `n` is negative.
+    pub fn expand(&self, n: i64) -> Self {
+        Self::new(
+            Point::new(self.min.x - n, self.min.y - n),
+            Point::new(self.max.x + n, self.max.y + n),
+        )
+    }
+
+    /// Every point within these bounds, a row of increasing x at a time from
+    /// the smallest y.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::geometry::{Bounds, Point};
+    /// let bounds = Bounds::around(&Point::new(5, 5)).expand(1);
+    /// let points = bounds.points().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(points.len() as i64, bounds.area());
+    /// assert_eq!(points[0], Point::new(4, 4));
+    /// assert_eq!(points[1], Point::new(5, 4));
+    /// assert!(points.iter().all(|p| bounds.contains(p)));
+    /// ```
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+
+    /// Whether `point` lies on the edge of these bounds.
+    pub fn on_edge(&self, point: &Point) -> bool {
+        point.x == self.min.x
+            || point.x == self.max.x
+            || point.y == self.min.y
+            || point.y == self.max.y
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// The number of points within these bounds.
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+}
@@ -0,0 +1,548 @@
+//! Grids for the puzzles that aren't a fixed rectangle of tiles read from the
+//! input, and transformations of the ones that are (as rows of tiles, like
+//! those from [char_grid](crate::parsing::char_grid)).
+use std::{cell::Cell, fmt::Display};
+
+use rustc_hash::FxHashMap;
+
+use crate::geometry::{Bounds, Point};
+
+/// A grid of the few points that have a value, for when the coordinates are
+/// too large or unpredictable to allocate every tile (like elves spreading
+/// out in every direction). The [Bounds] of the points are kept up to date as
+/// values are inserted and removed.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{geometry::Point, grid::SparseGrid};
+/// let mut grid = SparseGrid::default();
+/// grid.insert(Point::new(-2, 0), '#');
+/// grid.insert(Point::new(1, 1), '#');
+///
+/// let bounds = grid.bounds().unwrap();
+/// assert_eq!((bounds.width(), bounds.height()), (4, 2));
+/// assert_eq!(
+///     grid.to_dense('.'),
+///     vec![vec!['#', '.', '.', '.'], vec!['.', '.', '.', '#']]
+/// );
+///
+/// grid.remove(&Point::new(-2, 0));
+/// assert_eq!(grid.bounds().unwrap().area(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    points: FxHashMap<Point, T>,
+    bounds: Cell<Option<Bounds>>,
+    /// Whether a point on the edge of the bounds has been removed since they
+    /// were last found, so they might be smaller.
+    stale: Cell<bool>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            points: FxHashMap::default(),
+            bounds: Cell::new(None),
+            stale: Cell::new(false),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            points: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
+            ..Default::default()
+        }
+    }
+
+    pub fn get(&self, point: &Point) -> Option<&T> {
+        self.points.get(point)
+    }
+
+    pub fn get_mut(&mut self, point: &Point) -> Option<&mut T> {
+        self.points.get_mut(point)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.points.contains_key(point)
+    }
+
+    /// Set the value at `point`, returning the one it replaced, if any.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        if !self.stale.get() {
+            let mut bounds = self.bounds.get().unwrap_or_else(|| Bounds::around(&point));
+            bounds.include(&point);
+            self.bounds.set(Some(bounds));
+        }
+        self.points.insert(point, value)
+    }
+
+    /// Remove the value at `point`, if there is one.
+    pub fn remove(&mut self, point: &Point) -> Option<T> {
+        let removed = self.points.remove(point)?;
+        if matches!(self.bounds.get(), Some(bounds) if bounds.on_edge(point)) {
+            self.stale.set(true);
+        }
+        Some(removed)
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.points.iter()
+    }
+
+    /// The points with a value, in no particular order.
+    pub fn points(&self) -> impl Iterator<Item = &Point> {
+        self.points.keys()
+    }
+
+    /// The smallest bounds containing every point with a value, if there are
+    /// any. These are only searched for again if a point on their edge was
+    /// removed.
+    pub fn bounds(&self) -> Option<Bounds> {
+        if self.stale.replace(false) {
+            self.bounds
+                .set(Bounds::from_points(self.points.keys().copied()));
+        }
+
+        self.bounds.get()
+    }
+
+    /// The rows of the [SparseGrid::bounds], from the smallest y to the
+    /// largest, with `fill` for the points without a value.
+    pub fn to_dense(&self, fill: T) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        let bounds = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+
+        let mut rows = vec![vec![fill; bounds.width() as usize]; bounds.height() as usize];
+        for (point, value) in self.points.iter() {
+            rows[(point.y - bounds.min.y) as usize][(point.x - bounds.min.x) as usize] =
+                value.clone();
+        }
+
+        rows
+    }
+}
+
+/// A fixed size grid of on/off tiles, packed into a [u64] per 64 columns of
+/// each row, for occupancy maps where whole rows can be shifted and compared
+/// at once. Column 0 is the lowest bit of a row's first word.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::grid::BitGrid;
+/// let mut grid = BitGrid::new(2, 100);
+/// grid.set(0, 0);
+/// grid.set(0, 63);
+/// grid.set(1, 99);
+/// assert_eq!(grid.count_ones(), 3);
+///
+/// // everything in the first row moves a column east, across the word
+/// grid.shift_row_right(0, 1);
+/// assert!(grid.test(0, 1) && grid.test(0, 64));
+/// assert!(!grid.test(0, 0));
+///
+/// // and the last column falls off the edge
+/// grid.shift_row_right(1, 1);
+/// assert_eq!(grid.row_count_ones(1), 0);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every tile off.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        // enough whole words for every column
+        let words_per_row = (cols + 63) >> 6;
+        Self {
+            rows,
+            cols,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Whether the tile at `(row, col)` is on, or `None` if it's outside the
+    /// grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<bool> {
+        (row < self.rows && col < self.cols).then(|| self.test(row, col))
+    }
+
+    /// Whether the tile at `(row, col)` is on. Anything outside the grid is
+    /// off.
+    pub fn test(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols && self.row(row)[col / 64] & (1 << (col % 64)) != 0
+    }
+
+    /// Turn on the tile at `(row, col)`, which must be in the grid.
+    pub fn set(&mut self, row: usize, col: usize) {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.row_mut(row)[col / 64] |= 1 << (col % 64);
+    }
+
+    /// Turn off the tile at `(row, col)`, which must be in the grid.
+    pub fn unset(&mut self, row: usize, col: usize) {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.row_mut(row)[col / 64] &= !(1 << (col % 64));
+    }
+
+    /// The words of a row, for combining whole rows at once.
+    pub fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// The words of a row. Anything set past the last column is ignored by
+    /// [BitGrid::count_ones] and dropped by the shifts.
+    pub fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        &mut self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// The number of tiles that are on.
+    pub fn count_ones(&self) -> usize {
+        (0..self.rows).map(|row| self.row_count_ones(row)).sum()
+    }
+
+    /// The number of tiles in `row` that are on.
+    pub fn row_count_ones(&self, row: usize) -> usize {
+        let mask = self.last_word_mask();
+        let words = self.row(row);
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let word = if i + 1 == words.len() {
+                    word & mask
+                } else {
+                    *word
+                };
+                word.count_ones() as usize
+            })
+            .sum()
+    }
+
+    /// Move every tile in `row` `n` columns towards column 0, dropping the
+    /// ones that go past it.
+    pub fn shift_row_left(&mut self, row: usize, n: usize) {
+        let mask = self.last_word_mask();
+        let (skip, bits) = (n / 64, n % 64);
+        let words = self.row_mut(row);
+        if let Some(last) = words.last_mut() {
+            *last &= mask;
+        }
+
+        for i in 0..words.len() {
+            let lo = words.get(i + skip).map_or(0, |w| w >> bits);
+            let hi = match words.get(i + skip + 1) {
+                Some(w) if bits > 0 => w << (64 - bits),
+                _ => 0,
+            };
+            words[i] = lo | hi;
+        }
+    }
+
+    /// Move every tile in `row` `n` columns away from column 0, dropping the
+    /// ones that go past the last column.
+    pub fn shift_row_right(&mut self, row: usize, n: usize) {
+        let mask = self.last_word_mask();
+        let (skip, bits) = (n / 64, n % 64);
+        let words = self.row_mut(row);
+
+        for i in (0..words.len()).rev() {
+            let hi = match i.checked_sub(skip) {
+                Some(src) => words[src] << bits,
+                None => 0,
+            };
+            let lo = match i.checked_sub(skip + 1) {
+                Some(src) if bits > 0 => words[src] >> (64 - bits),
+                _ => 0,
+            };
+            words[i] = hi | lo;
+        }
+
+        if let Some(last) = words.last_mut() {
+            *last &= mask;
+        }
+    }
+
+    /// The bits of the last word in each row that are within the grid.
+    fn last_word_mask(&self) -> u64 {
+        match self.cols % 64 {
+            0 => u64::MAX,
+            used => (1 << used) - 1,
+        }
+    }
+}
+
+/// Draws rows of tiles a line at a time, with `to_char` picking the character
+/// for each tile, for implementing [Display] or rendering frames for
+/// [Visualize](crate::Visualize) without collecting a [String] per row.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::Render;
+/// let grid = vec![vec![true, false], vec![false, true]];
+/// let render = Render::new(&grid, |wall| if *wall { '#' } else { '.' });
+///
+/// assert_eq!(render.to_string(), "#.\n.#\n");
+/// ```
+pub struct Render<'a, T, F> {
+    rows: &'a [Vec<T>],
+    to_char: F,
+}
+
+impl<'a, T, F> Render<'a, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    pub fn new(rows: &'a [Vec<T>], to_char: F) -> Self {
+        Self { rows, to_char }
+    }
+}
+
+impl<'a, T, F> Display for Render<'a, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows {
+            for tile in row {
+                write!(f, "{}", (self.to_char)(tile))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `rows` drawn as a [String], as with [Render].
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::render;
+/// assert_eq!(render(&[vec![1, 2, 3]], |n| char::from(b'0' + n)), "123\n");
+/// ```
+pub fn render<T>(rows: &[Vec<T>], to_char: impl Fn(&T) -> char) -> String {
+    Render::new(rows, to_char).to_string()
+}
+
+/// The `(row, col)` index moved by `(dr, dc)`, or `None` if that would take
+/// it off the top or left of the grid. Checking the far edges is left to
+/// whatever is indexed.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::{direction::Direction, grid::offset};
+/// assert_eq!(offset((2, 0), (-1, 3)), Some((1, 3)));
+/// assert_eq!(offset((0, 4), Direction::North.grid_delta()), None);
+/// assert_eq!(offset((0, 4), Direction::South.grid_delta()), Some((1, 4)));
+/// ```
+pub fn offset((row, col): (usize, usize), (dr, dc): (i64, i64)) -> Option<(usize, usize)> {
+    Some((
+        row.checked_add_signed(dr.try_into().ok()?)?,
+        col.checked_add_signed(dc.try_into().ok()?)?,
+    ))
+}
+
+/// The columns of `rows` as rows, so the tile at `(row, col)` ends up at
+/// `(col, row)`. Every row should be as long as the first.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::transpose;
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+///
+/// assert_eq!(transpose(&grid), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+/// ```
+pub fn transpose<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    let width = rows.first().map(|row| row.len()).unwrap_or_default();
+    (0..width)
+        .map(|col| rows.iter().map(|row| row[col].clone()).collect())
+        .collect()
+}
+
+/// `rows` turned a quarter turn clockwise.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::rotate_cw;
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+///
+/// assert_eq!(rotate_cw(&grid), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+/// ```
+pub fn rotate_cw<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    flip_h(&transpose(rows))
+}
+
+/// `rows` turned a quarter turn counterclockwise.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::{rotate_ccw, rotate_cw};
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+///
+/// assert_eq!(rotate_ccw(&grid), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+/// assert_eq!(rotate_ccw(&rotate_cw(&grid)), grid);
+/// ```
+pub fn rotate_ccw<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    flip_v(&transpose(rows))
+}
+
+/// `rows` mirrored left to right.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::flip_h;
+/// assert_eq!(flip_h(&[vec![1, 2], vec![3, 4]]), vec![vec![2, 1], vec![4, 3]]);
+/// ```
+pub fn flip_h<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    rows.iter()
+        .map(|row| row.iter().rev().cloned().collect())
+        .collect()
+}
+
+/// `rows` mirrored top to bottom.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::grid::flip_v;
+/// assert_eq!(flip_v(&[vec![1, 2], vec![3, 4]]), vec![vec![3, 4], vec![1, 2]]);
+/// ```
+pub fn flip_v<T: Clone>(rows: &[Vec<T>]) -> Vec<Vec<T>> {
+    rows.iter().rev().cloned().collect()
+}
+
+/// Grids are equal if they have the same values at the same points.
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.points == other.points
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (point, value) in iter {
+            self.insert(point, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds() {
+        let mut grid: SparseGrid<()> = (0..5).map(|i| (Point::new(i, i * 2), ())).collect();
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(0, 0), Point::new(4, 8)))
+        );
+
+        // inside, so the bounds don't change
+        grid.remove(&Point::new(2, 4));
+        assert_eq!(grid.bounds().unwrap().max, Point::new(4, 8));
+
+        grid.remove(&Point::new(4, 8));
+        grid.insert(Point::new(-1, 1), ());
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds::new(Point::new(-1, 0), Point::new(3, 6)))
+        );
+
+        for point in grid.points().copied().collect::<Vec<_>>() {
+            grid.remove(&point);
+        }
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.to_dense(()).is_empty());
+    }
+
+    #[test]
+    fn bit_shifts() {
+        let mut grid = BitGrid::new(1, 130);
+        for col in [0, 1, 63, 64, 127, 129] {
+            grid.set(0, col);
+        }
+
+        grid.shift_row_left(0, 65);
+        assert_eq!(
+            (0..130)
+                .filter(|col| grid.test(0, *col))
+                .collect::<Vec<_>>(),
+            vec![62, 64]
+        );
+
+        grid.shift_row_right(0, 66);
+        assert_eq!(
+            (0..130)
+                .filter(|col| grid.test(0, *col))
+                .collect::<Vec<_>>(),
+            vec![128]
+        );
+
+        grid.shift_row_right(0, 2);
+        assert_eq!(grid.count_ones(), 0);
+
+        // bits set past the last column by hand don't count
+        grid.row_mut(0)[2] = u64::MAX;
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.get(0, 130), None);
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = vec![vec!['a', 'b'], vec!['c', 'd'], vec!['e', 'f']];
+        let mut turned = grid.clone();
+        for _ in 0..4 {
+            turned = rotate_cw(&turned);
+        }
+        assert_eq!(turned, grid);
+
+        assert_eq!(rotate_cw(&rotate_cw(&grid)), flip_h(&flip_v(&grid)));
+        assert_eq!(transpose(&transpose(&grid)), grid);
+        assert!(transpose::<u8>(&[]).is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! Hexagonal grids, in axial coordinates, for the puzzles that tile the
+//! plane with hexagons instead of squares.
+use rustc_hash::FxHashMap;
+
+/// A hexagon, in axial coordinates `(q, r)`. The third cube coordinate is
+/// implied by `q + r + s = 0`, see [Hex::s].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    /// The steps to each neighbor, counterclockwise from east for hexagons
+    /// with a point at the top (or from south east for flat tops).
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// The implied third coordinate.
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// The hexagon `n` steps away in the given direction, one of
+    /// [Hex::DIRECTIONS].
+    pub fn step(&self, direction: &Hex, n: i64) -> Self {
+        Self::new(self.q + direction.q * n, self.r + direction.r * n)
+    }
+
+    /// The six hexagons sharing an edge with this one, in the order of
+    /// [Hex::DIRECTIONS].
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::hex::Hex;
+    /// let origin = Hex::default();
+    ///
+    /// assert_eq!(origin.neighbors().count(), 6);
+    /// assert!(origin.neighbors().all(|n| n.distance(&origin) == 1));
+    /// ```
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> + '_ {
+        Self::DIRECTIONS.iter().map(move |d| self.step(d, 1))
+    }
+
+    /// The fewest steps between this hexagon and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::hex::Hex;
+    /// let a = Hex::new(1, -3);
+    /// let b = Hex::new(-2, 1);
+    ///
+    /// assert_eq!(a.distance(&b), 4);
+    /// assert_eq!(b.distance(&a), 4);
+    /// ```
+    pub fn distance(&self, other: &Self) -> i64 {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s() - other.s()).abs()) / 2
+    }
+}
+
+impl From<(i64, i64)> for Hex {
+    fn from(value: (i64, i64)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+/// A grid of the hexagons that have a value.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::hex::{Hex, HexGrid};
+/// let mut grid = HexGrid::default();
+/// grid.insert(Hex::new(0, 0), 'a');
+/// grid.insert(Hex::new(1, -1), 'b');
+/// grid.insert(Hex::new(3, 0), 'c');
+///
+/// let around = grid.neighbors(&Hex::new(0, 0)).map(|(_, v)| *v).collect::<Vec<_>>();
+/// assert_eq!(around, vec!['b']);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HexGrid<T> {
+    hexes: FxHashMap<Hex, T>,
+}
+
+impl<T> Default for HexGrid<T> {
+    fn default() -> Self {
+        Self {
+            hexes: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T> HexGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, hex: &Hex) -> Option<&T> {
+        self.hexes.get(hex)
+    }
+
+    pub fn get_mut(&mut self, hex: &Hex) -> Option<&mut T> {
+        self.hexes.get_mut(hex)
+    }
+
+    pub fn contains(&self, hex: &Hex) -> bool {
+        self.hexes.contains_key(hex)
+    }
+
+    /// Set the value at `hex`, returning the one it replaced, if any.
+    pub fn insert(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.hexes.insert(hex, value)
+    }
+
+    pub fn remove(&mut self, hex: &Hex) -> Option<T> {
+        self.hexes.remove(hex)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hexes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hexes.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Hex, &T)> {
+        self.hexes.iter()
+    }
+
+    /// The neighbors of `hex` that have a value, in the order of
+    /// [Hex::DIRECTIONS].
+    pub fn neighbors<'a>(&'a self, hex: &'a Hex) -> impl Iterator<Item = (Hex, &'a T)> + 'a {
+        hex.neighbors()
+            .filter_map(move |n| self.hexes.get(&n).map(|value| (n, value)))
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        Self {
+            hexes: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walking_around() {
+        let start = Hex::new(2, -1);
+        let mut hex = start.step(&Hex::DIRECTIONS[4], 3);
+        assert_eq!(hex.distance(&start), 3);
+
+        // walk once around the ring of radius 3
+        let mut visited = Vec::new();
+        for direction in Hex::DIRECTIONS.iter() {
+            for _ in 0..3 {
+                visited.push(hex);
+                hex = hex.step(direction, 1);
+            }
+        }
+        assert_eq!(hex, start.step(&Hex::DIRECTIONS[4], 3));
+        assert_eq!(visited.len(), 18);
+        assert!(visited.iter().all(|h| h.distance(&start) == 3));
+
+        let grid: HexGrid<_> = visited.iter().map(|h| (*h, ())).collect();
+        assert_eq!(grid.len(), 18);
+        assert_eq!(grid.neighbors(&start).count(), 0);
+    }
+}
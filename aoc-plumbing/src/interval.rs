@@ -0,0 +1,307 @@
+use num::PrimInt;
+
+/// An inclusive interval `[start, end]` of integers.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    /// Make a new interval covering `start` through `end`. Reversed bounds
+    /// aren't swapped, so anything parsing them should reject them first.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(3, 7);
+    ///
+    /// assert_eq!(i.start, 3);
+    /// assert_eq!(i.end, 7);
+    /// ```
+    pub fn new(start: T, end: T) -> Self {
+        debug_assert!(start <= end, "interval bounds are reversed");
+        Self { start, end }
+    }
+
+    /// The number of values in this interval.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// assert_eq!(Interval::new(3, 7).len(), 5);
+    /// assert_eq!(Interval::new(-2, -2).len(), 1);
+    /// ```
+    pub fn len(&self) -> T {
+        self.end - self.start + T::one()
+    }
+
+    /// Whether `value` lies within this interval.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(3, 7);
+    ///
+    /// assert!(i.contains(&3));
+    /// assert!(i.contains(&7));
+    /// assert!(!i.contains(&8));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value <= self.end
+    }
+
+    /// Whether `other` lies entirely within this interval.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(2, 8);
+    ///
+    /// assert!(i.contains_interval(&Interval::new(3, 7)));
+    /// assert!(i.contains_interval(&i));
+    /// assert!(!i.contains_interval(&Interval::new(3, 9)));
+    /// ```
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether this interval and `other` share at least one value.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(5, 7);
+    ///
+    /// assert!(i.overlaps(&Interval::new(7, 9)));
+    /// assert!(i.overlaps(&Interval::new(1, 10)));
+    /// assert!(!i.overlaps(&Interval::new(8, 9)));
+    /// ```
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The values common to this interval and `other`, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(2, 6);
+    ///
+    /// assert_eq!(i.intersection(&Interval::new(4, 8)), Some(Interval::new(4, 6)));
+    /// assert_eq!(i.intersection(&Interval::new(7, 8)), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if self.overlaps(other) {
+            Some(Self {
+                start: self.start.max(other.start),
+                end: self.end.min(other.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The single interval covering this interval and `other`, if they
+    /// overlap.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::Interval;
+    /// let i = Interval::new(2, 6);
+    ///
+    /// assert_eq!(i.union(&Interval::new(4, 8)), Some(Interval::new(2, 8)));
+    /// assert_eq!(i.union(&Interval::new(7, 8)), None);
+    /// ```
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.overlaps(other) {
+            Some(Self {
+                start: self.start.min(other.start),
+                end: self.end.max(other.end),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: PrimInt> From<(T, T)> for Interval<T> {
+    fn from(value: (T, T)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+/// A set of integers, kept as sorted, disjoint intervals. Intervals that
+/// overlap or are adjacent are merged as they're inserted.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::interval::{Interval, IntervalSet};
+/// let mut set: IntervalSet<i64> = [(-2, 2), (12, 12), (2, 14), (16, 24)]
+///     .into_iter()
+///     .map(Interval::from)
+///     .collect();
+///
+/// assert_eq!(set.intervals(), &[Interval::new(-2, 14), Interval::new(16, 24)]);
+/// assert_eq!(set.len(), 26);
+///
+/// set.remove(&Interval::new(0, 20));
+/// assert_eq!(set.intervals(), &[Interval::new(-2, -1), Interval::new(21, 24)]);
+/// assert!(set.contains(&22));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// The disjoint intervals making up this set, in order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /// The number of values in this set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |acc, interval| acc + interval.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Whether `value` is in this set.
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < *value);
+        self.intervals
+            .get(idx)
+            .map(|i| i.contains(value))
+            .unwrap_or_default()
+    }
+
+    /// Add every value in `interval` to this set.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // the range of intervals that overlap or touch the new one
+        let lo = self
+            .intervals
+            .partition_point(|i| i.end.saturating_add(T::one()) < interval.start);
+        let hi = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end.saturating_add(T::one()));
+
+        let mut merged = interval;
+        if lo < hi {
+            merged.start = merged.start.min(self.intervals[lo].start);
+            merged.end = merged.end.max(self.intervals[hi - 1].end);
+        }
+
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// Remove every value in `interval` from this set.
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        let lo = self.intervals.partition_point(|i| i.end < interval.start);
+        let hi = self.intervals.partition_point(|i| i.start <= interval.end);
+        if lo >= hi {
+            return;
+        }
+
+        // whatever sticks out either side of the removed interval survives
+        let first = self.intervals[lo];
+        let last = self.intervals[hi - 1];
+        let mut remaining = Vec::with_capacity(2);
+        if first.start < interval.start {
+            remaining.push(Interval::new(first.start, interval.start - T::one()));
+        }
+        if last.end > interval.end {
+            remaining.push(Interval::new(interval.end + T::one(), last.end));
+        }
+
+        self.intervals.splice(lo..hi, remaining);
+    }
+
+    /// The values within `bounds` that are not in this set.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::interval::{Interval, IntervalSet};
+    /// let set: IntervalSet<u32> = [(3, 5), (9, 20)].into_iter().map(Interval::from).collect();
+    /// let gaps = set.gaps_within(&Interval::new(0, 10));
+    ///
+    /// assert_eq!(gaps.intervals(), &[Interval::new(0, 2), Interval::new(6, 8)]);
+    /// ```
+    pub fn gaps_within(&self, bounds: &Interval<T>) -> Self {
+        let mut gaps = Self::new();
+        gaps.insert(*bounds);
+        for interval in self.intervals.iter() {
+            gaps.remove(interval);
+        }
+        gaps
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: PrimInt> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().copied().map(Interval::from).collect()
+    }
+
+    #[test]
+    fn insert() {
+        let mut s = set(&[(10, 12), (1, 3), (6, 7)]);
+        assert_eq!(s, set(&[(1, 3), (6, 7), (10, 12)]));
+
+        // adjacent intervals are merged
+        s.insert(Interval::new(4, 5));
+        assert_eq!(s, set(&[(1, 7), (10, 12)]));
+
+        s.insert(Interval::new(0, 20));
+        assert_eq!(s.intervals(), &[Interval::new(0, 20)]);
+
+        let mut s = set(&[(i32::MAX - 1, i32::MAX)]);
+        s.insert(Interval::new(i32::MIN, i32::MIN + 1));
+        assert_eq!(s.intervals().len(), 2);
+    }
+
+    #[test]
+    fn remove() {
+        let mut s = set(&[(1, 7), (10, 12)]);
+        s.remove(&Interval::new(8, 9));
+        assert_eq!(s, set(&[(1, 7), (10, 12)]));
+
+        s.remove(&Interval::new(3, 4));
+        assert_eq!(s, set(&[(1, 2), (5, 7), (10, 12)]));
+
+        s.remove(&Interval::new(2, 10));
+        assert_eq!(s, set(&[(1, 1), (11, 12)]));
+
+        s.remove(&Interval::new(-5, 20));
+        assert!(s.is_empty());
+        assert_eq!(s.len(), 0);
+    }
+}
@@ -1,5 +1,37 @@
+#[cfg(feature = "async")]
+pub mod asynchronous;
 pub mod bits;
+pub mod cancellation;
+pub mod circular;
+pub mod cycle;
+pub mod direction;
+pub mod error;
+pub mod expected;
+pub mod formats;
 pub mod geometry;
+pub mod grid;
+pub mod hex;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod metadata;
+pub mod parsing;
+pub mod pathing;
 pub mod problem;
+pub mod progress;
+pub mod radix;
+pub mod recursion;
+pub mod registry;
+pub mod timing;
 
-pub use problem::{Problem, Solution};
+pub use aoc_macros::aoc_problem;
+pub use error::AocError;
+pub use expected::{Check, Expected, PartDiff, SolutionDiff, Validation};
+// used by `register!`
+#[doc(hidden)]
+pub use inventory;
+pub use problem::{
+    Alternatives, BorrowedProblem, Example, Implementation, NoAnswer, Phases, Problem, Solution,
+    Visualize,
+};
+pub use timing::{TimedSolution, Timings};
@@ -0,0 +1,116 @@
+//! Number theory for the puzzles that only finish in time if you work modulo
+//! something.
+use num::{integer::Integer, PrimInt};
+
+/// The greatest common divisor of `a` and `b`, which is never negative.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::math::gcd;
+/// assert_eq!(gcd(12, 18), 6);
+/// assert_eq!(gcd(-4, 6), 2);
+/// assert_eq!(gcd(0, 5), 5);
+/// ```
+pub fn gcd<T: PrimInt + Integer>(a: T, b: T) -> T {
+    a.gcd(&b)
+}
+
+/// The lowest common multiple of `a` and `b`, which is never negative.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::math::lcm;
+/// assert_eq!(lcm(4_usize, 6), 12);
+/// assert_eq!(lcm(7, 0), 0);
+/// ```
+pub fn lcm<T: PrimInt + Integer>(a: T, b: T) -> T {
+    a.lcm(&b)
+}
+
+/// The lowest common multiple of all of `values`, or one if there are none.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::math::lcm_all;
+/// assert_eq!(lcm_all([23_u64, 19, 13, 17, 13]), 96577);
+/// assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+/// ```
+pub fn lcm_all<T, I>(values: I) -> T
+where
+    T: PrimInt + Integer,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().fold(T::one(), |acc, v| acc.lcm(&v))
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, where `g` is the greatest
+/// common divisor of `a` and `b`.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::math::extended_gcd;
+/// let (g, x, y) = extended_gcd(240, 46);
+///
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solve a system of congruences `x = residue (mod modulus)` with the
+/// Chinese remainder theorem, returning the smallest non-negative `x` along
+/// with the modulus it repeats at. The moduli need not be coprime, but there
+/// is no solution if they disagree, or if they're not positive.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// // 6 and 4 share a factor, but 10 satisfies both
+/// assert_eq!(crt(&[(4, 6), (2, 4)]), Some((10, 12)));
+/// // nothing is both even and odd
+/// assert_eq!(crt(&[(0, 2), (1, 4)]), None);
+/// ```
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut acc = (0_i64, 1_i64);
+
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+
+        let (value, m) = acc;
+        let residue = residue.rem_euclid(modulus);
+        let (g, p, _) = extended_gcd(m, modulus);
+        if (residue - value) % g != 0 {
+            return None;
+        }
+
+        // value + m * k satisfies both when k = p * (residue - value) / g,
+        // modulo modulus / g
+        let step = modulus / g;
+        let k = (p as i128 * ((residue - value) / g) as i128).rem_euclid(step as i128);
+        let combined = m as i128 * step as i128;
+        let value = (value as i128 + m as i128 * k).rem_euclid(combined);
+
+        acc = (i64::try_from(value).ok()?, i64::try_from(combined).ok()?);
+    }
+
+    Some(acc)
+}
@@ -0,0 +1,338 @@
+//! Caches for searches that keep revisiting the same states.
+use std::{hash::Hash, marker::PhantomData};
+
+use rustc_hash::FxHashMap;
+
+/// Storage for a value per key, so code like [pathing](crate::pathing) can
+/// work with whichever cache suits its keys.
+pub trait Cache<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+
+    /// Store `value` for `key`, returning whatever was there before.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+}
+
+/// A cache of values by key, for memoizing a function or remembering the
+/// best result found for a state so far.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::memo::Memo;
+/// let mut best = Memo::with_capacity(16);
+///
+/// assert!(best.keep_min(("a", 3), 10));
+/// assert!(!best.keep_min(("a", 3), 12));
+/// assert!(best.keep_min(("a", 3), 7));
+/// assert_eq!(best.get(&("a", 3)), Some(&7));
+///
+/// let mut squares = Memo::new();
+/// assert_eq!(*squares.get_or_insert_with(4, || 4 * 4), 16);
+/// assert_eq!(*squares.get_or_insert_with(4, || unreachable!()), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: FxHashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            values: FxHashMap::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a cache with room for at least `capacity` values before it
+    /// reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    /// Store `value` for `key`, returning whatever was there before.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.values.insert(key, value)
+    }
+
+    /// The value for `key`, computing and storing it with `f` if there isn't
+    /// one yet.
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &V {
+        self.values.entry(key).or_insert_with(f)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.values.iter()
+    }
+}
+
+impl<K: Hash + Eq, V: Ord> Memo<K, V> {
+    /// Store `value` for `key` if it's smaller than what's there, returning
+    /// whether it was.
+    pub fn keep_min(&mut self, key: K, value: V) -> bool {
+        self.keep_if(key, value, |new, old| new < old)
+    }
+
+    /// Store `value` for `key` if it's larger than what's there, returning
+    /// whether it was.
+    pub fn keep_max(&mut self, key: K, value: V) -> bool {
+        self.keep_if(key, value, |new, old| new > old)
+    }
+
+    fn keep_if(&mut self, key: K, value: V, better: impl Fn(&V, &V) -> bool) -> bool {
+        match self.values.get_mut(&key) {
+            Some(old) if !better(&value, old) => false,
+            Some(old) => {
+                *old = value;
+                true
+            }
+            None => {
+                self.values.insert(key, value);
+                true
+            }
+        }
+    }
+}
+
+/// A [Memo] for keys that are small integers, backed by a fixed-size array
+/// instead of a hash map. Keys outside of that size panic.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::memo::DenseMemo;
+/// // the number of ways to climb n stairs taking 1 or 2 at a time
+/// fn ways(n: usize, memo: &mut DenseMemo<u64>) -> u64 {
+///     if n < 2 {
+///         return 1;
+///     }
+///     if let Some(v) = memo.get(n) {
+///         return *v;
+///     }
+///     let v = ways(n - 1, memo) + ways(n - 2, memo);
+///     memo.insert(n, v);
+///     v
+/// }
+///
+/// let mut memo = DenseMemo::new(91);
+/// assert_eq!(ways(90, &mut memo), 4660046610375530309);
+/// assert!(memo.keep_max(3, 100));
+/// assert!(!memo.keep_max(3, 99));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DenseMemo<V> {
+    values: Vec<Option<V>>,
+}
+
+impl<V> DenseMemo<V> {
+    /// Make a cache for the keys `0..size`.
+    pub fn new(size: usize) -> Self {
+        let mut values = Vec::with_capacity(size);
+        values.resize_with(size, || None);
+        Self { values }
+    }
+
+    pub fn get(&self, key: usize) -> Option<&V> {
+        self.values[key].as_ref()
+    }
+
+    /// Store `value` for `key`, returning whatever was there before.
+    pub fn insert(&mut self, key: usize, value: V) -> Option<V> {
+        self.values[key].replace(value)
+    }
+
+    /// The value for `key`, computing and storing it with `f` if there isn't
+    /// one yet.
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: usize, f: F) -> &V {
+        self.values[key].get_or_insert_with(f)
+    }
+}
+
+impl<V: Ord> DenseMemo<V> {
+    /// Store `value` for `key` if it's smaller than what's there, returning
+    /// whether it was.
+    pub fn keep_min(&mut self, key: usize, value: V) -> bool {
+        self.keep_if(key, value, |new, old| new < old)
+    }
+
+    /// Store `value` for `key` if it's larger than what's there, returning
+    /// whether it was.
+    pub fn keep_max(&mut self, key: usize, value: V) -> bool {
+        self.keep_if(key, value, |new, old| new > old)
+    }
+
+    fn keep_if(&mut self, key: usize, value: V, better: impl Fn(&V, &V) -> bool) -> bool {
+        match &mut self.values[key] {
+            Some(old) if !better(&value, old) => false,
+            slot => {
+                *slot = Some(value);
+                true
+            }
+        }
+    }
+}
+
+/// The sizes of each dimension of a dense array of `N` dimensions, for
+/// numbering keys like grid positions, points in space, or positions at a
+/// time to use with [IndexedMemo]. The last dimension varies fastest.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::memo::Dims;
+/// let dims = Dims::new([2, 3, 4]);
+///
+/// assert_eq!(dims.size(), 24);
+/// assert_eq!(dims.index([0, 0, 1]), 1);
+/// assert_eq!(dims.index([1, 0, 0]), 12);
+/// assert_eq!(dims.index([1, 2, 3]), 23);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Dims<const N: usize> {
+    sizes: [usize; N],
+}
+
+impl<const N: usize> Dims<N> {
+    pub fn new(sizes: [usize; N]) -> Self {
+        Self { sizes }
+    }
+
+    /// The number of distinct indices.
+    pub fn size(&self) -> usize {
+        self.sizes.iter().product()
+    }
+
+    /// The index of `coords`, each of which must be smaller than the size of
+    /// its dimension.
+    pub fn index(&self, coords: [usize; N]) -> usize {
+        coords
+            .iter()
+            .zip(self.sizes.iter())
+            .fold(0, |index, (coord, size)| {
+                assert!(coord < size, "{} out of bounds for {}", coord, size);
+                index * size + coord
+            })
+    }
+}
+
+/// A [DenseMemo] for keys that aren't integers themselves, but can be
+/// numbered by `index` (with [Dims], say). Keys that number outside of the
+/// size of the cache panic.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::memo::{Dims, IndexedMemo};
+/// // positions over a repeating cycle of 4 minutes
+/// let dims = Dims::new([4, 10, 10]);
+/// let mut best = IndexedMemo::new(dims.size(), |((row, col), minute): &((usize, usize), usize)| {
+///     dims.index([minute % 4, *row, *col])
+/// });
+///
+/// assert!(best.keep_min(((2, 3), 1), 1));
+/// // the same place at the same point in the cycle
+/// assert!(!best.keep_min(((2, 3), 5), 5));
+/// assert_eq!(best.get(&((2, 3), 9)), Some(&1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexedMemo<K, V, F> {
+    values: DenseMemo<V>,
+    index: F,
+    key: PhantomData<fn(&K)>,
+}
+
+impl<K, V, F> IndexedMemo<K, V, F>
+where
+    F: Fn(&K) -> usize,
+{
+    /// Make a cache for the keys `index` numbers within `0..size`.
+    pub fn new(size: usize, index: F) -> Self {
+        Self {
+            values: DenseMemo::new(size),
+            index,
+            key: PhantomData,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.values.get((self.index)(key))
+    }
+
+    /// Store `value` for `key`, returning whatever was there before.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.values.insert((self.index)(&key), value)
+    }
+
+    /// The value for `key`, computing and storing it with `f` if there isn't
+    /// one yet.
+    pub fn get_or_insert_with<G: FnOnce() -> V>(&mut self, key: K, f: G) -> &V {
+        self.values.get_or_insert_with((self.index)(&key), f)
+    }
+}
+
+impl<K, V: Ord, F> IndexedMemo<K, V, F>
+where
+    F: Fn(&K) -> usize,
+{
+    /// Store `value` for `key` if it's smaller than what's there, returning
+    /// whether it was.
+    pub fn keep_min(&mut self, key: K, value: V) -> bool {
+        self.values.keep_min((self.index)(&key), value)
+    }
+
+    /// Store `value` for `key` if it's larger than what's there, returning
+    /// whether it was.
+    pub fn keep_max(&mut self, key: K, value: V) -> bool {
+        self.values.keep_max((self.index)(&key), value)
+    }
+}
+
+impl<K: Hash + Eq, V> Cache<K, V> for Memo<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.values.get(key)
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.values.insert(key, value)
+    }
+}
+
+impl<V> Cache<usize, V> for DenseMemo<V> {
+    fn get(&self, key: &usize) -> Option<&V> {
+        self.values[*key].as_ref()
+    }
+
+    fn insert(&mut self, key: usize, value: V) -> Option<V> {
+        self.values[key].replace(value)
+    }
+}
+
+impl<K, V, F> Cache<K, V> for IndexedMemo<K, V, F>
+where
+    F: Fn(&K) -> usize,
+{
+    fn get(&self, key: &K) -> Option<&V> {
+        self.values.get((self.index)(key))
+    }
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.values.insert((self.index)(&key), value)
+    }
+}
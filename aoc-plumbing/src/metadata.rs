@@ -0,0 +1,127 @@
+//! Describing how a result came to be, so archived answers and timings still
+//! make sense once they're separated from the run that produced them.
+use std::{process::Command, time::SystemTime};
+
+use serde::Serialize;
+
+/// Where, when, and from what a result was produced. Anything that can't be
+/// determined is `null`.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{metadata::Metadata, Solution};
+/// let metadata = Metadata::collect("0.1.0", "1000\n2000\n");
+/// assert_eq!(metadata.input_hash, "6267d714dce49b46");
+///
+/// let json = serde_json::to_value(Solution::new(1, 2).with_metadata(metadata)).unwrap();
+/// assert_eq!(json["metadata"]["version"], "0.1.0");
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct Metadata {
+    /// The version of whatever produced the result.
+    pub version: String,
+    /// The commit of the code, from `AOC_GIT_COMMIT` if it's set (say, in a
+    /// CI checkout without a `.git`), otherwise from the current checkout.
+    pub git_commit: Option<String>,
+    /// The [content_hash] of the input.
+    pub input_hash: String,
+    pub hostname: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: Option<u64>,
+}
+
+impl Metadata {
+    /// The metadata for solving `input` here and now, with `version` usually
+    /// being the caller's `env!("CARGO_PKG_VERSION")`.
+    pub fn collect(version: &str, input: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            git_commit: git_commit(),
+            input_hash: content_hash(input.as_bytes()),
+            hostname: hostname(),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+        }
+    }
+
+    /// The names and displayed values of each field, as in
+    /// [Formats::record](crate::formats::Formats::record).
+    pub fn record(&self) -> Vec<(&'static str, String)> {
+        let show = |value: &Option<String>| value.clone().unwrap_or_default();
+        vec![
+            ("version", self.version.clone()),
+            ("git_commit", show(&self.git_commit)),
+            ("input_hash", self.input_hash.clone()),
+            ("hostname", show(&self.hostname)),
+            (
+                "timestamp",
+                self.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+            ),
+        ]
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, in hex. Unlike the std hashers it's
+/// stable across releases, so hashes can be compared between runs.
+///
+/// ```
+/// use aoc_plumbing::metadata::content_hash;
+/// assert_eq!(content_hash(b""), "cbf29ce484222325");
+/// assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+/// ```
+pub fn content_hash(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn git_commit() -> Option<String> {
+    if let Ok(commit) = std::env::var("AOC_GIT_COMMIT") {
+        return Some(commit);
+    }
+
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|commit| commit.trim().to_string())
+}
+
+fn hostname() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|name| name.trim().to_string())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collected() {
+        let metadata = Metadata::collect("1.2.3", "");
+        assert_eq!(metadata.version, "1.2.3");
+        assert_eq!(metadata.input_hash, content_hash(b""));
+        assert!(metadata.timestamp.unwrap() > 1_600_000_000);
+        if cfg!(target_os = "linux") {
+            assert!(metadata.hostname.is_some());
+        }
+
+        let record = metadata.record();
+        assert_eq!(record[0], ("version", "1.2.3".to_string()));
+        assert_eq!(record.len(), 5);
+    }
+}
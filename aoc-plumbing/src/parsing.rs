@@ -0,0 +1,144 @@
+//! Parsers for the shapes puzzle inputs keep coming in, mostly as nom
+//! combinators.
+//!
+//! The examples in the days' tests are usually indented to line up with the
+//! code around them, so these skip leading spaces on each line.
+use std::fmt::Display;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{line_ending, multispace0, space0},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+    IResult, Parser,
+};
+
+use crate::AocError;
+
+/// A signed integer, ignoring any spaces around it.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::signed;
+/// assert_eq!(signed("  -12 ,3"), Ok((",3", -12)));
+/// ```
+pub fn signed(input: &str) -> IResult<&str, i64> {
+    delimited(space0, nom::character::complete::i64, space0)(input)
+}
+
+/// An unsigned integer, ignoring any spaces around it.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::unsigned;
+/// assert_eq!(unsigned("12 \n3"), Ok(("\n3", 12)));
+/// assert!(unsigned("-12").is_err());
+/// ```
+pub fn unsigned(input: &str) -> IResult<&str, u64> {
+    delimited(space0, nom::character::complete::u64, space0)(input)
+}
+
+/// One or more of `parser`, one per line.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::{separated_lines, signed};
+/// let (rest, values) = separated_lines(signed)("1\n  -2\n3\n\n4").unwrap();
+///
+/// assert_eq!(values, vec![1, -2, 3]);
+/// assert_eq!(rest, "\n\n4");
+/// ```
+pub fn separated_lines<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(tuple((line_ending, space0)), parser)
+}
+
+/// One or more of `parser`, separated by blank lines.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::{blocks, separated_lines, unsigned};
+/// let input = "1000\n2000\n\n4000\n  \n5000\n6000";
+/// let (_, elves) = blocks(separated_lines(unsigned))(input).unwrap();
+///
+/// assert_eq!(elves, vec![vec![1000, 2000], vec![4000], vec![5000, 6000]]);
+/// ```
+pub fn blocks<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    separated_list1(tuple((line_ending, space0, line_ending, space0)), parser)
+}
+
+/// `parser` after `label`, like the `Test: divisible by 23` in a list of
+/// properties. Any whitespace before the label, including newlines, is
+/// skipped.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::{field, unsigned};
+/// use nom::sequence::pair;
+/// let mut test = pair(field("Test: divisible by", unsigned), field("If true:", unsigned));
+///
+/// assert_eq!(test("Test: divisible by 23\n    If true: 2"), Ok(("", (23, 2))));
+/// ```
+pub fn field<'a, O, F>(label: &'static str, parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, nom::error::Error<&'a str>>,
+{
+    preceded(tuple((multispace0, tag(label))), parser)
+}
+
+/// The rows of a grid with a cell for each char of `lines`, as mapped by `f`.
+/// Rows shorter than the longest are padded out with `pad`. A char that `f`
+/// rejects is reported at its row and column in the grid.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::parsing::char_grid;
+/// let digits = |ch: char| ch.to_digit(10).ok_or("not a digit");
+/// let grid = char_grid("303\n25\n6".lines(), 0, digits).unwrap();
+///
+/// assert_eq!(grid, vec![vec![3, 0, 3], vec![2, 5, 0], vec![6, 0, 0]]);
+/// assert_eq!(
+///     char_grid("12\n3x".lines(), 0, digits).unwrap_err().to_string(),
+///     "failed to parse line 2, column 2 (not a digit): `3x`"
+/// );
+/// ```
+pub fn char_grid<'a, T, E, F>(
+    lines: impl IntoIterator<Item = &'a str>,
+    pad: T,
+    mut f: F,
+) -> Result<Vec<Vec<T>>, AocError>
+where
+    T: Clone,
+    E: Display,
+    F: FnMut(char) -> Result<T, E>,
+{
+    let mut rows = lines
+        .into_iter()
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(col, ch)| {
+                    f(ch).map_err(|e| AocError::Parse {
+                        line: row + 1,
+                        column: col + 1,
+                        snippet: line.to_string(),
+                        message: e.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+    for row in rows.iter_mut() {
+        row.resize(width, pad.clone());
+    }
+
+    Ok(rows)
+}
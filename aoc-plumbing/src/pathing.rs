@@ -0,0 +1,500 @@
+//! Searching for the cheapest way from one node to another, so each day only
+//! has to say what its nodes are and how they connect.
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    convert::Infallible,
+    hash::Hash,
+};
+
+use num::{traits::SaturatingAdd, Bounded, Zero};
+use rustc_hash::FxHashMap;
+
+use crate::memo::{Cache, Memo};
+
+/// A node waiting to be explored, ordered so the one with the cheapest
+/// estimated total is popped first.
+struct Entry<N, C> {
+    node: N,
+    cost: C,
+    /// The cost so far plus the heuristic's guess at the rest.
+    estimate: C,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+/// The cheapest node satisfying `goal` that can be reached from `start`,
+/// along with what it cost to get there. `successors` gives the nodes
+/// reachable in one step from a node, with the (non-negative) cost of that
+/// step.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::pathing::dijkstra;
+/// // a step of 1 costs 1, but a jump of 3 only costs 2
+/// let found = dijkstra(0_i32, |n| [(n + 1, 1), (n + 3, 2)], |n| *n == 6);
+/// assert_eq!(found, Some((6, 4)));
+///
+/// let nowhere = dijkstra(0_i32, |n| (*n > -5).then(|| (n - 1, 1)), |n| *n == 1);
+/// assert_eq!(nowhere, None);
+/// ```
+pub fn dijkstra<N, C, FN, IN, FG>(start: N, mut successors: FN, goal: FG) -> Option<(N, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    infallible(try_dijkstra(start, |node| Ok(successors(node)), goal))
+}
+
+/// [dijkstra] for successors that can fail, such as by being cancelled. The
+/// search stops at the first error.
+pub fn try_dijkstra<N, C, E, FN, IN, FG>(
+    start: N,
+    successors: FN,
+    goal: FG,
+) -> Result<Option<(N, C)>, E>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> Result<IN, E>,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    search(
+        start,
+        successors,
+        |_| C::zero(),
+        goal,
+        &mut Memo::new(),
+        None,
+    )
+}
+
+/// [dijkstra], but with every node along the way from `start` to the goal,
+/// inclusive of both.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::pathing::dijkstra_path;
+/// let found = dijkstra_path(0_i32, |n| [(n + 1, 1), (n + 3, 2)], |n| *n == 6);
+///
+/// assert_eq!(found, Some((vec![0, 3, 6], 4)));
+/// ```
+pub fn dijkstra_path<N, C, FN, IN, FG>(start: N, successors: FN, goal: FG) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FG: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::zero(), goal)
+}
+
+/// The cheapest path from `start` to a node satisfying `goal`, inclusive of
+/// both, along with its cost. Like [dijkstra_path], but nodes that look
+/// closer to the goal by `heuristic` are explored first.
+///
+/// The path is only guaranteed to be the cheapest if `heuristic` never
+/// overestimates the remaining cost, and never drops by more than the cost of
+/// a step (as with the manhattan distance on a grid of unit steps).
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::pathing::astar;
+/// let found = astar(
+///     0_i32,
+///     |n| [(n + 1, 1), (n + 3, 2)],
+///     |n| (9 - n).max(0) / 2,
+///     |n| *n == 9,
+/// );
+///
+/// assert_eq!(found, Some((vec![0, 3, 6, 9], 6)));
+/// ```
+pub fn astar<N, C, FN, IN, FH, FG>(
+    start: N,
+    mut successors: FN,
+    heuristic: FH,
+    goal: FG,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    infallible(try_astar(
+        start,
+        |node| Ok(successors(node)),
+        heuristic,
+        goal,
+    ))
+}
+
+/// [astar] for successors that can fail, such as by being cancelled. The
+/// search stops at the first error.
+pub fn try_astar<N, C, E, FN, IN, FH, FG>(
+    start: N,
+    successors: FN,
+    heuristic: FH,
+    goal: FG,
+) -> Result<Option<(Vec<N>, C)>, E>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> Result<IN, E>,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+{
+    let mut parents = FxHashMap::default();
+    let found = search(
+        start,
+        successors,
+        heuristic,
+        goal,
+        &mut Memo::new(),
+        Some(&mut parents),
+    )?;
+
+    Ok(found.map(|(end, cost)| (unwind(end, &parents), cost)))
+}
+
+/// [try_astar] without the path, keeping the cheapest known cost of each node
+/// in `best` instead of a hash map. Nodes that can be numbered can then use a
+/// flat array, like an [IndexedMemo](crate::memo::IndexedMemo), and nodes that
+/// `best` treats as the same are only explored by the cheapest way to them.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::{memo::DenseMemo, pathing::try_astar_with};
+/// let mut best = DenseMemo::new(10);
+/// let found = try_astar_with(
+///     0_usize,
+///     |n| Ok::<_, ()>([(n + 1, 1), (n + 3, 2)].into_iter().filter(|(n, _)| *n < 10)),
+///     |n| (9 - n) / 2,
+///     |n| *n == 9,
+///     &mut best,
+/// );
+///
+/// assert_eq!(found, Ok(Some((9, 6))));
+/// assert_eq!(best.get(3), Some(&2));
+/// ```
+pub fn try_astar_with<N, C, E, FN, IN, FH, FG, B>(
+    start: N,
+    successors: FN,
+    heuristic: FH,
+    goal: FG,
+    best: &mut B,
+) -> Result<Option<(N, C)>, E>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> Result<IN, E>,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+    B: Cache<N, C>,
+{
+    search(start, successors, heuristic, goal, best, None)
+}
+
+/// The number of steps to every node reachable from any of `starts`, each of
+/// which is zero steps away. The keys are everything that was visited, so
+/// this doubles as a flood fill.
+///
+/// Every step counts the same, so when only the distances (or only what's
+/// reachable) matter, this is cheaper than searching with a cost.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::pathing::bfs_distances;
+/// // everything from 0 to 9, stepping by 2 or 3
+/// let distances = bfs_distances([0_u32], |n| [n + 2, n + 3].into_iter().filter(|n| *n < 10));
+///
+/// assert_eq!(distances.len(), 9);
+/// assert!(!distances.contains_key(&1));
+/// assert_eq!(distances[&6], 2);
+/// assert_eq!(distances[&7], 3);
+/// ```
+pub fn bfs_distances<N, S, FN, IN>(starts: S, mut successors: FN) -> FxHashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut distances = FxHashMap::default();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if distances.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// The cheapest cost between every pair of nodes (Floyd–Warshall), where
+/// `adjacency[i][j]` is the cost of the edge from `i` to `j`, or the max value
+/// of `C` if there isn't one. Every node is free to reach from itself, and
+/// pairs that can't reach each other are left at the max instead of
+/// overflowing.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::pathing::all_pairs_shortest_paths;
+/// const NONE: i64 = i64::MAX;
+/// // 0 -> 1 -> 2 is cheaper than 0 -> 2, and nothing leads to 3
+/// let adjacency = vec![
+///     vec![0, 1, 5, NONE],
+///     vec![NONE, 0, 2, NONE],
+///     vec![NONE, NONE, 0, NONE],
+///     vec![1, NONE, NONE, 0],
+/// ];
+/// let costs = all_pairs_shortest_paths(adjacency);
+///
+/// assert_eq!(costs[0], vec![0, 1, 3, NONE]);
+/// assert_eq!(costs[3], vec![1, 2, 4, 0]);
+/// ```
+pub fn all_pairs_shortest_paths<C>(mut adjacency: Vec<Vec<C>>) -> Vec<Vec<C>>
+where
+    C: Copy + Ord + Zero + Bounded + SaturatingAdd,
+{
+    let len = adjacency.len();
+    for (i, row) in adjacency.iter_mut().enumerate() {
+        assert_eq!(row.len(), len, "adjacency must be square");
+        row[i] = C::zero();
+    }
+
+    for k in 0..len {
+        let from_k = adjacency[k].clone();
+        for row in adjacency.iter_mut() {
+            let to_k = row[k];
+            if to_k == C::max_value() {
+                continue;
+            }
+
+            for (cost, rest) in row.iter_mut().zip(from_k.iter()) {
+                *cost = (*cost).min(to_k.saturating_add(rest));
+            }
+        }
+    }
+
+    adjacency
+}
+
+/// The path to `end`, by following each node's parent back to the start.
+fn unwind<N: Clone + Eq + Hash>(end: N, parents: &FxHashMap<N, N>) -> Vec<N> {
+    let mut path = vec![end];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+fn infallible<T>(res: Result<T, Infallible>) -> T {
+    match res {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+/// The search shared by everything here, recording the parent of each node
+/// it finds a cheaper way to if given somewhere to put them. A `heuristic` of
+/// zero everywhere makes this plain dijkstra.
+fn search<N, C, E, FN, IN, FH, FG, B>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut goal: FG,
+    best: &mut B,
+    mut parents: Option<&mut FxHashMap<N, N>>,
+) -> Result<Option<(N, C)>, E>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    FN: FnMut(&N) -> Result<IN, E>,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FG: FnMut(&N) -> bool,
+    B: Cache<N, C>,
+{
+    let mut heap = BinaryHeap::new();
+
+    best.insert(start.clone(), C::zero());
+    heap.push(Entry {
+        estimate: heuristic(&start),
+        node: start,
+        cost: C::zero(),
+    });
+
+    while let Some(Entry { node, cost, .. }) = heap.pop() {
+        if goal(&node) {
+            return Ok(Some((node, cost)));
+        }
+
+        // a cheaper way here was already explored
+        if matches!(best.get(&node), Some(b) if cost > *b) {
+            continue;
+        }
+
+        for (next, step) in successors(&node)? {
+            let next_cost = cost + step;
+            if matches!(best.get(&next), Some(b) if next_cost >= *b) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+            if let Some(parents) = parents.as_mut() {
+                parents.insert(next.clone(), node.clone());
+            }
+            heap.push(Entry {
+                estimate: next_cost + heuristic(&next),
+                node: next,
+                cost: next_cost,
+            });
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_stop_the_search() {
+        let mut explored = 0;
+        let res = try_dijkstra(
+            0_u32,
+            |n| {
+                explored += 1;
+                if *n == 3 {
+                    Err("too far")
+                } else {
+                    Ok([(n + 1, 1_u32)])
+                }
+            },
+            |n| *n == 10,
+        );
+
+        assert_eq!(res, Err("too far"));
+        assert_eq!(explored, 4);
+    }
+
+    #[test]
+    fn cheapest_path() {
+        // stepping onto an odd column is expensive, less so on the second row
+        let cost = |row: i32, col: i32| match (row, col % 2) {
+            (_, 0) => 1,
+            (1, _) => 2,
+            _ => 5,
+        };
+        let found = dijkstra_path(
+            (0, 0),
+            |&(row, col): &(i32, i32)| {
+                [(row + 1, col), (row, col + 1)]
+                    .into_iter()
+                    .filter(|(r, c)| *r < 2 && *c < 3)
+                    .map(move |(r, c)| ((r, c), cost(r, c)))
+            },
+            |node| *node == (1, 2),
+        );
+
+        assert_eq!(found, Some((vec![(0, 0), (1, 0), (1, 1), (1, 2)], 4)));
+    }
+
+    #[test]
+    fn several_starts() {
+        let distances = bfs_distances([0_i32, 10, 0], |n| {
+            [n - 1, n + 1].into_iter().filter(|n| (0..=10).contains(n))
+        });
+
+        assert_eq!(distances.len(), 11);
+        assert_eq!(distances[&0], 0);
+        assert_eq!(distances[&10], 0);
+        assert_eq!(distances[&5], 5);
+        assert_eq!(distances[&7], 3);
+    }
+
+    #[test]
+    fn all_pairs_saturate() {
+        // a ring of edges so expensive that going most of the way around
+        // would overflow
+        let big = u32::MAX / 2;
+        let mut adjacency = vec![vec![u32::MAX; 4]; 4];
+        for (i, row) in adjacency.iter_mut().enumerate() {
+            row[(i + 1) % 4] = big;
+        }
+
+        let costs = all_pairs_shortest_paths(adjacency);
+        assert_eq!(costs[0][1], big);
+        assert_eq!(costs[0][2], big * 2);
+        assert_eq!(costs[0][3], u32::MAX);
+        assert_eq!(costs[2][2], 0);
+        assert!(all_pairs_shortest_paths::<i64>(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn heuristic_explores_less() {
+        // an open grid, where only the manhattan distance matters
+        let search = |heuristic: fn(&(i32, i32)) -> i32| {
+            let mut explored = 0;
+            let found = astar(
+                (0_i32, 0_i32),
+                |&(x, y)| {
+                    explored += 1;
+                    [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                        .into_iter()
+                        .filter(|(x, y)| x.abs() <= 20 && y.abs() <= 20)
+                        .map(|node| (node, 1))
+                },
+                heuristic,
+                |node| *node == (10, 10),
+            );
+            (found.map(|(path, cost)| (path.len(), cost)), explored)
+        };
+
+        let (guided, guided_explored) = search(|&(x, y)| (10 - x).abs() + (10 - y).abs());
+        let (blind, blind_explored) = search(|_| 0);
+        assert_eq!(guided, Some((21, 20)));
+        assert_eq!(blind, guided);
+        assert!(guided_explored < blind_explored);
+    }
+}
@@ -1,7 +1,29 @@
-use std::{fmt::Display, str::FromStr};
+use std::{fmt::Display, io::Read, str::FromStr, time::Instant};
 
 use serde::Serialize;
 
+use crate::{
+    cancellation::{with_cancellation, CancellationToken},
+    expected::{Check, Expected, PartDiff, SolutionDiff, Validation},
+    metadata::Metadata,
+    timing::{peak_rss, TimedSolution, Timings},
+};
+
+/// Evaluate `$body` in a span for a stage of solving `$problem`, with the
+/// day and title as fields, if the `trace` feature is enabled.
+macro_rules! stage {
+    ($name:literal, $problem:ty, $body:expr) => {{
+        #[cfg(feature = "trace")]
+        let _span = tracing::info_span!(
+            $name,
+            day = <$problem as Problem>::DAY,
+            title = <$problem as Problem>::TITLE
+        )
+        .entered();
+        $body
+    }};
+}
+
 /// This struct enables printing a given solution in either plaintext or JSON,
 /// depending on the presence of the `AOC_OUTPUT_JSON` ENV var. Its main purpose
 /// is to standardize the output for consuption by the CI system.
@@ -31,6 +53,9 @@ where
 {
     pub part_one: T,
     pub part_two: G,
+    /// How the solution came to be, if it's being archived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
 }
 
 /// The default implementation of `Solution` is as follows:
@@ -53,17 +78,92 @@ where
     G: Display + Serialize + PartialEq,
 {
     pub fn new(part_one: T, part_two: G) -> Self {
-        Self { part_one, part_two }
+        Self {
+            part_one,
+            part_two,
+            metadata: None,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// The parts of `other` whose displayed answers differ from these, which
+    /// are taken to be the expected ones.
+    pub fn diff<U, H>(&self, other: &Solution<U, H>) -> SolutionDiff
+    where
+        U: Display + Serialize + PartialEq,
+        H: Display + Serialize + PartialEq,
+    {
+        SolutionDiff {
+            part_one: PartDiff::new(&self.part_one.to_string(), &other.part_one.to_string()),
+            part_two: PartDiff::new(&self.part_two.to_string(), &other.part_two.to_string()),
+        }
     }
 }
 
+/// Parts without an answer are left out, and multi-line answers start on the
+/// line after their label:
+///
+/// ```
+/// use aoc_plumbing::{NoAnswer, Solution};
+/// let grid = Solution::new(4, "#.\n.#");
+/// assert_eq!(grid.to_string(), "part 1: 4\npart 2:\n#.\n.#");
+///
+/// let single = Solution::new(4, NoAnswer);
+/// assert_eq!(single.to_string(), "part 1: 4");
+/// ```
 impl<T, G> Display for Solution<T, G>
 where
     T: Display + Serialize + PartialEq,
     G: Display + Serialize + PartialEq,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "part 1: {}\npart 2: {}", self.part_one, self.part_two)
+        let lines = [self.part_one.to_string(), self.part_two.to_string()]
+            .iter()
+            .enumerate()
+            .filter(|(_, answer)| !answer.is_empty())
+            .map(|(idx, answer)| display_part(idx + 1, answer))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Label an answer as it's displayed in a [Solution], which other frontends
+/// can use to display answers the same way.
+pub fn display_part(part: usize, answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("part {}:\n{}", part, answer)
+    } else {
+        format!("part {}: {}", part, answer)
+    }
+}
+
+/// The answer to a part that doesn't have one, like the second part of the
+/// last day. It displays as nothing and serializes as `null`.
+///
+/// ```
+/// use aoc_plumbing::{NoAnswer, Solution};
+/// assert_eq!(
+///     serde_json::to_string(&Solution::new(4, NoAnswer)).unwrap(),
+///     "{\"part_one\":4,\"part_two\":null}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl Serialize for NoAnswer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_none()
     }
 }
 
@@ -77,10 +177,85 @@ where
     }
 }
 
+/// An example input from a problem's description, along with the expected
+/// answers as they would be displayed. Parts without an expected answer are
+/// not checked.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::problem::Example;
+/// let example = Example::new("1\n2\n3", "6", "3");
+///
+/// assert_eq!(example.part_one, Some("6"));
+/// assert_eq!(example.part_two, Some("3"));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part_one: Option<&'static str>,
+    pub part_two: Option<&'static str>,
+}
+
+impl Example {
+    pub const fn new(input: &'static str, part_one: &'static str, part_two: &'static str) -> Self {
+        Self {
+            input,
+            part_one: Some(part_one),
+            part_two: Some(part_two),
+        }
+    }
+}
+
+/// Generate a test checking every one of a [Problem]'s (or a
+/// [BorrowedProblem]'s) examples with [BorrowedProblem::verify_examples].
+/// `#[aoc_problem]` does this itself, so this is for the concrete types of
+/// generic problems.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     aoc_plumbing::example_tests!(BeaconExclusionZoneGen<10, 20>);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($problem:ty) => {
+        #[test]
+        fn examples() {
+            let validations = <$problem as $crate::BorrowedProblem>::verify_examples()
+                .unwrap_or_else(|_| panic!("Failed to solve the examples"));
+            for (idx, validation) in validations.iter().enumerate() {
+                assert!(
+                    !validation.part_one.failed(),
+                    "example {} part one: {:?}",
+                    idx + 1,
+                    validation.part_one
+                );
+                assert!(
+                    !validation.part_two.failed(),
+                    "example {} part two: {:?}",
+                    idx + 1,
+                    validation.part_two
+                );
+            }
+        }
+    };
+}
+
 pub trait Problem: FromStr {
     const DAY: usize;
     const TITLE: &'static str;
     const README: &'static str;
+    /// The examples from the problem's description, if any.
+    const EXAMPLES: &'static [Example] = &[];
+    /// The answers for the day's `input.txt`, if known.
+    const EXPECTED: Option<Expected> = None;
+    /// Whether part two relies on state left behind by part one, so it can
+    /// only be run (or benchmarked) after it on the same instance.
+    const LINKED_PARTS: bool = false;
 
     type ProblemError: Send + Sync + From<<Self as FromStr>::Err> + 'static;
     type P1: Display + Serialize + PartialEq;
@@ -93,11 +268,241 @@ pub trait Problem: FromStr {
         Self::from_str(raw_input)
     }
 
+    /// Parse and solve both parts. With the `trace` feature, each of these is
+    /// in its own span (`parse`, `part_one`, and `part_two`), with the `day`
+    /// and `title` as fields.
     fn solve(raw_input: &str) -> Result<Solution<Self::P1, Self::P2>, Self::ProblemError> {
-        let mut inst = Self::instance(raw_input)?;
+        let mut inst = stage!("parse", Self, Self::instance(raw_input))?;
+        let part_one = stage!("part_one", Self, inst.part_one())?;
+        let part_two = stage!("part_two", Self, inst.part_two())?;
+        Ok(Solution::new(part_one, part_two))
+    }
+
+    /// Parse the input from `reader`. This reads the whole input into memory
+    /// first, but days with line-oriented inputs can override it to parse as
+    /// they read, so very large inputs never need to be held as a string.
+    fn instance_reader<R: Read>(mut reader: R) -> Result<Self, Self::ProblemError>
+    where
+        Self::ProblemError: From<std::io::Error>,
+    {
+        let mut raw_input = String::new();
+        reader.read_to_string(&mut raw_input)?;
+        Ok(Self::instance(&raw_input)?)
+    }
+
+    /// [Problem::solve] for an input read from `reader`, which is parsed with
+    /// [Problem::instance_reader].
+    ///
+    /// ```
+    /// use aoc_plumbing::{Problem, Solution};
+    /// # use std::str::FromStr;
+    /// # struct Lines(usize);
+    /// # impl FromStr for Lines {
+    /// #     type Err = anyhow::Error;
+    /// #     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// #         Ok(Self(s.lines().count()))
+    /// #     }
+    /// # }
+    /// # impl Problem for Lines {
+    /// #     const DAY: usize = 1;
+    /// #     const TITLE: &'static str = "lines";
+    /// #     const README: &'static str = "";
+    /// #     type ProblemError = anyhow::Error;
+    /// #     type P1 = usize;
+    /// #     type P2 = usize;
+    /// #     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+    /// #         Ok(self.0)
+    /// #     }
+    /// #     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+    /// #         Ok(self.0 * 2)
+    /// #     }
+    /// # }
+    ///
+    /// let input = "a\nb\nc";
+    /// assert_eq!(Lines::solve_reader(input.as_bytes()).unwrap(), Solution::new(3, 6));
+    /// ```
+    fn solve_reader<R: Read>(reader: R) -> Result<Solution<Self::P1, Self::P2>, Self::ProblemError>
+    where
+        Self::ProblemError: From<std::io::Error>,
+    {
+        let mut inst = Self::instance_reader(reader)?;
         Ok(Solution::new(inst.part_one()?, inst.part_two()?))
     }
 
+    /// Solve and check the answers against [Problem::EXPECTED]. Every part is
+    /// [Unknown](crate::Check::Unknown) if there are no expected answers.
+    fn validate(raw_input: &str) -> Result<Validation, Self::ProblemError> {
+        let solution = Self::solve(raw_input)?;
+        Ok(Self::EXPECTED.unwrap_or_default().check(&solution))
+    }
+
+    /// Solve and check each of [Problem::EXAMPLES], in order. Parts without
+    /// an expected answer are [Unknown](crate::Check::Unknown), and aren't
+    /// solved unless part two needs them, since they might not support the
+    /// example.
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use aoc_plumbing::{Check, Example, Problem};
+    /// # struct Lines(usize);
+    /// # impl FromStr for Lines {
+    /// #     type Err = std::io::Error;
+    /// #     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// #         Ok(Self(s.lines().count()))
+    /// #     }
+    /// # }
+    /// impl Problem for Lines {
+    ///     const EXAMPLES: &'static [Example] = &[
+    ///         Example::new("a\nb", "2", "4"),
+    ///         Example { input: "a", part_one: Some("2"), part_two: None },
+    ///     ];
+    ///     // ...
+    /// #     const DAY: usize = 1;
+    /// #     const TITLE: &'static str = "lines";
+    /// #     const README: &'static str = "";
+    /// #     type ProblemError = std::io::Error;
+    /// #     type P1 = usize;
+    /// #     type P2 = usize;
+    /// #     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+    /// #         Ok(self.0)
+    /// #     }
+    /// #     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+    /// #         Ok(self.0 * 2)
+    /// #     }
+    /// }
+    ///
+    /// let validations = Lines::verify_examples().unwrap();
+    /// assert_eq!(validations[0].failures(), 0);
+    /// assert!(validations[1].part_one.failed());
+    /// assert_eq!(validations[1].part_two, Check::Unknown);
+    /// ```
+    ///
+    /// With [Problem::LINKED_PARTS], part one is still solved before part
+    /// two, even without an expected answer of its own.
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use aoc_plumbing::{BorrowedProblem, Check, Example, Problem};
+    /// # struct Scan { lines: usize, seen: usize }
+    /// # impl FromStr for Scan {
+    /// #     type Err = std::io::Error;
+    /// #     fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// #         Ok(Self { lines: s.lines().count(), seen: 0 })
+    /// #     }
+    /// # }
+    /// impl Problem for Scan {
+    ///     const EXAMPLES: &'static [Example] = &[Example {
+    ///         input: "a\nb",
+    ///         part_one: None,
+    ///         part_two: Some("2"),
+    ///     }];
+    ///     const LINKED_PARTS: bool = true;
+    ///     // ...
+    /// #     const DAY: usize = 1;
+    /// #     const TITLE: &'static str = "scan";
+    /// #     const README: &'static str = "";
+    /// #     type ProblemError = std::io::Error;
+    /// #     type P1 = usize;
+    /// #     type P2 = usize;
+    ///
+    ///     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+    ///         self.seen = self.lines;
+    ///         Ok(self.lines)
+    ///     }
+    ///
+    ///     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+    ///         Ok(self.seen)
+    ///     }
+    /// }
+    ///
+    /// let validations = <Scan as Problem>::verify_examples().unwrap();
+    /// assert_eq!(validations[0].part_two, Check::Pass);
+    /// let validations = <Scan as BorrowedProblem>::verify_examples().unwrap();
+    /// assert_eq!(validations[0].part_two, Check::Pass);
+    /// ```
+    fn verify_examples() -> Result<Vec<Validation>, Self::ProblemError> {
+        Self::EXAMPLES
+            .iter()
+            .map(|example| {
+                let mut inst = Self::instance(example.input)?;
+                let part_one = match example.part_one {
+                    Some(expected) => Check::new(Some(expected), &inst.part_one()?.to_string()),
+                    // part two relies on part one having been solved
+                    None if Self::LINKED_PARTS && example.part_two.is_some() => {
+                        inst.part_one()?;
+                        Check::Unknown
+                    }
+                    None => Check::Unknown,
+                };
+                let part_two = match example.part_two {
+                    Some(expected) => Check::new(Some(expected), &inst.part_two()?.to_string()),
+                    None => Check::Unknown,
+                };
+
+                Ok(Validation { part_one, part_two })
+            })
+            .collect()
+    }
+
+    /// [Problem::solve] with `token` as the thread's cancellation token, so
+    /// solvers that check it fail with
+    /// [Cancelled](crate::cancellation::Cancelled) once it's cancelled.
+    fn solve_cancellable(
+        raw_input: &str,
+        token: &CancellationToken,
+    ) -> Result<Solution<Self::P1, Self::P2>, Self::ProblemError> {
+        with_cancellation(token, || Self::solve(raw_input))
+    }
+
+    /// [Problem::solve_cancellable] on tokio's blocking pool, for frontends
+    /// that are async. Dropping the future cancels `token`.
+    #[cfg(feature = "async")]
+    fn solve_async(
+        raw_input: String,
+        token: CancellationToken,
+    ) -> crate::asynchronous::SolveFuture<Self>
+    where
+        Self: 'static,
+        Self::P1: Send + 'static,
+        Self::P2: Send + 'static,
+    {
+        Box::pin(crate::asynchronous::solve::<Self>(raw_input, token))
+    }
+
+    /// Solve both parts, timing parsing and each part separately.
+    fn solve_timed(
+        raw_input: &str,
+    ) -> Result<TimedSolution<Self::P1, Self::P2>, Self::ProblemError> {
+        let start = Instant::now();
+        let mut inst = Self::instance(raw_input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part_one = inst.part_one()?;
+        let part_one_time = start.elapsed();
+
+        let start = Instant::now();
+        let part_two = inst.part_two()?;
+        let part_two_time = start.elapsed();
+
+        Ok(TimedSolution {
+            solution: Solution::new(part_one, part_two),
+            timings: Timings {
+                parse,
+                part_one: part_one_time,
+                part_two: part_two_time,
+            },
+            peak_rss: peak_rss(),
+            metadata: None,
+        })
+    }
+
+    /// Notable facts about the parsed input (sizes, counts, bounds), for
+    /// checking a parser without solving anything.
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
     fn problem_label() -> String {
         format!(
             "{:03} {}",
@@ -118,3 +523,270 @@ pub trait Problem: FromStr {
         )
     }
 }
+
+/// Solving a problem in separate phases: parsing the input into something
+/// other than the problem itself, then solving each part from that.
+///
+/// Days opt in to this when the work they'd rather not count as solving (or
+/// the shape they'd rather solve from) isn't the problem's own instance. A
+/// day registered with `register!(T, year, phases)` is solved through this by
+/// the cli and the benchmarks, so what they time as parsing is
+/// [Phases::parse]. [Problem::solve] still goes through [Problem::instance],
+/// which can share the work by wrapping what's parsed, as below.
+///
+/// # Usage
+///
+/// ```
+/// use std::str::FromStr;
+/// use aoc_plumbing::{Phases, Problem};
+///
+/// // the running totals, which both parts are answered from
+/// struct Totals(Vec<usize>);
+///
+/// impl FromStr for Totals {
+///     type Err = std::num::ParseIntError;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(Self(Self::parse(s)?))
+///     }
+/// }
+///
+/// impl Problem for Totals {
+///     const DAY: usize = 1;
+///     const TITLE: &'static str = "totals";
+///     const README: &'static str = "";
+///
+///     type ProblemError = std::num::ParseIntError;
+///     type P1 = usize;
+///     type P2 = usize;
+///
+///     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+///         <Self as Phases>::part_one(&mut self.0)
+///     }
+///
+///     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+///         <Self as Phases>::part_two(&mut self.0)
+///     }
+/// }
+///
+/// impl Phases for Totals {
+///     type Parsed = Vec<usize>;
+///
+///     fn parse(raw_input: &str) -> Result<Self::Parsed, Self::ProblemError> {
+///         let mut total = 0;
+///         raw_input
+///             .lines()
+///             .map(|line| {
+///                 total += line.parse::<usize>()?;
+///                 Ok(total)
+///             })
+///             .collect()
+///     }
+///
+///     fn part_one(parsed: &mut Self::Parsed) -> Result<Self::P1, Self::ProblemError> {
+///         Ok(parsed.last().copied().unwrap_or_default())
+///     }
+///
+///     fn part_two(parsed: &mut Self::Parsed) -> Result<Self::P2, Self::ProblemError> {
+///         Ok(parsed.iter().filter(|total| **total > 4).count())
+///     }
+/// }
+///
+/// let mut parsed = Totals::parse("2\n3\n4").unwrap();
+/// assert_eq!(parsed, vec![2, 5, 9]);
+/// assert_eq!(<Totals as Phases>::part_one(&mut parsed), Ok(9));
+/// assert_eq!(<Totals as Phases>::part_two(&mut parsed), Ok(2));
+/// assert_eq!(Totals::solve("2\n3\n4").unwrap().part_two, 2);
+/// ```
+pub trait Phases: Problem {
+    /// What the input is parsed into, which both parts are solved from.
+    type Parsed;
+
+    fn parse(raw_input: &str) -> Result<Self::Parsed, Self::ProblemError>;
+    fn part_one(parsed: &mut Self::Parsed) -> Result<Self::P1, Self::ProblemError>;
+    fn part_two(parsed: &mut Self::Parsed) -> Result<Self::P2, Self::ProblemError>;
+
+    /// See [Problem::summary].
+    fn summary(_parsed: &Self::Parsed) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// A problem whose instance may borrow from the input, so a zero-copy parser
+/// doesn't have to be followed by a second pass converting everything into
+/// owned structures.
+///
+/// Every [Problem] is one, with an instance that borrows nothing. Implement
+/// this instead of `Problem` when the instance needs the input's lifetime. The
+/// two traits have items with the same names, so generic code bounded by both
+/// has to qualify them.
+///
+/// # Usage
+///
+/// ```
+/// use aoc_plumbing::{BorrowedProblem, Solution};
+///
+/// struct LongestWord;
+///
+/// impl BorrowedProblem for LongestWord {
+///     const DAY: usize = 1;
+///     const TITLE: &'static str = "longest word";
+///     const README: &'static str = "";
+///
+///     type ProblemError = std::convert::Infallible;
+///     type Instance<'a> = Vec<&'a str>;
+///     type P1 = String;
+///     type P2 = usize;
+///
+///     fn instance(raw_input: &str) -> Result<Self::Instance<'_>, Self::ProblemError> {
+///         Ok(raw_input.split_whitespace().collect())
+///     }
+///
+///     fn part_one(inst: &mut Self::Instance<'_>) -> Result<Self::P1, Self::ProblemError> {
+///         Ok(inst.iter().max_by_key(|w| w.len()).unwrap_or(&"").to_string())
+///     }
+///
+///     fn part_two(inst: &mut Self::Instance<'_>) -> Result<Self::P2, Self::ProblemError> {
+///         Ok(inst.len())
+///     }
+/// }
+///
+/// assert_eq!(
+///     LongestWord::solve("a bb ccc").unwrap(),
+///     Solution::new("ccc".to_string(), 3)
+/// );
+/// ```
+pub trait BorrowedProblem {
+    const DAY: usize;
+    const TITLE: &'static str;
+    const README: &'static str;
+    /// The examples from the problem's description, if any.
+    const EXAMPLES: &'static [Example] = &[];
+    /// The answers for the day's `input.txt`, if known.
+    const EXPECTED: Option<Expected> = None;
+    /// See [Problem::LINKED_PARTS].
+    const LINKED_PARTS: bool = false;
+
+    type ProblemError: Send + Sync + 'static;
+    /// The parsed input, which may borrow from the raw input.
+    type Instance<'a>;
+    type P1: Display + Serialize + PartialEq;
+    type P2: Display + Serialize + PartialEq;
+
+    fn instance(raw_input: &str) -> Result<Self::Instance<'_>, Self::ProblemError>;
+    fn part_one(inst: &mut Self::Instance<'_>) -> Result<Self::P1, Self::ProblemError>;
+    fn part_two(inst: &mut Self::Instance<'_>) -> Result<Self::P2, Self::ProblemError>;
+
+    /// See [Problem::summary].
+    fn summary(_inst: &Self::Instance<'_>) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn solve(raw_input: &str) -> Result<Solution<Self::P1, Self::P2>, Self::ProblemError> {
+        let mut inst = Self::instance(raw_input)?;
+        Ok(Solution::new(
+            Self::part_one(&mut inst)?,
+            Self::part_two(&mut inst)?,
+        ))
+    }
+
+    /// See [Problem::validate].
+    fn validate(raw_input: &str) -> Result<Validation, Self::ProblemError> {
+        let solution = Self::solve(raw_input)?;
+        Ok(Self::EXPECTED.unwrap_or_default().check(&solution))
+    }
+
+    /// See [Problem::verify_examples].
+    fn verify_examples() -> Result<Vec<Validation>, Self::ProblemError> {
+        Self::EXAMPLES
+            .iter()
+            .map(|example| {
+                let mut inst = Self::instance(example.input)?;
+                let part_one = match example.part_one {
+                    Some(expected) => {
+                        Check::new(Some(expected), &Self::part_one(&mut inst)?.to_string())
+                    }
+                    // part two relies on part one having been solved
+                    None if Self::LINKED_PARTS && example.part_two.is_some() => {
+                        Self::part_one(&mut inst)?;
+                        Check::Unknown
+                    }
+                    None => Check::Unknown,
+                };
+                let part_two = match example.part_two {
+                    Some(expected) => {
+                        Check::new(Some(expected), &Self::part_two(&mut inst)?.to_string())
+                    }
+                    None => Check::Unknown,
+                };
+
+                Ok(Validation { part_one, part_two })
+            })
+            .collect()
+    }
+}
+
+impl<T: Problem> BorrowedProblem for T {
+    const DAY: usize = <T as Problem>::DAY;
+    const TITLE: &'static str = <T as Problem>::TITLE;
+    const README: &'static str = <T as Problem>::README;
+    const EXAMPLES: &'static [Example] = <T as Problem>::EXAMPLES;
+    const EXPECTED: Option<Expected> = <T as Problem>::EXPECTED;
+    const LINKED_PARTS: bool = <T as Problem>::LINKED_PARTS;
+
+    type ProblemError = <T as Problem>::ProblemError;
+    type Instance<'a> = T;
+    type P1 = <T as Problem>::P1;
+    type P2 = <T as Problem>::P2;
+
+    fn instance(raw_input: &str) -> Result<Self::Instance<'_>, Self::ProblemError> {
+        Ok(<T as Problem>::instance(raw_input)?)
+    }
+
+    fn part_one(inst: &mut Self::Instance<'_>) -> Result<Self::P1, Self::ProblemError> {
+        Problem::part_one(inst)
+    }
+
+    fn part_two(inst: &mut Self::Instance<'_>) -> Result<Self::P2, Self::ProblemError> {
+        Problem::part_two(inst)
+    }
+
+    fn summary(inst: &Self::Instance<'_>) -> Vec<(&'static str, String)> {
+        Problem::summary(inst)
+    }
+}
+
+/// A problem whose intermediate state can be rendered as it's solved, for
+/// animating in a terminal.
+pub trait Visualize: Problem {
+    /// The rendered state after each step of the solution, in order.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_>;
+}
+
+/// A named implementation of both parts of a problem.
+pub struct Implementation<T: Problem> {
+    pub name: &'static str,
+    pub part_one: fn(&mut T) -> Result<T::P1, T::ProblemError>,
+    pub part_two: fn(&mut T) -> Result<T::P2, T::ProblemError>,
+}
+
+impl<T: Problem> Implementation<T> {
+    pub const fn new(
+        name: &'static str,
+        part_one: fn(&mut T) -> Result<T::P1, T::ProblemError>,
+        part_two: fn(&mut T) -> Result<T::P2, T::ProblemError>,
+    ) -> Self {
+        Self {
+            name,
+            part_one,
+            part_two,
+        }
+    }
+}
+
+/// A problem with more than one way of solving it, for comparing their
+/// answers and performance.
+pub trait Alternatives: Problem + 'static {
+    /// Every implementation, including the one used by [Problem::solve].
+    const IMPLEMENTATIONS: &'static [Implementation<Self>];
+}
@@ -0,0 +1,82 @@
+//! Coarse progress from long-running solvers, for frontends to display.
+//!
+//! Solvers call [tick] as they go, which does nothing unless a frontend is
+//! listening via [with_progress]. Ticks can come from any thread, so solvers
+//! using rayon can tick from inside parallel iterators.
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    RwLock,
+};
+
+/// How far through a stage of its solution a solver is.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Progress {
+    /// What's being counted, e.g. `blueprints`.
+    pub stage: &'static str,
+    pub done: usize,
+    pub total: usize,
+}
+
+type Hook = Box<dyn Fn(Progress) + Send + Sync>;
+
+// checked first, so solvers don't pay for the lock when no one is listening
+static LISTENING: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+/// Report that `done` of the `total` things in `stage` are finished.
+pub fn tick(stage: &'static str, done: usize, total: usize) {
+    if !LISTENING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if let Ok(hook) = HOOK.read() {
+        if let Some(hook) = hook.as_ref() {
+            hook(Progress { stage, done, total });
+        }
+    }
+}
+
+/// Call `hook` with every [tick] made while `f` runs.
+///
+/// There is only one hook at a time, so a nested or concurrent call replaces
+/// the hook until it returns.
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use aoc_plumbing::progress::{tick, with_progress, Progress};
+///
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let hook_seen = seen.clone();
+/// with_progress(
+///     move |p: Progress| hook_seen.lock().unwrap().push(p.done),
+///     || (1..=3).for_each(|done| tick("things", done, 3)),
+/// );
+/// tick("things", 4, 3);
+///
+/// assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3]);
+/// ```
+pub fn with_progress<H, F, R>(hook: H, f: F) -> R
+where
+    H: Fn(Progress) + Send + Sync + 'static,
+    F: FnOnce() -> R,
+{
+    struct Reset;
+
+    // so a panicking solver doesn't leave the hook behind
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            LISTENING.store(false, Ordering::Relaxed);
+            if let Ok(mut hook) = HOOK.write() {
+                *hook = None;
+            }
+        }
+    }
+
+    if let Ok(mut current) = HOOK.write() {
+        *current = Some(Box::new(hook));
+    }
+    LISTENING.store(true, Ordering::Relaxed);
+
+    let _reset = Reset;
+    f()
+}
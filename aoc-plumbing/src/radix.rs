@@ -0,0 +1,383 @@
+use std::{
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, Neg, Sub},
+    str::FromStr,
+};
+
+/// Symbols for the negative digits, where the symbol for `-n` is at index
+/// `n - 1`. The first two match those used by SNAFU numbers.
+const NEGATIVE_SYMBOLS: [char; 9] = ['-', '=', '~', '^', '*', '%', '$', '#', '@'];
+
+/// Error returned when parsing a [BalancedRadix] from a string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ParseDigitError {
+    pub ch: char,
+    pub radix: u32,
+}
+
+impl Display for ParseDigitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid digit char for balanced base {}: {}",
+            self.radix, self.ch
+        )
+    }
+}
+
+impl std::error::Error for ParseDigitError {}
+
+/// A number in balanced base `B`, where each digit is in the range
+/// `-(B - 1) / 2..=(B - 1) / 2`.
+///
+/// `B` must be odd and between 3 and 19 (inclusive). Non-negative digits are
+/// written as `0`-`9`, and negative digits `-1` through `-9` are written as
+/// `-`, `=`, `~`, `^`, `*`, `%`, `$`, `#`, `@`, respectively.
+///
+/// # Examples
+/// ```
+/// use aoc_plumbing::radix::{BalancedRadix, Snafu};
+/// let n = Snafu::from(2022);
+/// assert_eq!(n.to_string(), "1=11-2");
+///
+/// let ternary: BalancedRadix<3> = "1-0".parse().unwrap();
+/// assert_eq!(ternary.to_decimal(), 6);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct BalancedRadix<const B: u32> {
+    // least significant digit first, with no trailing (most significant)
+    // zeros
+    digits: Vec<i8>,
+}
+
+/// A SNAFU number is balanced base 5.
+pub type Snafu = BalancedRadix<5>;
+
+impl<const B: u32> BalancedRadix<B> {
+    /// The largest value of a single digit.
+    pub const MAX_DIGIT: i8 = {
+        assert!(
+            B % 2 == 1 && B >= 3 && B <= 19,
+            "balanced radix must be odd and in 3..=19"
+        );
+        ((B - 1) / 2) as i8
+    };
+
+    /// The digits of this number, most significant first.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// let n: Snafu = "1=-0".parse().unwrap();
+    /// assert_eq!(n.digits().collect::<Vec<_>>(), vec![1, -2, -1, 0]);
+    /// ```
+    pub fn digits(&self) -> impl Iterator<Item = i8> + '_ {
+        self.digits.iter().rev().copied()
+    }
+
+    /// Convert this number to decimal.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// let n: Snafu = "1=-0-2".parse().unwrap();
+    /// assert_eq!(n.to_decimal(), 1747);
+    /// ```
+    ///
+    /// # Panics
+    /// If the value does not fit in an `i128`. See
+    /// [BalancedRadix::checked_to_decimal] for a non-panicking alternative.
+    pub fn to_decimal(&self) -> i128 {
+        self.checked_to_decimal()
+            .unwrap_or_else(|| panic!("{} does not fit in an i128", self))
+    }
+
+    /// Convert this number to decimal, returning `None` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// let n: Snafu = "2=-01".parse().unwrap();
+    /// assert_eq!(n.checked_to_decimal(), Some(976));
+    ///
+    /// let n: Snafu = "2".repeat(60).parse().unwrap();
+    /// assert_eq!(n.checked_to_decimal(), None);
+    /// ```
+    pub fn checked_to_decimal(&self) -> Option<i128> {
+        let base = B as i128;
+
+        self.digits().try_fold(0_i128, |acc, d| {
+            let d = d as i128;
+            // acc * base may overflow even though acc * base + d would not,
+            // so borrow from acc in that case to keep the intermediate value
+            // in range
+            if acc > 0 && d < 0 {
+                (acc - 1).checked_mul(base)?.checked_add(base + d)
+            } else if acc < 0 && d > 0 {
+                (acc + 1).checked_mul(base)?.checked_add(d - base)
+            } else {
+                acc.checked_mul(base)?.checked_add(d)
+            }
+        })
+    }
+
+    /// Convert this number to an `i64`, returning `None` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// assert_eq!(Snafu::from(i64::MIN).checked_to_i64(), Some(i64::MIN));
+    /// assert_eq!((Snafu::from(i64::MAX) + Snafu::from(1)).checked_to_i64(), None);
+    /// ```
+    pub fn checked_to_i64(&self) -> Option<i64> {
+        self.checked_to_decimal()
+            .and_then(|v| i64::try_from(v).ok())
+    }
+
+    /// The value of the digit written as `ch`, if it is valid for this base.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::{BalancedRadix, Snafu};
+    /// assert_eq!(Snafu::digit_value('='), Some(-2));
+    /// assert_eq!(Snafu::digit_value('3'), None);
+    /// assert_eq!(BalancedRadix::<7>::digit_value('3'), Some(3));
+    /// ```
+    pub fn digit_value(ch: char) -> Option<i8> {
+        let value = match ch.to_digit(10) {
+            Some(v) => v as i8,
+            None => {
+                -(NEGATIVE_SYMBOLS
+                    .iter()
+                    .position(|s| *s == ch)
+                    .map(|idx| idx as i8 + 1)?)
+            }
+        };
+
+        if value.abs() <= Self::MAX_DIGIT {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// The symbol for the digit `value`.
+    ///
+    /// # Panics
+    /// If `value` is not a valid digit for this base.
+    pub fn digit_char(value: i8) -> char {
+        assert!(
+            value.abs() <= Self::MAX_DIGIT,
+            "invalid digit for balanced base {}: {}",
+            B,
+            value
+        );
+
+        if value >= 0 {
+            (b'0' + value as u8) as char
+        } else {
+            NEGATIVE_SYMBOLS[(-value - 1) as usize]
+        }
+    }
+
+    fn normalize(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+    }
+}
+
+impl<const B: u32> From<i128> for BalancedRadix<B> {
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// for n in [0, 1, -1, 3, -3, 2022, -2022, i128::MAX, i128::MIN] {
+    ///     assert_eq!(Snafu::from(n).to_decimal(), n);
+    /// }
+    ///
+    /// assert_eq!(Snafu::from(-2022).to_string(), "-2--1=");
+    ///
+    /// for n in [0, -2022, i64::MAX, i64::MIN] {
+    ///     assert_eq!(Snafu::from(n).checked_to_i64(), Some(n));
+    /// }
+    /// ```
+    fn from(value: i128) -> Self {
+        let base = B as i128;
+        let mut digits = Vec::new();
+
+        // use euclidean division so that negative values work, adjusting the
+        // quotient rather than the working value so we can't overflow
+        let mut working = value;
+        while working != 0 {
+            let digit = working.rem_euclid(base);
+            working = working.div_euclid(base);
+
+            if digit > Self::MAX_DIGIT as i128 {
+                digits.push((digit - base) as i8);
+                working += 1;
+            } else {
+                digits.push(digit as i8);
+            }
+        }
+
+        Self { digits }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl<const B: u32> From<$t> for BalancedRadix<B> {
+                fn from(value: $t) -> Self {
+                    Self::from(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl<const B: u32> Display for BalancedRadix<B> {
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// assert_eq!(Snafu::default().to_string(), "0");
+    /// assert_eq!("000".parse::<Snafu>().unwrap().to_string(), "0");
+    /// assert_eq!("-0=".parse::<Snafu>().unwrap().to_decimal(), -27);
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.digits.is_empty() {
+            return "0".fmt(f);
+        }
+
+        let s: String = self.digits().map(Self::digit_char).collect();
+        s.fmt(f)
+    }
+}
+
+impl<const B: u32> FromStr for BalancedRadix<B> {
+    type Err = ParseDigitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut out = Self {
+            digits: s
+                .chars()
+                .rev()
+                .map(|ch| Self::digit_value(ch).ok_or(ParseDigitError { ch, radix: B }))
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        out.normalize();
+
+        Ok(out)
+    }
+}
+
+impl<const B: u32> Add<&BalancedRadix<B>> for &BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    /// Adds digit-by-digit, so the result is not limited by the range of any
+    /// integer type.
+    ///
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// let a: Snafu = "2=".parse().unwrap();
+    /// let b: Snafu = "1-".parse().unwrap();
+    /// assert_eq!((&a + &b).to_decimal(), 8 + 4);
+    /// ```
+    fn add(self, rhs: &BalancedRadix<B>) -> Self::Output {
+        let base = B as i8;
+        let max = BalancedRadix::<B>::MAX_DIGIT;
+        let len = self.digits.len().max(rhs.digits.len());
+        let mut digits = Vec::with_capacity(len + 1);
+        let mut carry = 0;
+
+        for idx in 0..len {
+            let mut sum = carry
+                + self.digits.get(idx).copied().unwrap_or_default()
+                + rhs.digits.get(idx).copied().unwrap_or_default();
+
+            carry = 0;
+            if sum > max {
+                sum -= base;
+                carry = 1;
+            } else if sum < -max {
+                sum += base;
+                carry = -1;
+            }
+
+            digits.push(sum);
+        }
+
+        if carry != 0 {
+            digits.push(carry);
+        }
+
+        // drop leading zeros that may have appeared from cancellation
+        let mut out = BalancedRadix { digits };
+        out.normalize();
+        out
+    }
+}
+
+impl<const B: u32> Add for BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<const B: u32> Neg for &BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    fn neg(self) -> Self::Output {
+        BalancedRadix {
+            digits: self.digits.iter().map(|d| -d).collect(),
+        }
+    }
+}
+
+impl<const B: u32> Neg for BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<const B: u32> Sub<&BalancedRadix<B>> for &BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    fn sub(self, rhs: &BalancedRadix<B>) -> Self::Output {
+        self + &(-rhs)
+    }
+}
+
+impl<const B: u32> Sub for BalancedRadix<B> {
+    type Output = BalancedRadix<B>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl<const B: u32> Sum for BalancedRadix<B> {
+    /// # Examples
+    /// ```
+    /// use aoc_plumbing::radix::Snafu;
+    /// let total: Snafu = ["1=", "2-", "-"].iter().map(|s| s.parse::<Snafu>().unwrap()).sum();
+    /// assert_eq!(total.to_decimal(), 3 + 9 - 1);
+    /// ```
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, n| &acc + &n)
+    }
+}
+
+impl<'a, const B: u32> Sum<&'a BalancedRadix<B>> for BalancedRadix<B> {
+    fn sum<I: Iterator<Item = &'a BalancedRadix<B>>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, n| &acc + n)
+    }
+}
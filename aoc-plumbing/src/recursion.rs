@@ -0,0 +1,60 @@
+//! Keeping deeply recursive solvers from overflowing the stack.
+//!
+//! With the `stacker` feature (on by default), [grow] switches to a fresh,
+//! heap-allocated stack whenever the current one is nearly used up. Without
+//! it, for targets stacker doesn't support, it just calls the function.
+
+/// How much stack has to be left before [grow] allocates more.
+pub const RED_ZONE: usize = 64 * 1024;
+
+/// How much stack [grow] allocates at a time.
+pub const STACK_SIZE: usize = 1024 * 1024;
+
+/// Run `f`, on a new stack if there's not much of the current one left. A
+/// recursive function wraps its recursive calls in this to recurse as deep as
+/// memory allows.
+///
+/// ```
+/// use aoc_plumbing::recursion::grow;
+///
+/// fn depth(n: u64) -> u64 {
+///     if n == 0 {
+///         0
+///     } else {
+///         1 + grow(|| depth(n - 1))
+///     }
+/// }
+///
+/// assert_eq!(depth(10), 10);
+/// ```
+#[inline]
+pub fn grow<R, F: FnOnce() -> R>(f: F) -> R {
+    #[cfg(feature = "stacker")]
+    {
+        stacker::maybe_grow(RED_ZONE, STACK_SIZE, f)
+    }
+
+    #[cfg(not(feature = "stacker"))]
+    {
+        f()
+    }
+}
+
+#[cfg(all(test, feature = "stacker"))]
+mod tests {
+    use super::*;
+
+    // far deeper than the test thread's stack would allow
+    fn sum(n: u64) -> u64 {
+        if n == 0 {
+            0
+        } else {
+            n + grow(|| sum(n - 1))
+        }
+    }
+
+    #[test]
+    fn deep() {
+        assert_eq!(sum(1_000_000), 500_000_500_000);
+    }
+}
@@ -0,0 +1,420 @@
+//! Days registering themselves, so they can be enumerated without a list of
+//! them being maintained anywhere.
+//!
+//! Each day crate calls [register!](crate::register) once, usually via the
+//! `year` of [aoc_problem](crate::aoc_problem). Every day linked
+//! into a binary is then available from [days], as long as the binary uses
+//! something from the day's crate (or from a crate that does, like `aoc`).
+use std::{fmt::Display, time::Instant};
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    timing::peak_rss, AocError, BorrowedProblem, Example, Expected, Phases, Solution,
+    TimedSolution, Timings, Validation,
+};
+
+/// An answer with its type erased, as it's displayed and as it's serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answer {
+    display: String,
+    value: serde_json::Value,
+}
+
+impl Answer {
+    pub fn new<T: Display + Serialize>(answer: &T) -> Result<Self, AocError> {
+        Ok(Self {
+            display: answer.to_string(),
+            value: serde_json::to_value(answer).map_err(|e| AocError::Other(e.into()))?,
+        })
+    }
+
+    /// The answer as json, which is `null` for [NoAnswer](crate::NoAnswer).
+    pub fn value(&self) -> &serde_json::Value {
+        &self.value
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+/// A parsed problem with its type erased, so it can be solved without
+/// knowing which day it is.
+pub trait ErasedProblem {
+    fn part_one(&mut self) -> Result<Answer, AocError>;
+    fn part_two(&mut self) -> Result<Answer, AocError>;
+    /// See [Problem::summary](crate::Problem::summary).
+    fn summary(&self) -> Vec<(&'static str, String)>;
+}
+
+/// What `T` was parsed into, along with how to solve it.
+struct Parsed<'a, T: BorrowedProblem>(T::Instance<'a>);
+
+impl<'a, T> ErasedProblem for Parsed<'a, T>
+where
+    T: BorrowedProblem,
+    T::ProblemError: Into<AocError>,
+{
+    fn part_one(&mut self) -> Result<Answer, AocError> {
+        Answer::new(&T::part_one(&mut self.0).map_err(Into::into)?)
+    }
+
+    fn part_two(&mut self) -> Result<Answer, AocError> {
+        Answer::new(&T::part_two(&mut self.0).map_err(Into::into)?)
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        T::summary(&self.0)
+    }
+}
+
+/// What `T` was parsed into by its [Phases].
+struct Phased<T: Phases>(T::Parsed);
+
+impl<T> ErasedProblem for Phased<T>
+where
+    T: Phases,
+    T::ProblemError: Into<AocError>,
+{
+    fn part_one(&mut self) -> Result<Answer, AocError> {
+        Answer::new(&<T as Phases>::part_one(&mut self.0).map_err(Into::into)?)
+    }
+
+    fn part_two(&mut self) -> Result<Answer, AocError> {
+        Answer::new(&<T as Phases>::part_two(&mut self.0).map_err(Into::into)?)
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        <T as Phases>::summary(&self.0)
+    }
+}
+
+/// A registered day.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: usize,
+    pub day: usize,
+    pub title: &'static str,
+    /// The name of the crate implementing the day, e.g. `calorie-counting`.
+    pub crate_name: &'static str,
+    pub readme: &'static str,
+    pub examples: &'static [Example],
+    pub expected: Option<Expected>,
+    /// See [Problem::LINKED_PARTS](crate::Problem::LINKED_PARTS).
+    pub linked_parts: bool,
+    /// Parse the input for the day.
+    pub instance: fn(&str) -> Result<Box<dyn ErasedProblem + '_>, AocError>,
+}
+
+impl Day {
+    /// Use [register!](crate::register) instead of calling this directly.
+    pub const fn of<T>(year: usize, crate_name: &'static str) -> Self
+    where
+        T: BorrowedProblem + 'static,
+        T::ProblemError: Into<AocError>,
+    {
+        Self {
+            year,
+            day: T::DAY,
+            title: T::TITLE,
+            crate_name,
+            readme: T::README,
+            examples: T::EXAMPLES,
+            expected: T::EXPECTED,
+            linked_parts: T::LINKED_PARTS,
+            instance: instance::<T>,
+        }
+    }
+
+    /// Like [Day::of], but solved through the day's [Phases], so parsing is
+    /// [Phases::parse]. Use `register!(T, year, phases)` instead of calling
+    /// this directly.
+    pub const fn phased<T>(year: usize, crate_name: &'static str) -> Self
+    where
+        T: Phases + 'static,
+        T::ProblemError: Into<AocError>,
+    {
+        Self {
+            instance: phased_instance::<T>,
+            ..Self::of::<T>(year, crate_name)
+        }
+    }
+
+    /// Parse and solve both parts, with the answers as they're displayed.
+    pub fn solve(&self, input: &str) -> Result<Solution<String, String>, AocError> {
+        let mut inst = (self.instance)(input)?;
+        Ok(Solution::new(
+            inst.part_one()?.to_string(),
+            inst.part_two()?.to_string(),
+        ))
+    }
+
+    /// Solve both parts, timing parsing and each part separately, like
+    /// [Problem::solve_timed](crate::Problem::solve_timed).
+    pub fn solve_timed(&self, input: &str) -> Result<TimedSolution<Answer, Answer>, AocError> {
+        let start = Instant::now();
+        let mut inst = (self.instance)(input)?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part_one = inst.part_one()?;
+        let part_one_time = start.elapsed();
+
+        let start = Instant::now();
+        let part_two = inst.part_two()?;
+        let part_two_time = start.elapsed();
+
+        Ok(TimedSolution {
+            solution: Solution::new(part_one, part_two),
+            timings: Timings {
+                parse,
+                part_one: part_one_time,
+                part_two: part_two_time,
+            },
+            peak_rss: peak_rss(),
+            metadata: None,
+        })
+    }
+
+    /// Solve and check the answers against the expected ones, if any.
+    pub fn validate(&self, input: &str) -> Result<Validation, AocError> {
+        Ok(self.expected.unwrap_or_default().check(&self.solve(input)?))
+    }
+
+    /// The same label as [Problem::problem_label](crate::Problem::problem_label), e.g. `001 calorie counting`.
+    pub fn label(&self) -> String {
+        format!("{:03} {}", self.day, self.title)
+    }
+
+    /// The directory of the day's crate, relative to the workspace root.
+    pub fn dir(&self) -> String {
+        format!("day-{:03}-{}", self.day, self.crate_name)
+    }
+}
+
+fn instance<T>(input: &str) -> Result<Box<dyn ErasedProblem + '_>, AocError>
+where
+    T: BorrowedProblem + 'static,
+    T::ProblemError: Into<AocError>,
+{
+    let inst = T::instance(input).map_err(Into::into)?;
+    Ok(Box::new(Parsed::<T>(inst)))
+}
+
+fn phased_instance<T>(input: &str) -> Result<Box<dyn ErasedProblem + '_>, AocError>
+where
+    T: Phases + 'static,
+    T::ProblemError: Into<AocError>,
+{
+    let parsed = T::parse(input).map_err(Into::into)?;
+    Ok(Box::new(Phased::<T>(parsed)))
+}
+
+inventory::collect!(Day);
+
+/// Every registered day, ordered by year then day.
+pub fn days() -> Vec<&'static Day> {
+    let mut days = inventory::iter::<Day>.into_iter().collect::<Vec<_>>();
+    days.sort_by_key(|d| (d.year, d.day));
+    days
+}
+
+/// The registered `day` of `year`, if any.
+pub fn find(year: usize, day: usize) -> Option<&'static Day> {
+    inventory::iter::<Day>
+        .into_iter()
+        .find(|d| d.year == year && d.day == day)
+}
+
+/// Register a [Problem](crate::Problem) (or a [BorrowedProblem]) for the given
+/// year, optionally to be solved through its [Phases].
+///
+/// ```ignore
+/// aoc_plumbing::register!(CalorieCounting, 2022);
+/// aoc_plumbing::register!(Totals, 2022, phases);
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($problem:ty, $year:literal) => {
+        $crate::inventory::submit! {
+            $crate::registry::Day::of::<$problem>($year, env!("CARGO_PKG_NAME"))
+        }
+    };
+    ($problem:ty, $year:literal, phases) => {
+        $crate::inventory::submit! {
+            $crate::registry::Day::phased::<$problem>($year, env!("CARGO_PKG_NAME"))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::Problem;
+
+    use super::*;
+
+    struct Doubler(usize);
+
+    impl FromStr for Doubler {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(s.trim().parse()?))
+        }
+    }
+
+    impl Problem for Doubler {
+        const DAY: usize = 26;
+        const TITLE: &'static str = "doubler";
+        const README: &'static str = "";
+        const EXPECTED: Option<Expected> = Some(Expected::new("6", "12"));
+
+        type ProblemError = anyhow::Error;
+        type P1 = usize;
+        type P2 = usize;
+
+        fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+            Ok(self.0 * 2)
+        }
+
+        fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+            Ok(self.0 * 4)
+        }
+    }
+
+    crate::register!(Doubler, 1999);
+
+    struct Shouter;
+
+    impl BorrowedProblem for Shouter {
+        const DAY: usize = 27;
+        const TITLE: &'static str = "shouter";
+        const README: &'static str = "";
+
+        type ProblemError = anyhow::Error;
+        type Instance<'a> = Vec<&'a str>;
+        type P1 = String;
+        type P2 = usize;
+
+        fn instance(raw_input: &str) -> Result<Self::Instance<'_>, Self::ProblemError> {
+            Ok(raw_input.split_whitespace().collect())
+        }
+
+        fn part_one(inst: &mut Self::Instance<'_>) -> Result<Self::P1, Self::ProblemError> {
+            Ok(inst.join(" ").to_uppercase())
+        }
+
+        fn part_two(inst: &mut Self::Instance<'_>) -> Result<Self::P2, Self::ProblemError> {
+            Ok(inst.len())
+        }
+    }
+
+    crate::register!(Shouter, 1999);
+
+    struct Summer(Vec<usize>);
+
+    impl FromStr for Summer {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(
+                s.split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+    }
+
+    impl Problem for Summer {
+        const DAY: usize = 28;
+        const TITLE: &'static str = "summer";
+        const README: &'static str = "";
+
+        type ProblemError = anyhow::Error;
+        type P1 = usize;
+        type P2 = usize;
+
+        fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+            Ok(self.0.iter().sum())
+        }
+
+        fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+            Ok(self.0.len())
+        }
+    }
+
+    // only the phases keep the total
+    impl Phases for Summer {
+        type Parsed = (usize, usize);
+
+        fn parse(raw_input: &str) -> Result<Self::Parsed, Self::ProblemError> {
+            let mut summer = Self::from_str(raw_input)?;
+            Ok((summer.part_one()?, summer.part_two()?))
+        }
+
+        fn part_one(parsed: &mut Self::Parsed) -> Result<Self::P1, Self::ProblemError> {
+            Ok(parsed.0)
+        }
+
+        fn part_two(parsed: &mut Self::Parsed) -> Result<Self::P2, Self::ProblemError> {
+            Ok(parsed.1)
+        }
+
+        fn summary(parsed: &Self::Parsed) -> Vec<(&'static str, String)> {
+            vec![("total", parsed.0.to_string())]
+        }
+    }
+
+    crate::register!(Summer, 1999, phases);
+
+    #[test]
+    fn registration() {
+        let day = find(1999, 26).unwrap();
+        assert_eq!(day.crate_name, "aoc-plumbing");
+        assert_eq!(day.label(), "026 doubler");
+        assert_eq!(day.dir(), "day-026-aoc-plumbing");
+        assert_eq!(
+            day.solve("3").unwrap(),
+            Solution::new("6".into(), "12".into())
+        );
+        assert!(day.solve("x").is_err());
+        assert_eq!(day.validate("3").unwrap().failures(), 0);
+        assert_eq!(day.validate("4").unwrap().failures(), 2);
+        assert!(!day.linked_parts);
+
+        let timed = day.solve_timed("3").unwrap();
+        assert_eq!(timed.solution.part_one.to_string(), "6");
+        assert_eq!(timed.solution.part_two.value(), &serde_json::json!(12));
+
+        let day = find(1999, 27).unwrap();
+        assert_eq!(day.expected, None);
+        assert_eq!(
+            day.solve("hello  world").unwrap(),
+            Solution::new("HELLO WORLD".into(), "2".into())
+        );
+
+        let day = find(1999, 28).unwrap();
+        assert_eq!(
+            day.solve("1 2 3").unwrap(),
+            Solution::new("6".into(), "3".into())
+        );
+        assert_eq!(
+            (day.instance)("1 2 3").unwrap().summary(),
+            vec![("total", "6".to_string())]
+        );
+
+        assert!(days().iter().any(|d| d.year == 1999 && d.day == 26));
+        assert!(find(1999, 25).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+//! Timing a solution, so every frontend reports the same measurements.
+use std::{fmt::Display, time::Duration};
+
+use serde::{Serialize, Serializer};
+
+use crate::{metadata::Metadata, Solution};
+
+/// Serialize a duration as u64 nanoseconds, which is plenty, as not every
+/// format supports u128. For use with `#[serde(serialize_with = "...")]`.
+pub fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+/// Wall times for the various stages of a solution.
+///
+/// # Usage
+///
+/// ```
+/// use std::time::Duration;
+/// use aoc_plumbing::Timings;
+/// let timings = Timings {
+///     parse: Duration::from_nanos(1),
+///     part_one: Duration::from_nanos(20),
+///     part_two: Duration::from_nanos(300),
+/// };
+///
+/// assert_eq!(timings.total(), Duration::from_nanos(321));
+/// assert_eq!(
+///     serde_json::to_string(&timings).unwrap(),
+///     "{\"parse_ns\":1,\"part_one_ns\":20,\"part_two_ns\":300}"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize)]
+pub struct Timings {
+    #[serde(rename = "parse_ns", serialize_with = "as_nanos")]
+    pub parse: Duration,
+    #[serde(rename = "part_one_ns", serialize_with = "as_nanos")]
+    pub part_one: Duration,
+    #[serde(rename = "part_two_ns", serialize_with = "as_nanos")]
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+/// A [Solution] along with how long it took, from
+/// [Problem::solve_timed](crate::Problem::solve_timed).
+///
+/// The answers are serialized alongside the timings, e.g.
+/// `{"part_one":24000,"part_two":45000,"peak_rss":...,"timings":{...}}`.
+/// Metadata belongs on the [TimedSolution] rather than its [Solution], so it
+/// comes after the timings.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TimedSolution<T, G>
+where
+    T: Display + Serialize + PartialEq,
+    G: Display + Serialize + PartialEq,
+{
+    #[serde(flatten)]
+    pub solution: Solution<T, G>,
+    /// The peak resident set size in bytes, where it can be determined. This
+    /// is for the whole process so far, not just this solution, so it's only
+    /// meaningful when solutions are run one at a time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_rss: Option<u64>,
+    // last, as toml needs tables after the plain values
+    pub timings: Timings,
+    /// How the solution came to be, if it's being archived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl<T, G> TimedSolution<T, G>
+where
+    T: Display + Serialize + PartialEq,
+    G: Display + Serialize + PartialEq,
+{
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+/// The peak resident set size of the process in bytes, which is only known on
+/// linux.
+pub fn peak_rss() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rss() {
+        if cfg!(target_os = "linux") {
+            assert!(peak_rss().unwrap() > 0);
+        }
+    }
+}
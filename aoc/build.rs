@@ -0,0 +1,18 @@
+//! Link every day `aoc` depends on, so they all register themselves without
+//! being listed anywhere other than `Cargo.toml`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let manifest = fs::read_to_string("Cargo.toml").expect("Could not read Cargo.toml");
+    let days = manifest
+        .lines()
+        .filter(|line| line.contains("path = \"../day-"))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, _)| format!("extern crate {} as _;\n", name.trim().replace('-', "_")))
+        .collect::<String>();
+
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("days.rs");
+    fs::write(out, days).expect("Could not write the list of days");
+}
@@ -1,30 +1,9 @@
-//! This just re-exports the various sub crates here.
+//! Every day, for the external bencher and the cli.
 //!
-//! The intent is to provide a nice import for the external bencher.
+//! Depending on this crate links every day it depends on, so they can all be
+//! enumerated with [days] without naming them. The `extern crate` for each
+//! is generated from `Cargo.toml` by `build.rs`.
 
-pub use beacon_exclusion_zone::BeaconExclusionZone;
-pub use blizzard_basin::BlizzardBasin;
-pub use boiling_boulders::BoilingBoulders;
-pub use calorie_counting::CalorieCounting;
-pub use camp_cleanup::CampCleanup;
-pub use cathode_ray_tube::CathodeRayTube;
-pub use distress_signal::DistressSignal;
-pub use full_of_hot_air::FullOfHotAir;
-pub use grove_positioning_system::GrovePositioningSystem;
-pub use hill_climbing_algorithm::HillClimbingAlgorithm;
-pub use monkey_in_the_middle::MonkeyInTheMiddle;
-pub use monkey_map::MonkeyMap;
-pub use monkey_math::MonkeyMath;
-pub use no_space_left_on_device::NoSpaceLeftOnDevice;
-pub use not_enough_minerals::NotEnoughMinerals;
-pub use proboscidea_volcanium::ProboscideaVolcanium;
-pub use pyroclastic_flow::PyroclasticFlow;
-pub use regolith_reservoir::RegolithReservoir;
-pub use rock_paper_scissors::RockPaperScissors;
-pub use rope_bridge::RopeBridge;
-pub use rucksack_reorganization::RucksackReorganization;
-pub use supply_stacks::SupplyStacks;
-pub use treetop_tree_house::TreetopTreeHouse;
-pub use tuning_trouble::TuningTrouble;
-pub use unstable_diffusion::UnstableDiffusion;
-// import_marker
+pub use aoc_plumbing::registry::{days, find, Day};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
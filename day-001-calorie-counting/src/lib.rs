@@ -1,8 +1,16 @@
-use std::str::FromStr;
+use std::{
+    io::{BufRead, BufReader, Read},
+    str::FromStr,
+};
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem,
+    parsing::{blocks, separated_lines, unsigned},
+    AocError, Example, Problem,
+};
 use itertools::Itertools;
+use nom::combinator::all_consuming;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct Elf {
@@ -24,27 +32,40 @@ impl FromStr for CalorieCounting {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<_> = s.trim().lines().collect();
+        let (_, blocks) = all_consuming(blocks(separated_lines(unsigned)))(s.trim())
+            .map_err(|e| AocError::from_nom(s, e))?;
 
-        let mut elves = Vec::default();
-
-        for values in lines.split(|l| l.is_empty()) {
-            let mut calories = 0;
-            for val in values {
-                calories += val.trim().parse::<usize>()?;
-            }
-
-            elves.push(Elf { calories })
-        }
+        let elves = blocks
+            .into_iter()
+            .map(|values| Elf {
+                calories: values.iter().sum::<u64>() as usize,
+            })
+            .collect();
 
         Ok(Self { elves })
     }
 }
 
+const EXAMPLE: &str = "
+            1000
+            2000
+            3000
+
+            4000
+
+            5000
+            6000
+
+            7000
+            8000
+            9000
+
+            10000
+            ";
+
+#[aoc_problem(day = 1, title = "calorie counting", year = 2022, full_dataset = (69795, 208437))]
 impl Problem for CalorieCounting {
-    const DAY: usize = 1;
-    const TITLE: &'static str = "calorie counting";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "24000", "45000")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -67,41 +88,33 @@ impl Problem for CalorieCounting {
             .take(3)
             .sum())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
+    // the elves are totalled as they're read, so the input never needs to be
+    // held in memory
+    fn instance_reader<R: Read>(reader: R) -> Result<Self, Self::ProblemError> {
+        let mut elves = Vec::default();
+        let mut calories = None;
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            match line.trim() {
+                "" => {
+                    if let Some(calories) = calories.take() {
+                        elves.push(Elf { calories });
+                    }
+                }
+                val => *calories.get_or_insert(0) += val.parse::<usize>()?,
+            }
+        }
 
-    use super::*;
+        if let Some(calories) = calories {
+            elves.push(Elf { calories });
+        }
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = CalorieCounting::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(69795, 208437));
+        Ok(Self { elves })
     }
 
-    #[test]
-    fn example() {
-        let input = "
-            1000
-            2000
-            3000
-
-            4000
-
-            5000
-            6000
-
-            7000
-            8000
-            9000
-
-            10000
-            ";
-        let solution = CalorieCounting::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(24000, 45000));
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![("elves", self.elves.len().to_string())]
     }
 }
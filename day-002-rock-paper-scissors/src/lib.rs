@@ -1,7 +1,7 @@
-use std::str::FromStr;
+use std::{marker::PhantomData, str::FromStr};
 
 use anyhow::{anyhow, bail};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, Example, Problem};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Outcome {
@@ -10,6 +10,16 @@ pub enum Outcome {
     Draw,
 }
 
+impl Outcome {
+    pub fn score(&self) -> usize {
+        match self {
+            Self::Win => 6,
+            Self::Draw => 3,
+            Self::Lose => 0,
+        }
+    }
+}
+
 impl FromStr for Outcome {
     type Err = anyhow::Error;
 
@@ -23,6 +33,54 @@ impl FromStr for Outcome {
     }
 }
 
+/// A set of moves along with the relation describing which moves beat which.
+///
+/// The outcome of any pair of moves, as well as which move achieves a desired
+/// outcome, are derived from the `BEATS` table, so adding a move only requires
+/// extending the tables.
+pub trait MoveSet: Copy + Eq + FromStr<Err = anyhow::Error> + 'static {
+    /// Every move in the set, in increasing shape score order.
+    const MOVES: &'static [Self];
+
+    /// `BEATS[i]` is the list of moves that `MOVES[i]` defeats.
+    const BEATS: &'static [&'static [Self]];
+
+    /// The position of this move in `MOVES`.
+    fn index(&self) -> usize;
+
+    /// Interpret the second column of a round as a desired outcome.
+    fn parse_desire(s: &str) -> Result<Outcome, anyhow::Error> {
+        Outcome::from_str(s)
+    }
+
+    fn score(&self) -> usize {
+        self.index() + 1
+    }
+
+    fn beats(&self, other: &Self) -> bool {
+        Self::BEATS[self.index()].contains(other)
+    }
+
+    fn evaluate(&self, other: &Self) -> Outcome {
+        if self == other {
+            Outcome::Draw
+        } else if self.beats(other) {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// The first move (in `MOVES` order) that results in `desire` when played
+    /// against this move.
+    fn match_desire(&self, desire: &Outcome) -> Self {
+        *Self::MOVES
+            .iter()
+            .find(|m| m.evaluate(self) == *desire)
+            .expect("move set is missing a move for the desired outcome")
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Choice {
     Rock,
@@ -30,53 +88,12 @@ pub enum Choice {
     Scissors,
 }
 
-impl Choice {
-    pub fn score(&self) -> usize {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
-        }
-    }
+impl MoveSet for Choice {
+    const MOVES: &'static [Self] = &[Self::Rock, Self::Paper, Self::Scissors];
+    const BEATS: &'static [&'static [Self]] = &[&[Self::Scissors], &[Self::Rock], &[Self::Paper]];
 
-    pub fn match_desire(&self, desire: &Outcome) -> Self {
-        match self {
-            Self::Rock => match desire {
-                Outcome::Win => Self::Paper,
-                Outcome::Lose => Self::Scissors,
-                Outcome::Draw => Self::Rock,
-            },
-            Self::Paper => match desire {
-                Outcome::Win => Self::Scissors,
-                Outcome::Lose => Self::Rock,
-                Outcome::Draw => Self::Paper,
-            },
-            Self::Scissors => match desire {
-                Outcome::Win => Self::Rock,
-                Outcome::Lose => Self::Paper,
-                Outcome::Draw => Self::Scissors,
-            },
-        }
-    }
-
-    pub fn evaluate(&self, other: &Self) -> Outcome {
-        match self {
-            Self::Rock => match other {
-                Self::Rock => Outcome::Draw,
-                Self::Paper => Outcome::Lose,
-                Self::Scissors => Outcome::Win,
-            },
-            Self::Paper => match other {
-                Self::Rock => Outcome::Win,
-                Self::Paper => Outcome::Draw,
-                Self::Scissors => Outcome::Lose,
-            },
-            Self::Scissors => match other {
-                Self::Rock => Outcome::Lose,
-                Self::Paper => Outcome::Win,
-                Self::Scissors => Outcome::Draw,
-            },
-        }
+    fn index(&self) -> usize {
+        *self as usize
     }
 }
 
@@ -93,110 +110,542 @@ impl FromStr for Choice {
     }
 }
 
+/// The five move Rock-Paper-Scissors-Lizard-Spock variant.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct Round {
-    other: Choice,
-    you: Choice,
+pub enum ExtendedChoice {
+    Rock,
+    Paper,
+    Scissors,
+    Lizard,
+    Spock,
+}
+
+impl MoveSet for ExtendedChoice {
+    const MOVES: &'static [Self] = &[
+        Self::Rock,
+        Self::Paper,
+        Self::Scissors,
+        Self::Lizard,
+        Self::Spock,
+    ];
+    const BEATS: &'static [&'static [Self]] = &[
+        &[Self::Scissors, Self::Lizard],
+        &[Self::Rock, Self::Spock],
+        &[Self::Paper, Self::Lizard],
+        &[Self::Paper, Self::Spock],
+        &[Self::Rock, Self::Scissors],
+    ];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Every move beats two moves and loses to two moves, so `V` and `W` both
+    /// mean lose, `X` means draw, and `Y` and `Z` both mean win.
+    fn parse_desire(s: &str) -> Result<Outcome, anyhow::Error> {
+        match s {
+            "V" | "W" => Ok(Outcome::Lose),
+            "X" => Ok(Outcome::Draw),
+            "Y" | "Z" => Ok(Outcome::Win),
+            _ => bail!("Invalid input for desire: {}", s),
+        }
+    }
+}
+
+impl FromStr for ExtendedChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "A" | "V" => Ok(Self::Rock),
+            "B" | "W" => Ok(Self::Paper),
+            "C" | "X" => Ok(Self::Scissors),
+            "D" | "Y" => Ok(Self::Lizard),
+            "E" | "Z" => Ok(Self::Spock),
+            _ => bail!("Invalid choice {}", value),
+        }
+    }
+}
+
+/// The points awarded for the shape played and the outcome of a round.
+///
+/// This allows house-rule variants to be evaluated against the same rounds.
+/// A table can be parsed from its shape scores, in `MoveSet::MOVES` order,
+/// followed by the scores for losing, drawing, and winning, as in
+/// `"1 2 3 / 0 3 6"`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Scoring<M: MoveSet = Choice> {
+    shapes: Vec<usize>,
+    pub win: usize,
+    pub draw: usize,
+    pub lose: usize,
+    moves: PhantomData<M>,
+}
+
+impl<M: MoveSet> Default for Scoring<M> {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl<M: MoveSet> Scoring<M> {
+    /// A table with the given score for each shape, indexed by
+    /// `MoveSet::index`, which must have a score for every move.
+    pub fn new(
+        shapes: Vec<usize>,
+        win: usize,
+        draw: usize,
+        lose: usize,
+    ) -> Result<Self, anyhow::Error> {
+        if shapes.len() != M::MOVES.len() {
+            bail!(
+                "Scoring has {} shape scores but there are {} moves",
+                shapes.len(),
+                M::MOVES.len()
+            );
+        }
+
+        Ok(Self {
+            shapes,
+            win,
+            draw,
+            lose,
+            moves: PhantomData,
+        })
+    }
+
+    /// The puzzle's scoring for the move set: shapes are worth their
+    /// position in `MOVES` plus one, and outcomes are worth 6/3/0.
+    pub fn standard() -> Self {
+        Self {
+            shapes: M::MOVES.iter().map(|m| m.score()).collect(),
+            win: 6,
+            draw: 3,
+            lose: 0,
+            moves: PhantomData,
+        }
+    }
+
+    /// The score for each shape, indexed by `MoveSet::index`.
+    pub fn shapes(&self) -> &[usize] {
+        &self.shapes
+    }
+
+    pub fn shape(&self, choice: &M) -> usize {
+        self.shapes[choice.index()]
+    }
+
+    pub fn outcome(&self, outcome: &Outcome) -> usize {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Lose => self.lose,
+        }
+    }
+
+    /// The score for playing `choice` against `other`.
+    pub fn play(&self, choice: &M, other: &M) -> usize {
+        self.shape(choice) + self.outcome(&choice.evaluate(other))
+    }
+}
+
+impl<M: MoveSet> FromStr for Scoring<M> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (shapes, outcomes) = s
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid scoring: {}", s))?;
+        let shapes = shapes
+            .split_whitespace()
+            .map(|v| v.parse())
+            .collect::<Result<Vec<usize>, _>>()?;
+
+        match outcomes
+            .split_whitespace()
+            .map(|v| v.parse())
+            .collect::<Result<Vec<usize>, _>>()?[..]
+        {
+            [lose, draw, win] => Self::new(shapes, win, draw, lose),
+            _ => bail!("Invalid outcome scores: {}", outcomes.trim()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Round<M: MoveSet = Choice> {
+    other: M,
+    you: M,
     desire: Outcome,
 }
 
-impl Round {
+impl<M: MoveSet> Round<M> {
+    pub fn other(&self) -> M {
+        self.other
+    }
+
+    pub fn you(&self) -> M {
+        self.you
+    }
+
+    pub fn desire(&self) -> Outcome {
+        self.desire
+    }
+
     pub fn score(&self) -> usize {
-        let score = self.you.score();
+        self.score_choice(self.you)
+    }
 
-        match self.you.evaluate(&self.other) {
-            Outcome::Win => 6 + score,
-            Outcome::Draw => 3 + score,
-            Outcome::Lose => score,
-        }
+    /// The score for playing `choice` against the opponent's move this round.
+    pub fn score_choice(&self, choice: M) -> usize {
+        choice.score() + choice.evaluate(&self.other).score()
     }
 
     pub fn score_desired(&self) -> usize {
-        let score = self.other.match_desire(&self.desire).score();
-        match self.desire {
-            Outcome::Win => 6 + score,
-            Outcome::Draw => 3 + score,
-            Outcome::Lose => score,
-        }
+        self.other.match_desire(&self.desire).score() + self.desire.score()
+    }
+
+    /// The score for playing `choice` this round under the given `scoring`.
+    pub fn score_choice_with(&self, choice: M, scoring: &Scoring<M>) -> usize {
+        scoring.play(&choice, &self.other)
     }
 }
 
-impl FromStr for Round {
+impl<M: MoveSet> FromStr for Round<M> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut iter = s.trim().split(' ');
-        let other = Choice::from_str(iter.next().ok_or_else(|| anyhow!("invalid input: {}", s))?)?;
+        let other = M::from_str(iter.next().ok_or_else(|| anyhow!("invalid input: {}", s))?)?;
         let second = iter.next().ok_or_else(|| anyhow!("invalid input: {}", s))?;
-        let you = Choice::from_str(second)?;
-        let desire = Outcome::from_str(second)?;
+        let you = M::from_str(second)?;
+        let desire = M::parse_desire(second)?;
 
         Ok(Self { other, you, desire })
     }
 }
 
+/// Something that can pick a move given the information in a round.
+pub trait Strategy<M: MoveSet = Choice> {
+    fn choose(&self, round: &Round<M>) -> M;
+}
+
+/// Always play the same move.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Fixed<M: MoveSet = Choice>(pub M);
+
+impl<M: MoveSet> Strategy<M> for Fixed<M> {
+    fn choose(&self, _round: &Round<M>) -> M {
+        self.0
+    }
+}
+
+/// Play whatever the opponent is going to play.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Mimic;
+
+impl<M: MoveSet> Strategy<M> for Mimic {
+    fn choose(&self, round: &Round<M>) -> M {
+        round.other
+    }
+}
+
+/// Treat the second column as the move to play (part one).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct FollowGuide;
+
+impl<M: MoveSet> Strategy<M> for FollowGuide {
+    fn choose(&self, round: &Round<M>) -> M {
+        round.you
+    }
+}
+
+/// Treat the second column as the desired outcome (part two).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DesiredOutcome;
+
+impl<M: MoveSet> Strategy<M> for DesiredOutcome {
+    fn choose(&self, round: &Round<M>) -> M {
+        round.other.match_desire(&round.desire)
+    }
+}
+
+impl<M, F> Strategy<M> for F
+where
+    M: MoveSet,
+    F: Fn(&Round<M>) -> M,
+{
+    fn choose(&self, round: &Round<M>) -> M {
+        self(round)
+    }
+}
+
+/// The result of two strategies playing every round against each other.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct MatchResult {
+    pub first: usize,
+    pub second: usize,
+    pub first_score: usize,
+    pub second_score: usize,
+    pub first_wins: usize,
+    pub second_wins: usize,
+    pub draws: usize,
+}
+
+impl MatchResult {
+    /// The index of the winning entrant, or `None` if the match was a draw.
+    pub fn winner(&self) -> Option<usize> {
+        match self.first_score.cmp(&self.second_score) {
+            std::cmp::Ordering::Greater => Some(self.first),
+            std::cmp::Ordering::Less => Some(self.second),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+/// The full set of results from a round-robin tournament.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Standings {
+    pub names: Vec<String>,
+    pub matches: Vec<MatchResult>,
+    pub totals: Vec<usize>,
+}
+
+impl Standings {
+    /// Entrant names and total scores, highest score first.
+    pub fn ranked(&self) -> Vec<(&str, usize)> {
+        let mut ranked: Vec<_> = self
+            .names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(self.totals.iter().copied())
+            .collect();
+        ranked.sort_by_key(|r| std::cmp::Reverse(r.1));
+        ranked
+    }
+}
+
+/// A round-robin tournament where every entrant plays every other entrant
+/// over the same set of rounds.
+///
+/// Each entrant sees the same round information, so the opponent column acts
+/// as the shared "guide" both strategies may react to.
+pub struct Tournament<'a, M: MoveSet = Choice> {
+    entrants: Vec<(String, Box<dyn Strategy<M> + 'a>)>,
+}
+
+impl<'a, M: MoveSet> Default for Tournament<'a, M> {
+    fn default() -> Self {
+        Self {
+            entrants: Vec::default(),
+        }
+    }
+}
+
+impl<'a, M: MoveSet> Tournament<'a, M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enter(&mut self, name: impl Into<String>, strategy: impl Strategy<M> + 'a) -> &mut Self {
+        self.entrants.push((name.into(), Box::new(strategy)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entrants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entrants.is_empty()
+    }
+
+    pub fn run(&self, rounds: &[Round<M>]) -> Standings {
+        let mut matches = Vec::new();
+        let mut totals = vec![0; self.entrants.len()];
+
+        for first in 0..self.entrants.len() {
+            for second in (first + 1)..self.entrants.len() {
+                let mut result = MatchResult {
+                    first,
+                    second,
+                    ..MatchResult::default()
+                };
+
+                for round in rounds {
+                    let a = self.entrants[first].1.choose(round);
+                    let b = self.entrants[second].1.choose(round);
+
+                    let outcome = a.evaluate(&b);
+                    match outcome {
+                        Outcome::Win => result.first_wins += 1,
+                        Outcome::Lose => result.second_wins += 1,
+                        Outcome::Draw => result.draws += 1,
+                    }
+
+                    result.first_score += a.score() + outcome.score();
+                    result.second_score += b.score() + b.evaluate(&a).score();
+                }
+
+                totals[first] += result.first_score;
+                totals[second] += result.second_score;
+                matches.push(result);
+            }
+        }
+
+        Standings {
+            names: self.entrants.iter().map(|(n, _)| n.clone()).collect(),
+            matches,
+            totals,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct RockPaperScissors {
-    rounds: Vec<Round>,
+pub struct RockPaperScissors<M: MoveSet = Choice> {
+    rounds: Vec<Round<M>>,
+}
+
+/// The same game, played with the five move variant.
+pub type RockPaperScissorsLizardSpock = RockPaperScissors<ExtendedChoice>;
+
+impl<M: MoveSet> RockPaperScissors<M> {
+    pub fn rounds(&self) -> &[Round<M>] {
+        &self.rounds
+    }
+
+    /// The total score from playing `strategy` against the opponent column.
+    pub fn score_with(&self, strategy: &impl Strategy<M>) -> usize {
+        self.rounds
+            .iter()
+            .map(|r| r.score_choice(strategy.choose(r)))
+            .sum()
+    }
+
+    /// The total score from playing `strategy` under an alternate `scoring`.
+    pub fn score_with_scoring(&self, strategy: &impl Strategy<M>, scoring: &Scoring<M>) -> usize {
+        self.rounds
+            .iter()
+            .map(|r| r.score_choice_with(strategy.choose(r), scoring))
+            .sum()
+    }
+
+    /// The running total score after each round when playing `strategy`.
+    pub fn timeline_with<'a, S: Strategy<M>>(
+        &'a self,
+        strategy: &'a S,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.rounds.iter().scan(0, move |total, r| {
+            *total += r.score_choice(strategy.choose(r));
+            Some(*total)
+        })
+    }
+
+    /// The running total scores after each round, as `(part one, part two)`
+    /// interpretations of the second column.
+    pub fn timeline(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.rounds.iter().scan((0, 0), |totals, r| {
+            totals.0 += r.score();
+            totals.1 += r.score_desired();
+            Some(*totals)
+        })
+    }
+
+    /// Run a round-robin tournament between strategies over these rounds.
+    pub fn tournament(&self, tournament: &Tournament<M>) -> Standings {
+        tournament.run(&self.rounds)
+    }
 }
 
-impl FromStr for RockPaperScissors {
+impl<M: MoveSet> FromStr for RockPaperScissors<M> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rounds = s
-            .trim()
-            .lines()
-            .map(|l| Round::from_str(l))
-            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<M: MoveSet> RockPaperScissors<M> {
+    /// Parse rounds by reading the fixed `A X` line layout directly from the
+    /// input bytes, without splitting or allocating per line.
+    ///
+    /// Surrounding whitespace on each line is ignored, as are blank lines.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, anyhow::Error> {
+        let mut rounds = Vec::with_capacity(input.len() / 4 + 1);
+
+        for (idx, line) in input.split(|b| *b == b'\n').enumerate() {
+            match trim_bytes(line) {
+                [] => continue,
+                [other, b' ', second] => {
+                    let second = byte_str(second)?;
+                    rounds.push(Round {
+                        other: M::from_str(byte_str(other)?)?,
+                        you: M::from_str(second)?,
+                        desire: M::parse_desire(second)?,
+                    });
+                }
+                line => bail!(
+                    "Invalid round on line {}: {:?}",
+                    idx + 1,
+                    String::from_utf8_lossy(line)
+                ),
+            }
+        }
 
         Ok(Self { rounds })
     }
 }
 
+fn trim_bytes(mut bytes: &[u8]) -> &[u8] {
+    while let [first, rest @ ..] = bytes {
+        if !first.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+
+    while let [rest @ .., last] = bytes {
+        if !last.is_ascii_whitespace() {
+            break;
+        }
+        bytes = rest;
+    }
+
+    bytes
+}
+
+fn byte_str(byte: &u8) -> Result<&str, anyhow::Error> {
+    std::str::from_utf8(std::slice::from_ref(byte))
+        .map_err(|_| anyhow!("Invalid byte in input: {:#x}", byte))
+}
+
+const EXAMPLE: &str = "
+            A Y
+            B X
+            C Z
+            ";
+
+#[aoc_problem(day = 2, title = "rock paper scissors", year = 2022, full_dataset = (10404, 10334))]
 impl Problem for RockPaperScissors {
-    const DAY: usize = 2;
-    const TITLE: &'static str = "rock paper scissors";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "15", "12")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
     type P2 = usize;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        Ok(self.rounds.iter().map(|r| r.score()).sum())
+        Ok(self.score_with(&FollowGuide))
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        Ok(self.rounds.iter().map(|r| r.score_desired()).sum())
+        Ok(self.score_with(&DesiredOutcome))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = RockPaperScissors::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(10404, 10334));
-    }
-
-    #[test]
-    fn example() {
-        let input = "
-            A Y
-            B X
-            C Z
-            ";
-        let solution = RockPaperScissors::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(15, 12));
-    }
-
     #[test]
     fn choice_ordering() {
         assert_eq!(Choice::Rock.evaluate(&Choice::Rock), Outcome::Draw);
@@ -229,4 +678,148 @@ mod tests {
         );
         assert_eq!(Choice::Scissors.match_desire(&Outcome::Lose), Choice::Paper);
     }
+
+    #[test]
+    fn strategies() {
+        let input = "
+            A Y
+            B X
+            C Z
+            ";
+        let game = RockPaperScissors::from_str(input).unwrap();
+
+        assert_eq!(game.score_with(&FollowGuide), 15);
+        assert_eq!(game.score_with(&DesiredOutcome), 12);
+        assert_eq!(game.score_with(&Mimic), 4 + 5 + 6);
+        assert_eq!(game.score_with(&Fixed(Choice::Rock)), 4 + 1 + 7);
+        assert_eq!(
+            game.score_with(&|r: &Round| r.other().match_desire(&Outcome::Win)),
+            8 + 9 + 7
+        );
+    }
+
+    #[test]
+    fn byte_parser() {
+        let input = b"A Y\r\nB X\nC Z\n";
+        let game = RockPaperScissors::<Choice>::from_bytes(input).unwrap();
+        assert_eq!(game, RockPaperScissors::from_str("A Y\nB X\nC Z").unwrap());
+
+        assert!(RockPaperScissors::<Choice>::from_bytes(b"A Y\nBX\n").is_err());
+        assert!(RockPaperScissors::<Choice>::from_bytes(b"A Y\nB Q\n").is_err());
+        assert!(RockPaperScissors::<Choice>::from_bytes(b"A YY\n").is_err());
+        assert!(RockPaperScissors::<Choice>::from_bytes(&[b'A', b' ', 0xff]).is_err());
+    }
+
+    #[test]
+    fn timeline() {
+        let input = "
+            A Y
+            B X
+            C Z
+            ";
+        let game = RockPaperScissors::from_str(input).unwrap();
+
+        let timeline: Vec<_> = game.timeline().collect();
+        assert_eq!(timeline, vec![(8, 4), (9, 5), (15, 12)]);
+
+        let timeline: Vec<_> = game.timeline_with(&Fixed(Choice::Rock)).collect();
+        assert_eq!(timeline, vec![4, 5, 12]);
+    }
+
+    #[test]
+    fn scoring() {
+        let input = "
+            A Y
+            B X
+            C Z
+            ";
+        let game: RockPaperScissors = input.parse().unwrap();
+
+        let standard = Scoring::default();
+        assert_eq!(game.score_with_scoring(&FollowGuide, &standard), 15);
+        assert_eq!(game.score_with_scoring(&DesiredOutcome, &standard), 12);
+
+        let house: Scoring = "0 0 0 / 0 0 1".parse().unwrap();
+        assert_eq!(game.score_with_scoring(&FollowGuide, &house), 1);
+        assert_eq!(game.score_with_scoring(&DesiredOutcome, &house), 1);
+
+        let extended = Scoring::<ExtendedChoice>::standard();
+        assert_eq!(extended.shapes(), &[1, 2, 3, 4, 5]);
+        assert_eq!(
+            extended.play(&ExtendedChoice::Spock, &ExtendedChoice::Rock),
+            11
+        );
+
+        // a table for three moves can't score the five move variant
+        assert!("1 2 3 / 0 3 6".parse::<Scoring<ExtendedChoice>>().is_err());
+        assert!(Scoring::<Choice>::new(vec![1, 2, 3, 4, 5], 6, 3, 0).is_err());
+        assert!("1 2 3 / 0 3".parse::<Scoring>().is_err());
+        assert!("1 2 3".parse::<Scoring>().is_err());
+    }
+
+    #[test]
+    fn extended_ordering() {
+        use ExtendedChoice::*;
+
+        for m in ExtendedChoice::MOVES {
+            assert_eq!(m.evaluate(m), Outcome::Draw);
+            let wins = ExtendedChoice::MOVES.iter().filter(|o| m.beats(o)).count();
+            assert_eq!(wins, 2);
+        }
+
+        assert_eq!(Rock.evaluate(&Lizard), Outcome::Win);
+        assert_eq!(Lizard.evaluate(&Spock), Outcome::Win);
+        assert_eq!(Spock.evaluate(&Scissors), Outcome::Win);
+        assert_eq!(Scissors.evaluate(&Lizard), Outcome::Win);
+        assert_eq!(Lizard.evaluate(&Paper), Outcome::Win);
+        assert_eq!(Paper.evaluate(&Spock), Outcome::Win);
+        assert_eq!(Spock.evaluate(&Rock), Outcome::Win);
+        assert_eq!(Rock.evaluate(&Spock), Outcome::Lose);
+
+        assert_eq!(Spock.match_desire(&Outcome::Win), Paper);
+        assert_eq!(Spock.match_desire(&Outcome::Lose), Rock);
+    }
+
+    #[test]
+    fn extended_example() {
+        let input = "
+            A Y
+            E V
+            D X
+            ";
+        let game = RockPaperScissorsLizardSpock::from_str(input).unwrap();
+
+        // lizard loses to rock, rock loses to spock, scissors beats lizard
+        assert_eq!(game.score_with(&FollowGuide), 4 + 1 + 9);
+        // paper beats rock, rock loses to spock, lizard draws
+        assert_eq!(game.score_with(&DesiredOutcome), 8 + 1 + 7);
+    }
+
+    #[test]
+    fn tournament() {
+        let input = "
+            A Y
+            B X
+            C Z
+            ";
+        let game = RockPaperScissors::from_str(input).unwrap();
+
+        let mut tournament = Tournament::new();
+        tournament
+            .enter("rock", Fixed(Choice::Rock))
+            .enter("paper", Fixed(Choice::Paper))
+            .enter("mimic", Mimic);
+
+        let standings = game.tournament(&tournament);
+        assert_eq!(standings.matches.len(), 3);
+
+        let rock_v_paper = &standings.matches[0];
+        assert_eq!(rock_v_paper.first_wins, 0);
+        assert_eq!(rock_v_paper.second_wins, 3);
+        assert_eq!(rock_v_paper.first_score, 3);
+        assert_eq!(rock_v_paper.second_score, 24);
+        assert_eq!(rock_v_paper.winner(), Some(1));
+
+        assert_eq!(standings.ranked()[0].0, "paper");
+    }
 }
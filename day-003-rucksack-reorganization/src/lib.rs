@@ -1,17 +1,62 @@
 use std::str::FromStr;
 
-use anyhow::bail;
-use aoc_plumbing::{bits::char_to_mask, Problem};
+use anyhow::{anyhow, bail};
+use aoc_plumbing::{aoc_problem, Example, Problem};
+#[cfg(feature = "par")]
+use rayon::prelude::*;
+
+/// The non-alphanumeric printable ASCII items allowed by `Alphabet::Extended`,
+/// in priority order.
+const PUNCTUATION: &[u8] = b"!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// The set of characters that are valid rucksack items.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Alphabet {
+    /// `a-z` and `A-Z`, with priorities 1-52.
+    #[default]
+    Letters,
+    /// Letters, then `0-9` (priorities 53-62), then the remaining printable
+    /// ASCII punctuation (priorities 63-94).
+    Extended,
+}
+
+impl Alphabet {
+    /// The bit index of `ch` (its priority minus one), if it is a valid item.
+    pub fn index(&self, ch: char) -> Option<u32> {
+        match ch {
+            'a'..='z' => Some(ch as u32 - 'a' as u32),
+            'A'..='Z' => Some(ch as u32 - 'A' as u32 + 26),
+            _ if *self == Self::Letters => None,
+            '0'..='9' => Some(ch as u32 - '0' as u32 + 52),
+            _ if ch.is_ascii() => PUNCTUATION
+                .iter()
+                .position(|p| *p == ch as u8)
+                .map(|pos| pos as u32 + 62),
+            _ => None,
+        }
+    }
+
+    /// The item with the given bit index.
+    pub fn item(index: u32) -> char {
+        match index {
+            0..=25 => (b'a' + index as u8) as char,
+            26..=51 => (b'A' + (index - 26) as u8) as char,
+            52..=61 => (b'0' + (index - 52) as u8) as char,
+            _ => PUNCTUATION[(index - 62) as usize] as char,
+        }
+    }
+}
 
 #[inline]
-fn priority_sum_from_bin(bin: u64) -> usize {
+fn priority_sum_from_bin(bin: u128) -> usize {
     let mut offset = bin.trailing_zeros() as usize;
     let mut shifted = bin;
     let mut total_shift = 0_usize;
     let mut sum = 0;
 
     while shifted > 0 {
-        shifted = shifted >> (offset + 1);
+        // a shift of the full width would overflow, and means we're done
+        shifted = shifted.checked_shr(offset as u32 + 1).unwrap_or_default();
         total_shift += offset + 1;
         sum += total_shift;
         offset = shifted.trailing_zeros() as usize;
@@ -20,19 +65,93 @@ fn priority_sum_from_bin(bin: u64) -> usize {
     sum
 }
 
+/// The item characters represented by the set bits in `bin`, in priority order.
+fn items_from_bin(bin: u128) -> Vec<char> {
+    (0..u128::BITS)
+        .filter(|shift| bin & (1 << shift) != 0)
+        .map(Alphabet::item)
+        .collect()
+}
+
+/// Fold the items in `s` into a mask, reporting the first invalid item and
+/// its (1-indexed) column, offset by `col_offset`.
+fn mask_from_str(s: &str, alphabet: Alphabet, col_offset: usize) -> Result<u128, anyhow::Error> {
+    s.chars().enumerate().try_fold(0, |acc, (col, ch)| {
+        alphabet
+            .index(ch)
+            .map(|idx| acc | (1 << idx))
+            .ok_or_else(|| anyhow!("invalid item {:?} at column {}", ch, col_offset + col + 1))
+    })
+}
+
+/// A rucksack made up of one or more compartments, each stored as a mask of
+/// the items it contains.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct Rucksack {
-    one: u64,
-    two: u64,
+    compartments: Vec<u128>,
 }
 
 impl Rucksack {
+    /// Split the items in `s` evenly into `num` compartments.
+    pub fn split_even(s: &str, num: usize, alphabet: Alphabet) -> Result<Self, anyhow::Error> {
+        if !s.is_ascii() {
+            let (col, ch) = s
+                .chars()
+                .enumerate()
+                .find(|(_, ch)| !ch.is_ascii())
+                .expect("non-ascii string has a non-ascii char");
+            bail!("invalid item {:?} at column {}", ch, col + 1);
+        }
+
+        if s.len().checked_rem(num) != Some(0) {
+            bail!("Cannot split {} items into {} compartments", s.len(), num);
+        }
+
+        let size = s.len() / num;
+        let compartments = (0..num)
+            .map(|i| mask_from_str(&s[(i * size)..((i + 1) * size)], alphabet, i * size))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { compartments })
+    }
+
+    /// Split the items in `s` into compartments wherever `separator` appears.
+    pub fn split_by(s: &str, separator: char, alphabet: Alphabet) -> Result<Self, anyhow::Error> {
+        let mut col_offset = 0;
+        let compartments = s
+            .split(separator)
+            .map(|part| {
+                let mask = mask_from_str(part, alphabet, col_offset);
+                col_offset += part.chars().count() + 1;
+                mask
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { compartments })
+    }
+
+    pub fn num_compartments(&self) -> usize {
+        self.compartments.len()
+    }
+
+    /// The mask of items common to every compartment.
+    pub fn common(&self) -> u128 {
+        self.compartments
+            .iter()
+            .fold(self.union(), |acc, c| acc & c)
+    }
+
     pub fn duplicate_priorities(&self) -> usize {
-        priority_sum_from_bin(self.one & self.two)
+        priority_sum_from_bin(self.common())
+    }
+
+    /// The items that appear in every compartment.
+    pub fn duplicates(&self) -> Vec<char> {
+        items_from_bin(self.common())
     }
 
-    pub fn union(&self) -> u64 {
-        self.one | self.two
+    pub fn union(&self) -> u128 {
+        self.compartments.iter().fold(0, |acc, c| acc | c)
     }
 }
 
@@ -40,15 +159,7 @@ impl FromStr for Rucksack {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if !s.is_ascii() {
-            bail!("invalid input: {}", s);
-        }
-
-        let mid = s.len() / 2;
-        let one = s[0..mid].chars().fold(0, |acc, ch| acc | char_to_mask(ch));
-        let two = s[mid..].chars().fold(0, |acc, ch| acc | char_to_mask(ch));
-
-        Ok(Self { one, two })
+        Self::split_even(s, 2, Alphabet::Letters)
     }
 }
 
@@ -57,74 +168,177 @@ pub struct RucksackReorganization {
     rucksacks: Vec<Rucksack>,
 }
 
-impl FromStr for RucksackReorganization {
-    type Err = anyhow::Error;
+impl RucksackReorganization {
+    /// The duplicated items in each rucksack, in input order.
+    pub fn duplicates(&self) -> Vec<Vec<char>> {
+        self.rucksacks.iter().map(|r| r.duplicates()).collect()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Sum the priorities of the badge shared by each group of `group_size`
+    /// consecutive rucksacks.
+    pub fn badge_priority_sum(&self, group_size: usize) -> Result<usize, anyhow::Error> {
+        self.check_group_size(group_size)?;
+
+        #[cfg(not(feature = "par"))]
+        let total = self
+            .rucksacks
+            .chunks(group_size)
+            .map(|chunk| priority_sum_from_bin(group_mask(chunk)))
+            .sum();
+        #[cfg(feature = "par")]
+        let total = self
+            .rucksacks
+            .par_chunks(group_size)
+            .map(|chunk| priority_sum_from_bin(group_mask(chunk)))
+            .sum();
+
+        Ok(total)
+    }
+
+    /// The badge item for each group of `group_size` consecutive rucksacks.
+    ///
+    /// Errors if any group does not share exactly one item.
+    pub fn badges(&self, group_size: usize) -> Result<Vec<char>, anyhow::Error> {
+        self.check_group_size(group_size)?;
+
+        #[cfg(not(feature = "par"))]
+        let badges = self
+            .rucksacks
+            .chunks(group_size)
+            .enumerate()
+            .map(|(idx, chunk)| group_badge(idx, chunk))
+            .collect();
+        #[cfg(feature = "par")]
+        let badges = self
+            .rucksacks
+            .par_chunks(group_size)
+            .enumerate()
+            .map(|(idx, chunk)| group_badge(idx, chunk))
+            .collect();
+
+        badges
+    }
+
+    fn check_group_size(&self, group_size: usize) -> Result<(), anyhow::Error> {
+        if self.rucksacks.len().checked_rem(group_size) != Some(0) {
+            bail!(
+                "Num rucksacks ({}) is not a multiple of {}",
+                self.rucksacks.len(),
+                group_size
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The single item shared by every rucksack in the `idx`th group.
+fn group_badge(idx: usize, group: &[Rucksack]) -> Result<char, anyhow::Error> {
+    match items_from_bin(group_mask(group))[..] {
+        [badge] => Ok(badge),
+        ref items => bail!("Group {} does not have a unique badge: {:?}", idx, items),
+    }
+}
+
+/// The mask of items shared by every rucksack in the group.
+fn group_mask(group: &[Rucksack]) -> u128 {
+    group
+        .iter()
+        .fold(group[0].union(), |acc, r| acc & r.union())
+}
+
+impl RucksackReorganization {
+    /// Parse the input, allowing items from the given `alphabet`.
+    ///
+    /// Invalid items are reported with their line and column.
+    pub fn parse_with(s: &str, alphabet: Alphabet) -> Result<Self, anyhow::Error> {
         let rucksacks = s
             .trim()
             .lines()
-            .map(|l| Rucksack::from_str(l.trim()))
+            .enumerate()
+            .map(|(idx, l)| {
+                Rucksack::split_even(l.trim(), 2, alphabet)
+                    .map_err(|e| anyhow!("line {}: {}", idx + 1, e))
+            })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self { rucksacks })
     }
 }
 
+impl FromStr for RucksackReorganization {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Alphabet::Letters)
+    }
+}
+
+const EXAMPLE: &str = "
+            vJrwpWtwJgWrhcsFMMfFFhFp
+            jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+            PmmdzqPrVvPwwTWBwg
+            wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+            ttgJtRGJQctTZtZT
+            CrZsJsPPZsGzwwsLwLmpwMDw
+            ";
+
+#[aoc_problem(day = 3, title = "rucksack reorganization", year = 2022, full_dataset = (7597, 2607))]
 impl Problem for RucksackReorganization {
-    const DAY: usize = 3;
-    const TITLE: &'static str = "rucksack reorganization";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "157", "70")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
     type P2 = usize;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        Ok(self
+        #[cfg(not(feature = "par"))]
+        let total = self
             .rucksacks
             .iter()
             .map(|r| r.duplicate_priorities())
-            .sum())
-    }
-
-    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        if self.rucksacks.len() % 3 != 0 {
-            bail!("Num rucksacks is not a multiple of 3");
-        }
-
+            .sum();
+        #[cfg(feature = "par")]
         let total = self
             .rucksacks
-            .chunks(3)
-            .map(|chunk| {
-                priority_sum_from_bin(
-                    chunk
-                        .iter()
-                        .fold(chunk[0].union(), |acc, r| acc & r.union()),
-                )
-            })
+            .par_iter()
+            .map(|r| r.duplicate_priorities())
             .sum();
 
         Ok(total)
     }
+
+    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+        self.badge_priority_sum(3)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = RucksackReorganization::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(7597, 2607));
+    fn group_sizes() {
+        let input = "
+            vJrwpWtwJgWrhcsFMMfFFhFp
+            jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+            PmmdzqPrVvPwwTWBwg
+            wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+            ttgJtRGJQctTZtZT
+            CrZsJsPPZsGzwwsLwLmpwMDw
+            ";
+        let inst = RucksackReorganization::from_str(input).unwrap();
+
+        assert_eq!(inst.badge_priority_sum(3).unwrap(), 70);
+        assert_eq!(inst.badge_priority_sum(2).unwrap(), 371);
+        // no single item is shared by all six
+        assert_eq!(inst.badge_priority_sum(6).unwrap(), 0);
+        assert!(inst.badge_priority_sum(4).is_err());
+        assert!(inst.badge_priority_sum(0).is_err());
     }
 
     #[test]
-    fn example() {
+    fn items() {
         let input = "
             vJrwpWtwJgWrhcsFMMfFFhFp
             jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -133,7 +347,71 @@ mod tests {
             ttgJtRGJQctTZtZT
             CrZsJsPPZsGzwwsLwLmpwMDw
             ";
-        let solution = RucksackReorganization::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(157, 70));
+        let inst = RucksackReorganization::from_str(input).unwrap();
+
+        assert_eq!(
+            inst.duplicates(),
+            vec![
+                vec!['p'],
+                vec!['L'],
+                vec!['P'],
+                vec!['v'],
+                vec!['t'],
+                vec!['s']
+            ]
+        );
+        assert_eq!(inst.badges(3).unwrap(), vec!['r', 'Z']);
+        assert!(inst.badges(2).is_err());
+        assert!(inst.badges(6).is_err());
+    }
+
+    #[test]
+    fn compartments() {
+        let r = Rucksack::split_even("abcXdbeXfbgX", 3, Alphabet::Letters).unwrap();
+        assert_eq!(r.num_compartments(), 3);
+        assert_eq!(r.duplicates(), vec!['b', 'X']);
+
+        let r = Rucksack::split_by("ab|bca|cb", '|', Alphabet::Letters).unwrap();
+        assert_eq!(r.num_compartments(), 3);
+        assert_eq!(r.duplicates(), vec!['b']);
+        assert_eq!(r.duplicate_priorities(), 2);
+
+        assert!(Rucksack::split_even("abcd", 3, Alphabet::Letters).is_err());
+        assert!(Rucksack::split_even("abcd", 0, Alphabet::Letters).is_err());
+    }
+
+    #[test]
+    fn alphabets() {
+        for idx in 0..94 {
+            let item = Alphabet::item(idx);
+            assert_eq!(Alphabet::Extended.index(item), Some(idx));
+        }
+
+        assert_eq!(Alphabet::Letters.index('Z'), Some(51));
+        assert_eq!(Alphabet::Letters.index('1'), None);
+        assert_eq!(Alphabet::Extended.index('0'), Some(52));
+        assert_eq!(Alphabet::Extended.index('~'), Some(93));
+        assert_eq!(Alphabet::Extended.index(' '), None);
+        assert_eq!(Alphabet::Extended.index('é'), None);
+
+        let r = Rucksack::split_even("a1~b1~", 2, Alphabet::Extended).unwrap();
+        assert_eq!(r.duplicates(), vec!['1', '~']);
+        assert_eq!(r.duplicate_priorities(), 54 + 94);
+    }
+
+    #[test]
+    fn validation() {
+        let input = "
+            vJrwpWtwJgWrhcsFMMfFFhFp
+            jqHRNqRjqzjGDLGLrs1MfFZSrLrFZsSL
+            ";
+        let err = RucksackReorganization::from_str(input).unwrap_err();
+        assert_eq!(err.to_string(), "line 2: invalid item '1' at column 19");
+
+        let inst = RucksackReorganization::parse_with(input, Alphabet::Extended).unwrap();
+        assert_eq!(inst.duplicates(), vec![vec!['p'], vec!['L']]);
+
+        let err = Rucksack::split_by("ab|c!", '|', Alphabet::Letters).unwrap_err();
+        assert_eq!(err.to_string(), "invalid item '!' at column 5");
     }
 }
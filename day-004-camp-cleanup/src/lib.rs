@@ -1,48 +1,85 @@
-use std::str::FromStr;
+use std::{
+    io::{BufRead, BufReader, Read},
+    str::FromStr,
+};
 
-use aoc_plumbing::Problem;
+use anyhow::{anyhow, bail};
+use aoc_plumbing::{
+    aoc_problem,
+    interval::{Interval, IntervalSet},
+    AocError, Example, Problem,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::{self, multispace0},
-    multi::many1,
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
     sequence::{preceded, separated_pair},
     IResult,
 };
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
-pub struct Assignment {
-    start: u64,
-    end: u64,
-}
+pub type Assignment = Interval<u64>;
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// The assignments listed on a single line. This is usually two, but any
+/// number of comma-separated assignments is supported.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct Pair {
-    left: Assignment,
-    right: Assignment,
+    assignments: Vec<Assignment>,
 }
 
 impl Pair {
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    fn pairs(&self) -> impl Iterator<Item = (&Assignment, &Assignment)> + '_ {
+        self.assignments
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, a)| self.assignments[(i + 1)..].iter().map(move |b| (a, b)))
+    }
+
+    /// Whether any assignment fully contains another.
     pub fn complete_overlap(&self) -> bool {
-        (self.left.start >= self.right.start && self.left.end <= self.right.end)
-            || (self.right.start >= self.left.start && self.right.end <= self.left.end)
+        self.pairs()
+            .any(|(a, b)| a.contains_interval(b) || b.contains_interval(a))
     }
 
+    /// Whether any two assignments overlap.
     pub fn partial_overlap(&self) -> bool {
-        !(self.left.end < self.right.start) && !(self.right.end < self.left.start)
+        self.pairs().any(|(a, b)| a.overlaps(b))
+    }
+
+    /// The sections common to every assignment, if any.
+    pub fn common(&self) -> Option<Assignment> {
+        let (first, rest) = self.assignments.split_first()?;
+        rest.iter().try_fold(*first, |acc, a| acc.intersection(a))
+    }
+
+    /// The number of sections assigned to more than one elf.
+    pub fn overlap_len(&self) -> u64 {
+        self.pairs()
+            .filter_map(|(a, b)| a.intersection(b))
+            .collect::<IntervalSet<_>>()
+            .len()
     }
 }
 
 fn assignment_parser(input: &str) -> IResult<&str, Assignment> {
-    let (input, (start, end)) = separated_pair(complete::u64, tag("-"), complete::u64)(input)?;
-    Ok((input, Assignment { start, end }))
+    let (rest, (start, end)) = separated_pair(complete::u64, tag("-"), complete::u64)(input)?;
+    if start > end {
+        // a failure, so the surrounding lists don't just stop before it
+        return Err(nom::Err::Failure(Error::new(input, ErrorKind::Verify)));
+    }
+    Ok((rest, Assignment::new(start, end)))
 }
 
 fn pair_parser(input: &str) -> IResult<&str, Pair> {
-    let (input, (left, right)) =
-        separated_pair(assignment_parser, tag(","), assignment_parser)(input)?;
-    Ok((input, Pair { left, right }))
+    let (input, assignments) = separated_list1(tag(","), assignment_parser)(input)?;
+    Ok((input, Pair { assignments }))
 }
 
+#[cfg_attr(feature = "byte-parser", allow(dead_code))]
 fn pairs_parser(input: &str) -> IResult<&str, Vec<Pair>> {
     many1(preceded(multispace0, pair_parser))(input)
 }
@@ -51,29 +88,205 @@ impl FromStr for Pair {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, pair) = pair_parser(s).map_err(|e| e.to_owned())?;
+        let (_, pair) = pair_parser(s).map_err(|e| AocError::from_nom(s, e))?;
         Ok(pair)
     }
 }
 
+/// How many elves cover each section, stored as a step function.
+///
+/// Each `(section, count)` entry means `count` elves cover every section from
+/// `section` up to (but not including) the next entry's section.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Coverage {
+    steps: Vec<(u64, usize)>,
+}
+
+impl Coverage {
+    pub fn new<'a>(assignments: impl Iterator<Item = &'a Assignment>) -> Self {
+        let mut events: Vec<(u64, isize)> = assignments
+            .flat_map(|a| {
+                // an assignment ending on the last section is open to the end
+                std::iter::once((a.start, 1)).chain(a.end.checked_add(1).map(|end| (end, -1)))
+            })
+            .collect();
+        events.sort_unstable();
+
+        let mut steps: Vec<(u64, usize)> = Vec::with_capacity(events.len());
+        let mut count = 0_isize;
+        for (section, delta) in events {
+            count += delta;
+            match steps.last_mut() {
+                Some(last) if last.0 == section => last.1 = count as usize,
+                _ => steps.push((section, count as usize)),
+            }
+        }
+
+        // collapse runs that ended up with the same count
+        steps.dedup_by(|cur, prev| cur.1 == prev.1);
+
+        Self { steps }
+    }
+
+    /// The number of elves covering `section`.
+    pub fn count_at(&self, section: u64) -> usize {
+        match self.steps.binary_search_by(|s| s.0.cmp(&section)) {
+            Ok(idx) => self.steps[idx].1,
+            Err(0) => 0,
+            Err(idx) => self.steps[idx - 1].1,
+        }
+    }
+
+    /// The largest number of elves covering any single section.
+    pub fn max_count(&self) -> usize {
+        self.steps.iter().map(|s| s.1).max().unwrap_or_default()
+    }
+
+    /// The maximal runs of sections covered by at least `k` elves.
+    pub fn at_least(&self, k: usize) -> Vec<Assignment> {
+        let mut runs: Vec<Assignment> = Vec::new();
+
+        for (idx, &(start, count)) in self.steps.iter().enumerate() {
+            if count < k {
+                continue;
+            }
+
+            let end = self
+                .steps
+                .get(idx + 1)
+                .map(|next| next.0 - 1)
+                .unwrap_or(u64::MAX);
+            match runs.last_mut() {
+                Some(last) if last.end + 1 == start => last.end = end,
+                _ => runs.push(Assignment::new(start, end)),
+            }
+        }
+
+        runs
+    }
+
+    /// The number of sections covered by at least `k` elves.
+    pub fn sections_at_least(&self, k: usize) -> u64 {
+        self.at_least(k).iter().map(|r| r.len()).sum()
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CampCleanup {
     assignments: Vec<Pair>,
 }
 
+impl CampCleanup {
+    /// The number of doubly-assigned sections for each pair, in input order.
+    pub fn overlap_lens(&self) -> Vec<u64> {
+        self.assignments.iter().map(|a| a.overlap_len()).collect()
+    }
+
+    /// How many elves, across all lines, cover each section.
+    pub fn coverage(&self) -> Coverage {
+        Coverage::new(self.assignments.iter().flat_map(|p| p.assignments()))
+    }
+
+    /// The total number of doubly-assigned sections across all pairs.
+    pub fn total_overlap(&self) -> u64 {
+        self.assignments.iter().map(|a| a.overlap_len()).sum()
+    }
+}
+
+impl CampCleanup {
+    /// Parse the input in a single pass over its bytes, reading the
+    /// `a-b,c-d` structure directly instead of going through nom.
+    ///
+    /// Whitespace surrounding a line is ignored, as are blank lines.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, anyhow::Error> {
+        let mut assignments = Vec::with_capacity(input.len() / 12);
+        let mut current = Vec::with_capacity(2);
+        let mut start: Option<u64> = None;
+        let mut num: Option<u64> = None;
+        // set once whitespace follows a number, which then has to end the
+        // line, so neither "1 2" nor "1 -2" is read as a section
+        let mut num_done = false;
+        let mut line = 1;
+
+        for &b in input.iter().chain(std::iter::once(&b'\n')) {
+            match b {
+                b'0'..=b'9' if !num_done => {
+                    num = num
+                        .unwrap_or_default()
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add((b - b'0') as u64))
+                        .map(Some)
+                        .ok_or_else(|| anyhow!("Section out of range on line {}", line))?;
+                }
+                b'-' if start.is_none() && num.is_some() && !num_done => {
+                    start = num.take();
+                    num_done = false;
+                }
+                b',' if num_done => bail!("Unexpected whitespace on line {}", line),
+                b',' | b'\n' => {
+                    match (start.take(), num.take()) {
+                        (Some(s), Some(e)) if s > e => {
+                            bail!("Reversed assignment on line {}", line)
+                        }
+                        (Some(s), Some(e)) => current.push(Assignment::new(s, e)),
+                        (None, None) if b == b'\n' && current.is_empty() => {}
+                        _ => bail!("Invalid assignment on line {}", line),
+                    }
+                    num_done = false;
+
+                    if b == b'\n' {
+                        if !current.is_empty() {
+                            assignments.push(Pair {
+                                assignments: std::mem::replace(&mut current, Vec::with_capacity(2)),
+                            });
+                        }
+                        line += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => {
+                    if num.is_some() {
+                        num_done = true;
+                    } else if start.is_some() || !current.is_empty() {
+                        bail!("Unexpected whitespace on line {}", line);
+                    }
+                }
+                _ => bail!("Unexpected {:?} on line {}", b as char, line),
+            }
+        }
+
+        if assignments.is_empty() {
+            bail!("No assignments in input");
+        }
+
+        Ok(Self { assignments })
+    }
+}
+
 impl FromStr for CampCleanup {
     type Err = anyhow::Error;
 
+    #[cfg(not(feature = "byte-parser"))]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, assignments) = pairs_parser(s).map_err(|e| e.to_owned())?;
+        let (_, assignments) = pairs_parser(s).map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { assignments })
     }
+
+    #[cfg(feature = "byte-parser")]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(s.as_bytes())
+    }
 }
 
+const EXAMPLE: &str = " 2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8 ";
+
+#[aoc_problem(day = 4, title = "camp cleanup", year = 2022, full_dataset = (494, 833))]
 impl Problem for CampCleanup {
-    const DAY: usize = 4;
-    const TITLE: &'static str = "camp cleanup";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "2", "4")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -94,6 +307,28 @@ impl Problem for CampCleanup {
             .filter(|a| a.partial_overlap())
             .count())
     }
+
+    fn instance_reader<R: Read>(reader: R) -> Result<Self, Self::ProblemError> {
+        let mut assignments = Vec::new();
+
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let (_, pair) =
+                pair_parser(trimmed).map_err(|e| AocError::from_nom(&line, e).on_line(idx + 1))?;
+            assignments.push(pair);
+        }
+
+        if assignments.is_empty() {
+            bail!("No assignments in input");
+        }
+
+        Ok(Self { assignments })
+    }
 }
 
 #[cfg(test)]
@@ -103,23 +338,126 @@ mod tests {
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
+    fn example() {
+        let input = EXAMPLE;
+        dbg!(&input);
         let solution = CampCleanup::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(494, 833));
+        assert_eq!(solution, Solution::new(2, 4));
     }
 
     #[test]
-    fn example() {
-        let input = " 2-4,6-8
+    fn reader() {
+        assert_eq!(
+            CampCleanup::solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            CampCleanup::solve(EXAMPLE).unwrap()
+        );
+
+        let err = CampCleanup::instance_reader("2-4,6-8\n\n 2-x,4-5".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to parse line 3, column 4 (Digit): ` 2-x,4-5`"
+        );
+    }
+
+    #[test]
+    fn overlap_metrics() {
+        let input = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+        let inst = CampCleanup::from_str(input).unwrap();
+        assert_eq!(inst.overlap_lens(), vec![0, 0, 1, 5, 1, 3]);
+        assert_eq!(inst.total_overlap(), 10);
+    }
+
+    #[test]
+    fn coverage() {
+        let input = "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
 6-6,4-6
+2-6,4-8";
+        let coverage = CampCleanup::from_str(input).unwrap().coverage();
+
+        assert_eq!(coverage.count_at(1), 0);
+        assert_eq!(coverage.count_at(2), 4);
+        assert_eq!(coverage.count_at(6), 8);
+        assert_eq!(coverage.count_at(9), 1);
+        assert_eq!(coverage.count_at(10), 0);
+        assert_eq!(coverage.max_count(), 8);
+
+        assert_eq!(coverage.at_least(1), vec![Assignment::new(2, 9)]);
+        assert_eq!(coverage.at_least(8), vec![Assignment::new(6, 6)]);
+        assert_eq!(coverage.sections_at_least(6), 4);
+        assert!(coverage.at_least(9).is_empty());
+    }
+
+    #[test]
+    fn coverage_to_the_last_section() {
+        let input = "0-18446744073709551615,5-18446744073709551615";
+        let coverage = CampCleanup::from_str(input).unwrap().coverage();
+
+        assert_eq!(coverage.count_at(4), 1);
+        assert_eq!(coverage.count_at(u64::MAX), 2);
+        assert_eq!(coverage.max_count(), 2);
+        assert_eq!(coverage.at_least(1), vec![Assignment::new(0, u64::MAX)]);
+        assert_eq!(coverage.at_least(2), vec![Assignment::new(5, u64::MAX)]);
+    }
+
+    #[test]
+    fn byte_parser() {
+        let input = " 2-4,6-8
+2-3,4-5
+5-7,7-9
+
+2-8,3-7\r
+6-6,4-6,1-1
 2-6,4-8 ";
-        dbg!(&input);
-        let solution = CampCleanup::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(2, 4));
+        let from_bytes = CampCleanup::from_bytes(input.as_bytes()).unwrap();
+        let (_, assignments) = pairs_parser(input).unwrap();
+        assert_eq!(from_bytes, CampCleanup { assignments });
+
+        assert!(CampCleanup::from_bytes(b"").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4,6-").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4,\n").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4-5,6-8").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4 5,6-8").is_err());
+        assert!(CampCleanup::from_bytes(b"2 -4,6-8").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4 ,6-8").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4,6 -8").is_err());
+        assert!(CampCleanup::from_bytes(b"2-4,6-8\n1-x").is_err());
+        assert!(CampCleanup::from_bytes(b"1-99999999999999999999").is_err());
+    }
+
+    #[test]
+    fn reversed_assignments() {
+        assert!(CampCleanup::from_bytes(b"2-4,6-8\n5-3,6-8").is_err());
+        assert!(pairs_parser("2-4,6-8\n5-3,6-8").is_err());
+        assert!(Pair::from_str("2-4,5-3").is_err());
+        assert!(Pair::from_str("3-3,4-4").is_ok());
+    }
+
+    #[test]
+    fn n_way() {
+        let pair = Pair::from_str("2-8,3-4,6-9").unwrap();
+        assert_eq!(pair.assignments().len(), 3);
+        assert!(pair.complete_overlap());
+        assert!(pair.partial_overlap());
+        assert_eq!(pair.common(), None);
+        // 3-4 and 6-8
+        assert_eq!(pair.overlap_len(), 5);
+
+        let pair = Pair::from_str("1-3,5-6,8-9").unwrap();
+        assert!(!pair.complete_overlap());
+        assert!(!pair.partial_overlap());
+        assert_eq!(pair.overlap_len(), 0);
+
+        let pair = Pair::from_str("1-10,4-12,2-6,5-7").unwrap();
+        assert!(pair.complete_overlap());
+        assert_eq!(pair.common(), Some(Assignment::new(5, 6)));
+        assert_eq!(pair.overlap_len(), 9);
     }
 }
@@ -1,7 +1,7 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
 use anyhow::{anyhow, bail};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, Example, Problem};
 use nom::{
     bytes::complete::tag,
     character,
@@ -50,6 +50,110 @@ fn parse_instruction(input: &str) -> IResult<&str, (u64, u64, u64)> {
     Ok((input, (quantity, start, end)))
 }
 
+/// The behavior of a crane when moving crates between two stacks.
+pub trait CraneModel {
+    /// Move `quantity` crates from the top of `from` to the top of `to`.
+    ///
+    /// The caller guarantees that `from` holds at least `quantity` crates.
+    fn transfer(
+        &self,
+        quantity: usize,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Reverse a previous [CraneModel::transfer] of `quantity` crates from
+    /// `from` to `to`, returning them to the top of `from`.
+    ///
+    /// The caller guarantees that `to` holds at least `quantity` crates. The
+    /// default implementation transfers the crates back, which is only
+    /// correct for models where doing so restores the original order (as is
+    /// the case for both the 9000 and 9001).
+    fn undo(
+        &self,
+        quantity: usize,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), anyhow::Error> {
+        self.transfer(quantity, to, from)
+    }
+}
+
+/// The index in `stack` above which the top `quantity` crates sit.
+///
+/// Both models move the whole run of crates with a single slice operation so
+/// that each instruction is linear in the number of crates moved, regardless
+/// of how large that is.
+fn top_split(stack: &[char], quantity: usize) -> Result<usize, anyhow::Error> {
+    stack
+        .len()
+        .checked_sub(quantity)
+        .ok_or_else(|| anyhow!("Not enough elements to move"))
+}
+
+/// Moves crates one at a time, reversing their order.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn transfer(
+        &self,
+        quantity: usize,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), anyhow::Error> {
+        let split = top_split(from, quantity)?;
+        to.extend(from.drain(split..).rev());
+
+        Ok(())
+    }
+}
+
+/// Moves multiple crates at once, preserving their order.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn transfer(
+        &self,
+        quantity: usize,
+        from: &mut Vec<char>,
+        to: &mut Vec<char>,
+    ) -> Result<(), anyhow::Error> {
+        let split = top_split(from, quantity)?;
+        to.extend_from_slice(&from[split..]);
+        from.truncate(split);
+
+        Ok(())
+    }
+}
+
+const CRATE_STRIDE: usize = 4;
+
+/// Validate the stack labels, which must be `1..=n` in order, returning `n`.
+fn parse_labels(index_line: &str) -> Result<usize, anyhow::Error> {
+    let mut num_stacks = 0;
+    for (idx, label) in index_line.split_whitespace().enumerate() {
+        let label: usize = label
+            .parse()
+            .map_err(|_| anyhow!("Invalid stack label: {}", label))?;
+
+        if label != idx + 1 {
+            bail!("Expected stack label {} but found {}", idx + 1, label);
+        }
+
+        num_stacks = label;
+    }
+
+    if num_stacks == 0 {
+        bail!("Invalid input missing stack labels");
+    }
+
+    Ok(num_stacks)
+}
+
+type StackPair<'a> = (&'a mut Vec<char>, &'a mut Vec<char>);
+
 // Use an intermediate object for indirection so I can clone this and not the
 // problem
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
@@ -58,43 +162,89 @@ pub struct Ship {
 }
 
 impl Ship {
-    pub fn carry_out(&mut self, instruction: &Instruction) -> Result<(), anyhow::Error> {
-        if self.columns.len() < instruction.start || self.columns.len() < instruction.end {
-            bail!("Invalid instruction: {:?}", instruction);
+    /// Carry out `instruction` using the given crane `model`.
+    pub fn execute<M: CraneModel>(
+        &mut self,
+        instruction: &Instruction,
+        model: &M,
+    ) -> Result<(), anyhow::Error> {
+        if let Some((from, to)) =
+            self.stacks_mut(instruction.start, instruction.end, instruction.quantity)?
+        {
+            model.transfer(instruction.quantity, from, to)?;
         }
 
-        for _ in 0..instruction.quantity {
-            let k = self.columns[instruction.start]
-                .crates
-                .pop()
-                .ok_or_else(|| anyhow!("attempted to remove from empty stack"))?;
-            self.columns[instruction.end].crates.push(k);
+        Ok(())
+    }
+
+    /// Reverse the effect of `instruction`, which must have been the most
+    /// recent instruction carried out with `model`.
+    pub fn undo<M: CraneModel>(
+        &mut self,
+        instruction: &Instruction,
+        model: &M,
+    ) -> Result<(), anyhow::Error> {
+        if let Some((to, from)) =
+            self.stacks_mut(instruction.end, instruction.start, instruction.quantity)?
+        {
+            model.undo(instruction.quantity, from, to)?;
         }
 
         Ok(())
     }
 
-    pub fn carry_out_advanced(&mut self, instruction: &Instruction) -> Result<(), anyhow::Error> {
-        if self.columns.len() <= instruction.start || self.columns.len() <= instruction.end {
-            bail!("Invalid instruction: {:?}", instruction);
+    /// Reverse the effect of carrying out all of `instructions` (in order)
+    /// with `model`, recovering the ship as it was before them.
+    pub fn rollback<M: CraneModel>(
+        &mut self,
+        instructions: &[Instruction],
+        model: &M,
+    ) -> Result<(), anyhow::Error> {
+        for inst in instructions.iter().rev() {
+            self.undo(inst, model)?;
         }
 
-        let len = self.columns[instruction.start].crates.len();
+        Ok(())
+    }
+
+    /// Get the `src` and `dst` stacks, ensuring `src` holds at least
+    /// `quantity` crates. Yields `None` if they are the same stack, as moving
+    /// a stack onto itself leaves it unchanged.
+    fn stacks_mut(
+        &mut self,
+        src: usize,
+        dst: usize,
+        quantity: usize,
+    ) -> Result<Option<StackPair<'_>>, anyhow::Error> {
+        if self.columns.len() <= src || self.columns.len() <= dst {
+            bail!("Invalid stacks: {} and {}", src + 1, dst + 1);
+        }
 
-        if len < instruction.quantity {
+        if self.columns[src].crates.len() < quantity {
             bail!("Not enough elements to move");
         }
 
-        for i in (len - instruction.quantity)..len {
-            let v = self.columns[instruction.start].crates[i];
-            self.columns[instruction.end].crates.push(v);
+        if src == dst {
+            return Ok(None);
         }
 
-        self.columns[instruction.start]
-            .crates
-            .truncate(len - instruction.quantity);
+        let (src, dst) = if src < dst {
+            let (left, right) = self.columns.split_at_mut(dst);
+            (&mut left[src], &mut right[0])
+        } else {
+            let (left, right) = self.columns.split_at_mut(src);
+            (&mut right[0], &mut left[dst])
+        };
 
-        Ok(())
+        Ok(Some((&mut src.crates, &mut dst.crates)))
+    }
+
+    pub fn carry_out(&mut self, instruction: &Instruction) -> Result<(), anyhow::Error> {
+        self.execute(instruction, &CrateMover9000)
+    }
+
+    pub fn carry_out_advanced(&mut self, instruction: &Instruction) -> Result<(), anyhow::Error> {
+        self.execute(instruction, &CrateMover9001)
     }
 
     pub fn top_values(&self) -> String {
@@ -105,12 +255,97 @@ impl Ship {
     }
 }
 
+impl Display for Ship {
+    /// Draws the ship in the same format as the puzzle input.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let height = self
+            .columns
+            .iter()
+            .map(|c| c.crates.len())
+            .max()
+            .unwrap_or_default();
+
+        for row in (0..height).rev() {
+            let line = self
+                .columns
+                .iter()
+                .map(|c| match c.crates.get(row) {
+                    Some(v) => format!("[{}]", v),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let labels = (1..=self.columns.len())
+            .map(|i| format!("{:^3}", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "{}", labels.trim_end())
+    }
+}
+
+/// Iterator over the state of a [Ship] after each instruction is carried out.
+///
+/// Iteration stops after the first instruction that fails.
+#[derive(Debug, Clone)]
+pub struct Steps<'a, M> {
+    ship: Ship,
+    instructions: std::slice::Iter<'a, Instruction>,
+    model: M,
+    failed: bool,
+}
+
+impl<'a, M: CraneModel> Steps<'a, M> {
+    pub fn new(ship: Ship, instructions: &'a [Instruction], model: M) -> Self {
+        Self {
+            ship,
+            instructions: instructions.iter(),
+            model,
+            failed: false,
+        }
+    }
+
+    /// The state of the ship after the most recently yielded step.
+    pub fn ship(&self) -> &Ship {
+        &self.ship
+    }
+}
+
+impl<'a, M: CraneModel> Iterator for Steps<'a, M> {
+    type Item = Result<Ship, anyhow::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let inst = self.instructions.next()?;
+        match self.ship.execute(inst, &self.model) {
+            Ok(_) => Some(Ok(self.ship.clone())),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SupplyStacks {
     ship: Ship,
     instructions: Vec<Instruction>,
 }
 
+impl SupplyStacks {
+    /// Step through the instructions with the given crane `model`, yielding
+    /// the ship after each one.
+    pub fn steps<M: CraneModel>(&self, model: M) -> Steps<'_, M> {
+        Steps::new(self.ship.clone(), &self.instructions, model)
+    }
+}
+
 impl FromStr for SupplyStacks {
     type Err = anyhow::Error;
 
@@ -119,42 +354,33 @@ impl FromStr for SupplyStacks {
             .split_once("\n\n")
             .ok_or_else(|| anyhow!("Invalid input, no separating newline"))?;
 
-        // let's get the last line of the picture and find every char index
-        // corresponding to a numeric character
+        // the last line of the picture labels the stacks, which may be more
+        // than one digit wide, so we only use it to determine how many stacks
+        // there are
         let mut iter = picture.lines().rev();
         let index_line = iter
             .next()
             .ok_or_else(|| anyhow!("Invalid input missing index line"))?;
 
-        let indicies: Vec<_> = index_line
-            .chars()
-            .enumerate()
-            .filter(|(_, ch)| ch.is_digit(10))
-            .collect();
-
-        // Now, if we found more than 9, we have a problem because our strategy
-        // relies on column alignment, so I'm going to bail here
-        if indicies.len() > 9 {
-            bail!("I am only allowing for up to 9 stacks")
-        }
+        let num_stacks = parse_labels(index_line)?;
 
-        // with the remaining lines, we're going to find every alpha char in a
-        // column that matches an index we discovered
-        let picture_lines: Vec<Vec<char>> = iter.map(|l| l.chars().collect::<Vec<_>>()).collect();
+        // with the remaining lines, crates are laid out on a fixed stride of
+        // four characters ("[X] "), regardless of how wide the labels are
+        let picture_lines: Vec<&[u8]> = iter.map(|l| l.as_bytes()).collect();
 
         if picture_lines.is_empty() {
             bail!("Empty picture");
         }
 
-        let mut columns: Vec<_> = (0..indicies.len()).map(|_| Column::default()).collect();
-        for (col, (idx, _)) in indicies.iter().enumerate() {
-            for line_idx in 0..picture_lines.len() {
+        let mut columns: Vec<_> = (0..num_stacks).map(|_| Column::default()).collect();
+        for line in picture_lines {
+            for (col, chunk) in line.chunks(CRATE_STRIDE).enumerate() {
                 // if we have uneven lines, the get will guard against that
-                if let Some(v) = picture_lines[line_idx]
-                    .get(*idx)
-                    .filter(|v| v.is_alphanum())
-                {
-                    columns[col].crates.push(*v);
+                if let Some(v) = chunk.get(1).filter(|v| v.is_alphanum()) {
+                    let column = columns.get_mut(col).ok_or_else(|| {
+                        anyhow!("Crate '{}' is not in a labeled stack", *v as char)
+                    })?;
+                    column.crates.push(*v as char);
                 }
             }
         }
@@ -173,10 +399,19 @@ impl FromStr for SupplyStacks {
     }
 }
 
+const EXAMPLE: &str = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+#[aoc_problem(day = 5, title = "supply stacks", year = 2022, full_dataset = ("VQZNJMWTR", "NLCDCLVMQ"))]
 impl Problem for SupplyStacks {
-    const DAY: usize = 5;
-    const TITLE: &'static str = "supply stacks";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "CMZ", "MCD")];
 
     type ProblemError = anyhow::Error;
     type P1 = String;
@@ -186,7 +421,7 @@ impl Problem for SupplyStacks {
         // we need to clone here so we don't mess with part two (and the bench)
         let mut ship = self.ship.clone();
         for inst in self.instructions.iter() {
-            ship.carry_out(inst)?;
+            ship.execute(inst, &CrateMover9000)?;
         }
 
         Ok(ship.top_values())
@@ -196,7 +431,7 @@ impl Problem for SupplyStacks {
         // we need to clone here so we don't mess with the bench
         let mut ship = self.ship.clone();
         for inst in self.instructions.iter() {
-            ship.carry_out_advanced(inst)?;
+            ship.execute(inst, &CrateMover9001)?;
         }
 
         Ok(ship.top_values())
@@ -209,19 +444,129 @@ mod tests {
 
     use super::*;
 
+    // moves at most `capacity` crates at a time, preserving the order of each
+    // batch
+    struct LimitedMover {
+        capacity: usize,
+    }
+
+    impl CraneModel for LimitedMover {
+        fn transfer(
+            &self,
+            quantity: usize,
+            from: &mut Vec<char>,
+            to: &mut Vec<char>,
+        ) -> Result<(), anyhow::Error> {
+            let mut remaining = quantity;
+            while remaining > 0 {
+                let batch = remaining.min(self.capacity);
+                CrateMover9001.transfer(batch, from, to)?;
+                remaining -= batch;
+            }
+
+            Ok(())
+        }
+    }
+
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = SupplyStacks::solve(&input).unwrap();
+    fn crane_models() {
+        let stacks = SupplyStacks::from_str(
+            "[A]
+[B]
+[C]
+[D]
+[E]
+ 1   2
+
+move 5 from 1 to 2",
+        )
+        .unwrap();
+        let inst = stacks.instructions[0];
+
+        let mut ship = stacks.ship.clone();
+        ship.execute(&inst, &CrateMover9000).unwrap();
+        assert_eq!(ship.columns[1].crates, vec!['A', 'B', 'C', 'D', 'E']);
+
+        let mut ship = stacks.ship.clone();
+        ship.execute(&inst, &CrateMover9001).unwrap();
+        assert_eq!(ship.columns[1].crates, vec!['E', 'D', 'C', 'B', 'A']);
+
+        let mut ship = stacks.ship.clone();
+        ship.execute(&inst, &LimitedMover { capacity: 2 }).unwrap();
+        assert_eq!(ship.columns[1].crates, vec!['B', 'A', 'D', 'C', 'E']);
+
+        // moving onto the same stack, too many crates, or to a missing stack
+        let mut ship = stacks.ship.clone();
+        let same = Instruction::from_str("move 2 from 1 to 1").unwrap();
+        ship.execute(&same, &CrateMover9000).unwrap();
+        assert_eq!(ship, stacks.ship);
+
+        let too_many = Instruction::from_str("move 6 from 1 to 2").unwrap();
+        assert!(ship.execute(&too_many, &CrateMover9001).is_err());
+
+        let missing = Instruction::from_str("move 1 from 1 to 3").unwrap();
+        assert!(ship.execute(&missing, &CrateMover9000).is_err());
+        assert_eq!(ship, stacks.ship);
+    }
+
+    #[test]
+    fn steps() {
+        let input = "    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+        let stacks = SupplyStacks::from_str(input).unwrap();
+
+        let tops: Vec<_> = stacks
+            .steps(CrateMover9000)
+            .map(|s| s.unwrap().top_values())
+            .collect();
+        assert_eq!(tops, vec!["DCP", "CZ", "MZ", "CMZ"]);
+
+        let tops: Vec<_> = stacks
+            .steps(CrateMover9001)
+            .map(|s| s.unwrap().top_values())
+            .collect();
+        assert_eq!(tops, vec!["DCP", "CD", "CD", "MCD"]);
+
+        let mut steps = stacks.steps(CrateMover9000);
+        steps.next();
         assert_eq!(
-            solution,
-            Solution::new("VQZNJMWTR".into(), "NLCDCLVMQ".into())
+            steps.ship().to_string(),
+            "[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3"
         );
+
+        // the rendered ship can be parsed back
+        let reparsed =
+            SupplyStacks::from_str(&format!("{}\n\nmove 1 from 1 to 2", steps.ship())).unwrap();
+        assert_eq!(&reparsed.ship, steps.ship());
+
+        // iteration stops at the first error
+        let bad = SupplyStacks::from_str(
+            "[A]
+ 1   2
+
+move 1 from 1 to 2
+move 2 from 2 to 1
+move 1 from 2 to 1",
+        )
+        .unwrap();
+        let results: Vec<_> = bad.steps(CrateMover9001).collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
     }
 
     #[test]
-    fn example() {
+    fn rollback() {
         let input = "    [D]
 [N] [C]
 [Z] [M] [P]
@@ -231,8 +576,93 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
+        let stacks = SupplyStacks::from_str(input).unwrap();
+
+        let mut ship = stacks.ship.clone();
+        for inst in stacks.instructions.iter() {
+            ship.execute(inst, &CrateMover9000).unwrap();
+        }
+        ship.rollback(&stacks.instructions[2..], &CrateMover9000)
+            .unwrap();
+        let expected = stacks.steps(CrateMover9000).nth(1).unwrap().unwrap();
+        assert_eq!(ship, expected);
+        ship.rollback(&stacks.instructions[..2], &CrateMover9000)
+            .unwrap();
+        assert_eq!(ship, stacks.ship);
+
+        let mut ship = stacks.ship.clone();
+        for inst in stacks.instructions.iter() {
+            ship.execute(inst, &CrateMover9001).unwrap();
+        }
+        ship.rollback(&stacks.instructions, &CrateMover9001)
+            .unwrap();
+        assert_eq!(ship, stacks.ship);
+
+        // rolling back instructions that were never carried out
+        let mut ship = stacks.ship.clone();
+        assert!(ship
+            .rollback(&stacks.instructions, &CrateMover9000)
+            .is_err());
+    }
+
+    #[test]
+    fn bulk_moves() {
+        let mut ship = Ship {
+            columns: vec![
+                Column {
+                    crates: ('A'..='Z').cycle().take(100_000).collect(),
+                },
+                Column::default(),
+            ],
+        };
+        let original = ship.clone();
+        let there = Instruction::from_str("move 99999 from 1 to 2").unwrap();
+        let back = Instruction::from_str("move 99999 from 2 to 1").unwrap();
+
+        for _ in 0..100 {
+            ship.execute(&there, &CrateMover9000).unwrap();
+            assert_eq!(ship.columns[1].crates.len(), 99_999);
+            assert_eq!(ship.columns[1].crates[0], 'D');
+            ship.execute(&back, &CrateMover9000).unwrap();
+
+            ship.execute(&there, &CrateMover9001).unwrap();
+            assert_eq!(ship.columns[1].crates[0], 'B');
+            ship.execute(&back, &CrateMover9001).unwrap();
+        }
+
+        assert_eq!(ship, original);
+    }
+
+    #[test]
+    fn many_stacks() {
+        let input = "                                            [X]
+[A] [B] [C] [D] [E] [F] [G] [H] [I] [J] [K] [L]
+ 1   2   3   4   5   6   7   8   9   10  11  12
+
+move 1 from 12 to 1
+move 1 from 10 to 11";
         let solution = SupplyStacks::solve(input).unwrap();
-        assert_eq!(solution, Solution::new("CMZ".into(), "MCD".into()));
+        assert_eq!(
+            solution,
+            Solution::new("XBCDEFGHIJL".into(), "XBCDEFGHIJL".into())
+        );
+    }
+
+    #[test]
+    fn label_validation() {
+        assert_eq!(parse_labels(" 1   2   3 ").unwrap(), 3);
+        assert_eq!(
+            parse_labels(" 1   2   3   4   5   6   7   8   9   10  11 ").unwrap(),
+            11
+        );
+        assert!(parse_labels(" 1   3 ").is_err());
+        assert!(parse_labels("   ").is_err());
+
+        let input = "[A] [B] [C]
+ 1   2
+
+move 1 from 1 to 2";
+        assert!(SupplyStacks::from_str(input).is_err());
     }
 
     #[test]
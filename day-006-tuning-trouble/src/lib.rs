@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use anyhow::bail;
-use aoc_plumbing::{bits::char_to_mask, Problem};
+use aoc_plumbing::{aoc_problem, bits::try_char_to_mask, AocError, Example, Problem};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TuningTrouble {
@@ -9,7 +8,7 @@ pub struct TuningTrouble {
 }
 
 impl TuningTrouble {
-    pub fn find_unique(&self, size: usize) -> Result<usize, anyhow::Error> {
+    pub fn find_unique(&self, size: usize) -> Result<usize, AocError> {
         let mut idx = size - 1;
         'outer: while idx < self.message.len() {
             let mut sum = self.message[idx];
@@ -29,7 +28,10 @@ impl TuningTrouble {
             return Ok(idx + 1);
         }
 
-        bail!("None found");
+        Err(AocError::UnsolvableInput(format!(
+            "no run of {} unique characters",
+            size
+        )))
     }
 }
 
@@ -38,17 +40,22 @@ impl FromStr for TuningTrouble {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
-            message: s.chars().map(char_to_mask).collect(),
+            message: s
+                .trim()
+                .chars()
+                .map(try_char_to_mask)
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
+const EXAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+#[aoc_problem(day = 6, title = "tuning trouble", year = 2022, full_dataset = (1625, 2250))]
 impl Problem for TuningTrouble {
-    const DAY: usize = 6;
-    const TITLE: &'static str = "tuning trouble";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "7", "19")];
 
-    type ProblemError = anyhow::Error;
+    type ProblemError = AocError;
     type P1 = usize;
     type P2 = usize;
 
@@ -60,25 +67,3 @@ impl Problem for TuningTrouble {
         self.find_unique(14)
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = TuningTrouble::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1625, 2250));
-    }
-
-    #[test]
-    fn example() {
-        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-        let solution = TuningTrouble::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(7, 19));
-    }
-}
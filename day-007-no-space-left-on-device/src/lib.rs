@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, recursion::grow, AocError, Example, Problem};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -82,7 +82,7 @@ impl Directory {
             + self
                 .directories
                 .values()
-                .map(|i| directories[*i].size(directories, results, criteria))
+                .map(|i| grow(|| directories[*i].size(directories, results, criteria)))
                 .sum::<u64>();
 
         if criteria(s) {
@@ -118,7 +118,7 @@ impl FromStr for NoSpaceLeftOnDevice {
         let mut cur = 0;
 
         for res in s.trim().lines().map(|l| parse_history(l.trim())) {
-            let (_, out) = res.map_err(|e| e.to_owned())?;
+            let (_, out) = res.map_err(|e| AocError::from_nom(s, e))?;
 
             let next_inode = filesystem.directories.len();
             match out {
@@ -159,50 +159,7 @@ impl FromStr for NoSpaceLeftOnDevice {
     }
 }
 
-impl Problem for NoSpaceLeftOnDevice {
-    const DAY: usize = 7;
-    const TITLE: &'static str = "no space left on device";
-    const README: &'static str = include_str!("../README.md");
-
-    type ProblemError = anyhow::Error;
-    type P1 = u64;
-    type P2 = u64;
-
-    fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        let mut results = Vec::with_capacity(self.directories.len());
-        self.directories[0].size(&self.directories, &mut results, |v| v <= 100000);
-        Ok(results.iter().sum())
-    }
-
-    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        let mut results = Vec::with_capacity(self.directories.len());
-        let desired = 30000000 - (70000000 - self.total_size);
-        self.directories[0].size(&self.directories, &mut results, |v| v >= desired);
-
-        results
-            .into_iter()
-            .min()
-            .ok_or_else(|| anyhow!("could not find directory"))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = NoSpaceLeftOnDevice::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1792222, 1112963));
-    }
-
-    #[test]
-    fn example() {
-        let input = "
+const EXAMPLE: &str = "
             $ cd /
             $ ls
             dir a
@@ -227,7 +184,29 @@ mod tests {
             5626152 d.ext
             7214296 k
             ";
-        let solution = NoSpaceLeftOnDevice::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(95437, 24933642));
+
+#[aoc_problem(day = 7, title = "no space left on device", year = 2022, full_dataset = (1792222, 1112963))]
+impl Problem for NoSpaceLeftOnDevice {
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "95437", "24933642")];
+
+    type ProblemError = anyhow::Error;
+    type P1 = u64;
+    type P2 = u64;
+
+    fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+        let mut results = Vec::with_capacity(self.directories.len());
+        self.directories[0].size(&self.directories, &mut results, |v| v <= 100000);
+        Ok(results.iter().sum())
+    }
+
+    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+        let mut results = Vec::with_capacity(self.directories.len());
+        let desired = 30000000 - (70000000 - self.total_size);
+        self.directories[0].size(&self.directories, &mut results, |v| v >= desired);
+
+        results
+            .into_iter()
+            .min()
+            .ok_or_else(|| anyhow!("could not find directory"))
     }
 }
@@ -1,7 +1,6 @@
 use std::str::FromStr;
 
-use anyhow::{anyhow, bail};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, parsing::char_grid, AocError, Example, Problem};
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 pub struct VisualRange {
@@ -131,27 +130,32 @@ impl FromStr for TreetopTreeHouse {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let dim = s.lines().count();
+        let grid = char_grid(s.trim().lines().map(str::trim), 0, |ch| {
+            ch.to_digit(10).map(|d| d as u8).ok_or("invalid digit")
+        })?;
+        let height = grid.len();
+        let width = grid.first().map(|row| row.len()).unwrap_or_default();
+
+        let dim = height.max(width);
         if dim > 128 {
-            bail!("Sorry, can only handle grids of at most 128x128");
+            return Err(AocError::LimitExceeded {
+                what: "grid size",
+                limit: 128,
+                actual: dim,
+            }
+            .into());
         }
-        let mut grid = Vec::with_capacity(dim);
 
         // so we're not going to allocate for the 0, because those can NEVER be
         // seen unless on the edge and they always have a score of at most 4
-        let mut row_maps = vec![vec![0u128; 9]; dim];
-        let mut col_maps = vec![vec![0u128; 9]; dim];
+        let mut row_maps = vec![vec![0u128; 9]; height];
+        let mut col_maps = vec![vec![0u128; 9]; width];
 
         let mut row_mask = 1u128;
-        for (row, line) in s.trim().lines().enumerate() {
-            let mut new_row = Vec::with_capacity(dim);
+        for (row, digits) in grid.iter().enumerate() {
             let mut col_mask = 1u128;
-            for (col, ch) in line.trim().chars().enumerate() {
-                let digit =
-                    ch.to_digit(10)
-                        .ok_or_else(|| anyhow!("Invalid digit: {}", ch))? as u8;
-                new_row.push(digit);
-                if digit > 0 {
+            for (col, digit) in digits.iter().enumerate() {
+                if *digit > 0 {
                     // confusing naming, I realize, but the col mask is which
                     // bit in the integer for the row this digit corresponds to
                     row_maps[row][(digit - 1) as usize] |= col_mask;
@@ -160,8 +164,6 @@ impl FromStr for TreetopTreeHouse {
                 col_mask <<= 1;
             }
 
-            grid.push(new_row);
-
             row_mask <<= 1;
         }
 
@@ -192,13 +194,6 @@ impl FromStr for TreetopTreeHouse {
             }
         }
 
-        let height = grid.len();
-        let width = grid[0].len();
-
-        if grid.iter().any(|r| r.len() != width) {
-            bail!("Grid has uneven rows");
-        }
-
         Ok(Self {
             grid,
             width,
@@ -210,10 +205,19 @@ impl FromStr for TreetopTreeHouse {
     }
 }
 
+const EXAMPLE: &str = "
+            30373
+            25512
+            65332
+            33549
+            35390
+            ";
+
+#[aoc_problem(day = 8, title = "treetop tree house", year = 2022, full_dataset = (1859, 332640))]
 impl Problem for TreetopTreeHouse {
-    const DAY: usize = 8;
-    const TITLE: &'static str = "treetop tree house";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "21", "8")];
+    // the scenic scores are found while counting the visible trees
+    const LINKED_PARTS: bool = true;
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -245,32 +249,8 @@ impl Problem for TreetopTreeHouse {
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         Ok(self.max_score)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = TreetopTreeHouse::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1859, 332640));
-    }
 
-    #[test]
-    fn example() {
-        let input = "
-            30373
-            25512
-            65332
-            33549
-            35390
-            ";
-        let solution = TreetopTreeHouse::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(21, 8));
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![("grid", format!("{}x{}", self.width, self.height))]
     }
 }
@@ -1,7 +1,10 @@
 use std::{hash::Hash, str::FromStr};
 
-use anyhow::bail;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem,
+    direction::{Direction, InvalidDirection},
+    AocError, Example, Problem,
+};
 use nom::{
     character::complete::multispace0,
     combinator::map_res,
@@ -30,35 +33,25 @@ impl Location {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Motion {
-    Up(i64),
-    Down(i64),
-    Right(i64),
-    Left(i64),
+pub struct Motion {
+    direction: Direction,
+    distance: i64,
 }
 
 impl Motion {
     pub fn value(&self) -> i64 {
-        *match self {
-            Self::Up(v) => v,
-            Self::Down(v) => v,
-            Self::Right(v) => v,
-            Self::Left(v) => v,
-        }
+        self.distance
     }
 }
 
 impl TryFrom<(char, i64)> for Motion {
-    type Error = anyhow::Error;
-
-    fn try_from((ch, val): (char, i64)) -> Result<Self, Self::Error> {
-        match ch {
-            'U' => Ok(Self::Up(val)),
-            'D' => Ok(Self::Down(val)),
-            'R' => Ok(Self::Right(val)),
-            'L' => Ok(Self::Left(val)),
-            _ => bail!("Invalid direction: {}", ch),
-        }
+    type Error = InvalidDirection;
+
+    fn try_from((ch, distance): (char, i64)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            direction: ch.try_into()?,
+            distance,
+        })
     }
 }
 
@@ -92,12 +85,9 @@ impl<const N: usize> Default for Rope<N> {
 
 impl<const N: usize> Rope<N> {
     pub fn apply(&mut self, motion: &Motion, visited: &mut FxHashSet<Location>) {
-        match motion {
-            Motion::Up(v) => self.knots[0].y += v,
-            Motion::Down(v) => self.knots[0].y -= v,
-            Motion::Right(v) => self.knots[0].x += v,
-            Motion::Left(v) => self.knots[0].x -= v,
-        }
+        let delta = motion.direction.delta();
+        self.knots[0].x += delta.x * motion.distance;
+        self.knots[0].y += delta.y * motion.distance;
 
         'outer: loop {
             for cur in 1..N {
@@ -129,16 +119,26 @@ impl FromStr for RopeBridge {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, motions) = parse_motions(s).map_err(|e| e.to_owned())?;
+        let (_, motions) = parse_motions(s).map_err(|e| AocError::from_nom(s, e))?;
 
         Ok(Self { motions })
     }
 }
 
+const EXAMPLE: &str = "
+            R 5
+            U 8
+            L 8
+            D 3
+            R 17
+            D 10
+            L 25
+            U 20
+            ";
+
+#[aoc_problem(day = 9, title = "rope bridge", year = 2022, full_dataset = (6057, 2514))]
 impl Problem for RopeBridge {
-    const DAY: usize = 9;
-    const TITLE: &'static str = "rope bridge";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "88", "36")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -177,30 +177,6 @@ mod tests {
 
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = RopeBridge::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(6057, 2514));
-    }
-
-    #[test]
-    fn example() {
-        let input = "
-            R 5
-            U 8
-            L 8
-            D 3
-            R 17
-            D 10
-            L 25
-            U 20
-            ";
-        let solution = RopeBridge::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(88, 36));
-    }
-
     #[test]
     fn other() {
         let input = "
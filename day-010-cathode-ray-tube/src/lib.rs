@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, AocError, Example, Problem};
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::multispace0, multi::many1,
     sequence::preceded, IResult,
@@ -57,99 +57,12 @@ impl FromStr for CathodeRayTube {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, operations) = parse_opcodes(s).map_err(|e| e.to_owned())?;
+        let (_, operations) = parse_opcodes(s).map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { operations })
     }
 }
 
-impl Problem for CathodeRayTube {
-    const DAY: usize = 10;
-    const TITLE: &'static str = "cathode ray tube";
-    const README: &'static str = include_str!("../README.md");
-
-    type ProblemError = anyhow::Error;
-    type P1 = i64;
-    type P2 = String;
-
-    fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        let mut cycle = 1;
-        let mut breakpoint = 20;
-        let mut register = 1_i64;
-        let mut last_register = 1_i64;
-        let mut out = 0;
-
-        for op in self.operations.iter() {
-            cycle += op.num_cycles();
-
-            if let Opcode::Addx { val, .. } = op {
-                last_register = register;
-                register += val;
-            }
-
-            if cycle >= breakpoint {
-                if cycle == breakpoint {
-                    out += register * breakpoint;
-                } else {
-                    out += last_register * breakpoint;
-                }
-                breakpoint += 40;
-            }
-        }
-        Ok(out)
-    }
-
-    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        let mut pixels = String::with_capacity(240 + 6);
-        let mut program_counter = 0;
-        let mut op = self.operations[0];
-        let mut register = 1_i64;
-
-        for pixel in 0..240_i64 {
-            if pixel % 40 == 0 {
-                // this results in a leading newline, which I actually want
-                // because of the way I print the output with a leading 'part 2:'
-                pixels.push('\n');
-            }
-            let pos = pixel % 40;
-            if (register - pos).abs() <= 1 {
-                pixels.push('#');
-            } else {
-                pixels.push('.');
-            }
-
-            if op.done() {
-                if let Opcode::Addx { val, .. } = op {
-                    register += val;
-                }
-
-                program_counter += 1;
-                if program_counter < self.operations.len() {
-                    op = self.operations[program_counter];
-                }
-            }
-        }
-
-        Ok(pixels)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = CathodeRayTube::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(11720, "\n####.###...##..###..####.###...##....##.\n#....#..#.#..#.#..#.#....#..#.#..#....#.\n###..#..#.#....#..#.###..#..#.#.......#.\n#....###..#....###..#....###..#.......#.\n#....#.#..#..#.#.#..#....#....#..#.#..#.\n####.#..#..##..#..#.####.#.....##...##..".into()));
-    }
-
-    #[test]
-    fn example() {
-        let input = "
+const EXAMPLE: &str = "
             addx 15
             addx -11
             addx 6
@@ -297,7 +210,71 @@ mod tests {
             noop
             noop
             ";
-        let solution = CathodeRayTube::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(13140, "\n##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....".into()));
+
+#[aoc_problem(day = 10, title = "cathode ray tube", year = 2022, full_dataset = (11720, "####.###...##..###..####.###...##....##.\n#....#..#.#..#.#..#.#....#..#.#..#....#.\n###..#..#.#....#..#.###..#..#.#.......#.\n#....###..#....###..#....###..#.......#.\n#....#.#..#..#.#.#..#....#....#..#.#..#.\n####.#..#..##..#..#.####.#.....##...##.."))]
+impl Problem for CathodeRayTube {
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "13140", "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....")];
+
+    type ProblemError = anyhow::Error;
+    type P1 = i64;
+    type P2 = String;
+
+    fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+        let mut cycle = 1;
+        let mut breakpoint = 20;
+        let mut register = 1_i64;
+        let mut last_register = 1_i64;
+        let mut out = 0;
+
+        for op in self.operations.iter() {
+            cycle += op.num_cycles();
+
+            if let Opcode::Addx { val, .. } = op {
+                last_register = register;
+                register += val;
+            }
+
+            if cycle >= breakpoint {
+                if cycle == breakpoint {
+                    out += register * breakpoint;
+                } else {
+                    out += last_register * breakpoint;
+                }
+                breakpoint += 40;
+            }
+        }
+        Ok(out)
+    }
+
+    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
+        let mut pixels = String::with_capacity(240 + 5);
+        let mut program_counter = 0;
+        let mut op = self.operations[0];
+        let mut register = 1_i64;
+
+        for pixel in 0..240_i64 {
+            if pixel > 0 && pixel % 40 == 0 {
+                pixels.push('\n');
+            }
+            let pos = pixel % 40;
+            if (register - pos).abs() <= 1 {
+                pixels.push('#');
+            } else {
+                pixels.push('.');
+            }
+
+            if op.done() {
+                if let Opcode::Addx { val, .. } = op {
+                    register += val;
+                }
+
+                program_counter += 1;
+                if program_counter < self.operations.len() {
+                    op = self.operations[program_counter];
+                }
+            }
+        }
+
+        Ok(pixels)
     }
 }
@@ -1,16 +1,20 @@
 use std::{collections::VecDeque, str::FromStr};
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem,
+    math::lcm_all,
+    parsing::{blocks, field, unsigned},
+    AocError, Example, Problem,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{multispace0, multispace1},
-    multi::{many1, separated_list1},
+    multi::separated_list1,
     sequence::{delimited, preceded, tuple},
     IResult,
 };
-use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Operation {
@@ -76,16 +80,9 @@ impl Test {
 }
 
 fn parse_test(input: &str) -> IResult<&str, Test> {
-    let (input, denominator) =
-        preceded(tag("Test: divisible by "), nom::character::complete::u64)(input)?;
-    let (input, target_true) = preceded(
-        tuple((multispace1, tag("If true: throw to monkey "))),
-        nom::character::complete::u64,
-    )(input)?;
-    let (input, target_false) = preceded(
-        tuple((multispace1, tag("If false: throw to monkey "))),
-        nom::character::complete::u64,
-    )(input)?;
+    let (input, denominator) = field("Test: divisible by", unsigned)(input)?;
+    let (input, target_true) = field("If true: throw to monkey", unsigned)(input)?;
+    let (input, target_false) = field("If false: throw to monkey", unsigned)(input)?;
     Ok((
         input,
         Test {
@@ -141,10 +138,7 @@ impl Monkey {
 }
 
 fn parse_items(input: &str) -> IResult<&str, VecDeque<u64>> {
-    let (input, items) = preceded(
-        tag("Starting items: "),
-        separated_list1(tag(", "), nom::character::complete::u64),
-    )(input)?;
+    let (input, items) = separated_list1(tag(","), unsigned)(input)?;
     Ok((input, VecDeque::from(items)))
 }
 
@@ -154,9 +148,9 @@ fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
         nom::character::complete::u64,
         tag(":"),
     )(input)?;
-    let (input, items) = preceded(multispace1, parse_items)(input)?;
+    let (input, items) = field("Starting items:", parse_items)(input)?;
     let (input, operation) = preceded(multispace1, parse_operation)(input)?;
-    let (input, test) = preceded(multispace1, parse_test)(input)?;
+    let (input, test) = parse_test(input)?;
 
     Ok((
         input,
@@ -171,7 +165,7 @@ fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
 }
 
 fn parse_monkeys(input: &str) -> IResult<&str, Vec<Monkey>> {
-    preceded(multispace0, many1(parse_monkey))(input)
+    preceded(multispace0, blocks(parse_monkey))(input)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -196,15 +190,42 @@ impl FromStr for MonkeyInTheMiddle {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, monkeys) = parse_monkeys(s).map_err(|e| e.to_owned())?;
+        let (_, monkeys) = parse_monkeys(s).map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { monkeys })
     }
 }
 
+const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+#[aoc_problem(day = 11, title = "monkey in the middle", year = 2022, full_dataset = (95472, 17926061332))]
 impl Problem for MonkeyInTheMiddle {
-    const DAY: usize = 11;
-    const TITLE: &'static str = "monkey in the middle";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "10605", "2713310158")];
 
     type ProblemError = anyhow::Error;
     type P1 = u64;
@@ -225,9 +246,8 @@ impl Problem for MonkeyInTheMiddle {
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        let unique: FxHashSet<u64> = self.monkeys.iter().map(|m| m.test.denominator).collect();
-
-        let divisor: u64 = unique.iter().product();
+        // worry levels only matter modulo the tests' denominators
+        let divisor = lcm_all(self.monkeys.iter().map(|m| m.test.denominator));
 
         let mut working = self.clone();
         for _ in 0..10_000 {
@@ -241,52 +261,12 @@ impl Problem for MonkeyInTheMiddle {
         inspected.sort();
         Ok(inspected.pop().unwrap_or(0) * inspected.pop().unwrap_or(0))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = MonkeyInTheMiddle::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(95472, 17926061332));
-    }
-
-    #[test]
-    fn example() {
-        let input = "Monkey 0:
-  Starting items: 79, 98
-  Operation: new = old * 19
-  Test: divisible by 23
-    If true: throw to monkey 2
-    If false: throw to monkey 3
-
-Monkey 1:
-  Starting items: 54, 65, 75, 74
-  Operation: new = old + 6
-  Test: divisible by 19
-    If true: throw to monkey 2
-    If false: throw to monkey 0
-
-Monkey 2:
-  Starting items: 79, 60, 97
-  Operation: new = old * old
-  Test: divisible by 13
-    If true: throw to monkey 1
-    If false: throw to monkey 3
 
-Monkey 3:
-  Starting items: 74
-  Operation: new = old + 3
-  Test: divisible by 17
-    If true: throw to monkey 0
-    If false: throw to monkey 1";
-        let solution = MonkeyInTheMiddle::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(10605, 2713310158));
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let items: usize = self.monkeys.iter().map(|m| m.items.len()).sum();
+        vec![
+            ("monkeys", self.monkeys.len().to_string()),
+            ("items", items.to_string()),
+        ]
     }
 }
@@ -1,12 +1,15 @@
-use std::{collections::BinaryHeap, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_helpers::generic::{
-    pathing::{DASTNode, DNode, DefaultLocationCache},
-    prelude::*,
-    Grid, Location,
+use aoc_helpers::generic::{prelude::*, Grid, Location};
+use aoc_plumbing::{
+    aoc_problem,
+    bits::{char_to_num, try_char_to_num},
+    parsing::char_grid,
+    pathing::{astar, bfs_distances},
+    Example, Problem,
 };
-use aoc_plumbing::{bits::char_to_num, Problem};
+use rustc_hash::FxHashMap;
 
 const E_MARKER: u8 = 30;
 const S_MARKER: u8 = 44;
@@ -19,62 +22,30 @@ pub struct HillClimbingAlgorithm {
 
 impl HillClimbingAlgorithm {
     pub fn shortest_path(&self, begin: &Location, end: u8) -> Option<usize> {
-        let mut cache: DefaultLocationCache<usize> =
-            DefaultLocationCache::new(self.grid.size(), self.grid.cols());
-        let mut heap = BinaryHeap::new();
-
-        let start = DNode {
-            id: *begin,
-            cost: 0,
-        };
-        cache.cache_set(&start.id, 0);
-        heap.push(start);
-
-        while let Some(DNode { id, cost }) = heap.pop() {
-            // the unwrap is safe because we never insert anything not in the grid
-            let cur_val = self.grid.get(&id).unwrap();
-
-            if *cur_val == end {
-                return Some(cost);
-            }
-
-            if cost > cache.cache_get(&id) {
-                continue;
-            }
+        self.distances(begin)
+            .into_iter()
+            .filter(|(id, _)| self.grid.get(id) == Some(&end))
+            .map(|(_, steps)| steps)
+            .min()
+    }
 
-            // the unwrap is safe because we never insert anything not in the grid
-            let numeric_current = match *cur_val {
-                E_MARKER => char_to_num('z'),
-                S_MARKER => char_to_num('a'),
-                x => x,
-            };
-
-            for edge in id.orthogonal_neighbors() {
-                if let Some(neighbor_value) = self.grid.get(&edge) {
-                    let numeric_neighbor = match *neighbor_value {
-                        E_MARKER => char_to_num('z'),
-                        S_MARKER => char_to_num('a'),
-                        x => x,
-                    };
-
-                    if numeric_neighbor >= numeric_current
-                        || numeric_current - numeric_neighbor == 1
-                    {
-                        let next = DNode {
-                            id: edge,
-                            cost: cost + 1,
-                        };
-
-                        if next.cost < cache.cache_get(&next.id) {
-                            cache.cache_set(&next.id, next.cost);
-                            heap.push(next);
-                        }
-                    }
-                }
-            }
-        }
+    /// The number of steps from `begin` to every square that can be reached
+    /// from it, working backwards from the top.
+    pub fn distances(&self, begin: &Location) -> FxHashMap<Location, usize> {
+        bfs_distances([*begin], |id| self.climbable(id))
+    }
 
-        None
+    /// The neighbors of `id` that can be stepped to from it, working
+    /// backwards from the top: down at most one, or up any amount.
+    fn climbable(&self, id: &Location) -> Vec<Location> {
+        // the unwrap is safe because we never visit anything not in the grid
+        let current = height(*self.grid.get(id).unwrap());
+        id.orthogonal_neighbors()
+            .filter_map(|edge| {
+                let neighbor = height(*self.grid.get(&edge)?);
+                (neighbor >= current || current - neighbor == 1).then_some(edge)
+            })
+            .collect()
     }
 
     pub fn shortest_path_known_destination(
@@ -82,64 +53,23 @@ impl HillClimbingAlgorithm {
         begin: &Location,
         end: &Location,
     ) -> Option<usize> {
-        let mut cache: DefaultLocationCache<usize> =
-            DefaultLocationCache::new(self.grid.size(), self.grid.cols());
-        let mut heap = BinaryHeap::new();
-
-        let start = DASTNode {
-            id: *begin,
-            cost: 0,
-            path: 0,
-        };
-        cache.cache_set(&start.id, 0);
-        heap.push(start);
-
-        while let Some(DASTNode { id, cost, path }) = heap.pop() {
-            // the unwrap is safe because we never insert anything not in the grid
-            let cur_val = self.grid.get(&id).unwrap();
-
-            if id == *end {
-                return Some(path);
-            }
-
-            if cost > cache.cache_get(&id) {
-                continue;
-            }
-
-            // the unwrap is safe because we never insert anything not in the grid
-            let numeric_current = match *cur_val {
-                E_MARKER => char_to_num('z'),
-                S_MARKER => char_to_num('a'),
-                x => x,
-            };
-
-            for edge in id.orthogonal_neighbors() {
-                if let Some(neighbor_value) = self.grid.get(&edge) {
-                    let numeric_neighbor = match *neighbor_value {
-                        E_MARKER => char_to_num('z'),
-                        S_MARKER => char_to_num('a'),
-                        x => x,
-                    };
-
-                    if numeric_neighbor >= numeric_current
-                        || numeric_current - numeric_neighbor == 1
-                    {
-                        let next = DASTNode {
-                            id: edge,
-                            cost: cost + edge.manhattan_dist(end),
-                            path: path + 1,
-                        };
-
-                        if next.cost < cache.cache_get(&next.id) {
-                            cache.cache_set(&next.id, next.cost);
-                            heap.push(next);
-                        }
-                    }
-                }
-            }
-        }
+        astar(
+            *begin,
+            |id| self.climbable(id).into_iter().map(|edge| (edge, 1)),
+            |id| id.manhattan_dist(end),
+            |id| id == end,
+        )
+        .map(|(_, cost)| cost)
+    }
+}
 
-        None
+/// The height of a square, where the start and end have the heights of `a` and
+/// `z`.
+fn height(value: u8) -> u8 {
+    match value {
+        E_MARKER => char_to_num('z'),
+        S_MARKER => char_to_num('a'),
+        x => x,
     }
 }
 
@@ -147,16 +77,7 @@ impl FromStr for HillClimbingAlgorithm {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let vals = s
-            .trim()
-            .lines()
-            .map(|l| {
-                l.trim()
-                    .chars()
-                    .map(|ch| char_to_num(ch))
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
+        let vals = char_grid(s.trim().lines().map(str::trim), 0, try_char_to_num)?;
         let mut end = Location::default();
         'outer: for row in 0..vals.len() {
             for col in 0..vals[0].len() {
@@ -176,10 +97,17 @@ impl FromStr for HillClimbingAlgorithm {
     }
 }
 
+const EXAMPLE: &str = "
+            Sabqponm
+            abcryxxl
+            accszExk
+            acctuvwj
+            abdefghi
+            ";
+
+#[aoc_problem(day = 12, title = "hill climbing algorithm", year = 2022, full_dataset = (484, 478))]
 impl Problem for HillClimbingAlgorithm {
-    const DAY: usize = 12;
-    const TITLE: &'static str = "hill climbing algorithm";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "31", "29")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -206,31 +134,3 @@ impl Problem for HillClimbingAlgorithm {
             .ok_or_else(|| anyhow!("no path found"))
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = HillClimbingAlgorithm::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(484, 478));
-    }
-
-    #[test]
-    fn example() {
-        let input = "
-            Sabqponm
-            abcryxxl
-            accszExk
-            acctuvwj
-            abdefghi
-            ";
-        let solution = HillClimbingAlgorithm::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(31, 29));
-    }
-}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, AocError, Example, Problem};
 use nom::{
     branch::alt,
     character::complete::{self, multispace0, newline, space0},
@@ -98,7 +98,7 @@ impl FromStr for DistressSignal {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         #[cfg(not(feature = "par"))]
-        let (_, packet_pairs) = parse_packet_pairs(s).map_err(|e| e.to_owned())?;
+        let (_, packet_pairs) = parse_packet_pairs(s).map_err(|e| AocError::from_nom(s, e))?;
         #[cfg(feature = "par")]
         // There's a limitation with par_split that it doesn't split on a full pattern
         let packet_pairs = s
@@ -107,15 +107,39 @@ impl FromStr for DistressSignal {
             .par_split(':')
             .map(|g| parse_packet_pair(g).map(|(_, p)| p))
             .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| e.to_owned())?;
+            .map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { packet_pairs })
     }
 }
 
+const EXAMPLE: &str = "[1,1,3,1,1]
+            [1,1,5,1,1]
+
+            [[1],[2,3,4]]
+            [[1],4]
+
+            [9]
+            [[8,7,6]]
+
+            [[4,4],4,4]
+            [[4,4],4,4,4]
+
+            [7,7,7,7]
+            [7,7,7]
+
+            []
+            [3]
+
+            [[[]]]
+            [[]]
+
+            [1,[2,[3,[4,[5,6,7]]]],8,9]
+            [1,[2,[3,[4,[5,6,0]]]],8,9]
+            ";
+
+#[aoc_problem(day = 13, title = "distress signal", year = 2022, full_dataset = (5684, 22932))]
 impl Problem for DistressSignal {
-    const DAY: usize = 13;
-    const TITLE: &'static str = "distress signal";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "13", "140")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -170,48 +194,8 @@ impl Problem for DistressSignal {
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = DistressSignal::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(5684, 22932));
-    }
-
-    #[test]
-    fn example() {
-        let input = "[1,1,3,1,1]
-            [1,1,5,1,1]
-
-            [[1],[2,3,4]]
-            [[1],4]
-
-            [9]
-            [[8,7,6]]
-
-            [[4,4],4,4]
-            [[4,4],4,4,4]
-
-            [7,7,7,7]
-            [7,7,7]
-
-            []
-            [3]
-
-            [[[]]]
-            [[]]
-
-            [1,[2,[3,[4,[5,6,7]]]],8,9]
-            [1,[2,[3,[4,[5,6,0]]]],8,9]
-            ";
-        let solution = DistressSignal::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(13, 140));
-    }
-
     #[test]
     fn value_parsing() {
         let (_, _) = parse_value("[1,1,5,1,1]").unwrap();
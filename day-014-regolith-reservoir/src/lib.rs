@@ -1,7 +1,13 @@
 use std::{fmt::Display, str::FromStr};
 
 use aoc_helpers::generic::{prelude::GridLike, Bound2D, Grid, Location};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem,
+    geometry::{Bounds, Point},
+    grid::Render,
+    recursion::grow,
+    AocError, Example, Problem, Visualize,
+};
 use nom::{
     bytes::complete::tag, character::complete::multispace1, multi::separated_list1,
     sequence::separated_pair, IResult,
@@ -79,15 +85,37 @@ pub struct RegolithReservoir {
 
 impl Display for RegolithReservoir {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.locations.iter() {
-            writeln!(f, "{}", row.iter().map(|t| t.as_char()).collect::<String>())?;
-        }
-
-        Ok(())
+        write!(f, "{}", Render::new(&self.grid.locations, Tile::as_char))
     }
 }
 
 impl RegolithReservoir {
+    /// Drop a single grain of sand from the source, returning where it came to
+    /// rest, or `None` if it fell out of the bottom of the grid.
+    ///
+    /// This is far slower than [RegolithReservoir::fill] for the whole cave, but
+    /// lets the sand be watched piling up.
+    pub fn drop_grain(&mut self) -> Option<Location> {
+        let mut cur = self.source;
+
+        'falling: loop {
+            for next in [cur.south(), cur.south_west(), cur.south_east()] {
+                match next.and_then(|loc| self.grid.get(&loc).map(|t| (loc, *t))) {
+                    Some((loc, Tile::Air)) => {
+                        cur = loc;
+                        continue 'falling;
+                    }
+                    Some(_) => {}
+                    None => return None,
+                }
+            }
+
+            self.grid.set(&cur, Tile::Sand);
+            self.sand_count += 1;
+            return Some(cur);
+        }
+    }
+
     pub fn fill(&mut self) {
         let cur = self.source;
         self.fill_recur(&cur);
@@ -98,7 +126,7 @@ impl RegolithReservoir {
         match cur.south().and_then(|s| {
             self.grid.get(&s).copied().map(|t| {
                 if t == Tile::Air {
-                    self.fill_recur(&s)
+                    grow(|| self.fill_recur(&s))
                 } else {
                     t
                 }
@@ -152,7 +180,7 @@ impl RegolithReservoir {
                 // if the tile on the diagonal is air, recurse with the tile
                 // under that to fill beneath that tile
                 Some(Tile::Air) => {
-                    match self.fill_recur(&loc) {
+                    match grow(|| self.fill_recur(&loc)) {
                         Tile::Sand => {}
                         Tile::FlowingSand => {
                             return false;
@@ -188,7 +216,7 @@ impl RegolithReservoir {
         match cur.south().and_then(|s| {
             self.grid.get(&s).copied().map(|t| {
                 if t == Tile::Air {
-                    self.fill_infinite_recur(&s)
+                    grow(|| self.fill_infinite_recur(&s))
                 } else {
                     t
                 }
@@ -242,7 +270,7 @@ impl RegolithReservoir {
                 // if the tile on the diagonal is air, recurse with the tile
                 // under that to fill beneath that tile
                 Some(Tile::Air) => {
-                    match self.fill_infinite_recur(&loc) {
+                    match grow(|| self.fill_infinite_recur(&loc)) {
                         Tile::Sand => { /* nothing */ }
                         Tile::FlowingSand => {
                             return false;
@@ -276,37 +304,26 @@ impl FromStr for RegolithReservoir {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, paths) = paths_parser(s.trim()).map_err(|e| e.to_owned())?;
-
-        // calculate our actual bounds
-        let mut bounds: Bound2D<usize> = Bound2D::minmax();
-        for desc in paths.iter() {
-            for loc in desc.locations.iter() {
-                if loc.row < bounds.min_y {
-                    bounds.min_y = loc.row;
-                }
-
-                if loc.row > bounds.max_y {
-                    bounds.max_y = loc.row;
-                }
-
-                if loc.col < bounds.min_x {
-                    bounds.min_x = loc.col;
-                }
-
-                if loc.col > bounds.max_x {
-                    bounds.max_x = loc.col;
-                }
-            }
-        }
-
-        // reshape the bounds to accomodate the source
+        let (_, paths) = paths_parser(s.trim()).map_err(|e| AocError::from_nom(s, e))?;
+
+        // calculate our actual bounds, which have to include the source
+        let rock = Bounds::from_points(
+            paths
+                .iter()
+                .flat_map(|desc| desc.locations.iter())
+                .map(|loc| Point::new(loc.col as i64, loc.row as i64))
+                .chain([Point::new(500, 0)]),
+        )
+        // the unwrap is safe because the source is always there
+        .unwrap();
+
+        // reshape the bounds to leave room around the sides
         let bounds = Bound2D::new(
-            500.min(bounds.min_x) - 1, // we have to include the source
-            500.max(bounds.max_x) + 1, // we have to include the source
-            0,                         // we have to include the source
-            bounds.max_y + 1,          // this ended up being very fortunate
-                                       // as part 2 wanted an extra row
+            rock.min.x as usize - 1,
+            rock.max.x as usize + 1,
+            0,
+            rock.max.y as usize + 1, // this ended up being very fortunate
+                                     // as part 2 wanted an extra row
         );
 
         let mut grid = Grid::new(vec![vec![Tile::Air; bounds.width()]; bounds.height()]);
@@ -331,10 +348,22 @@ impl FromStr for RegolithReservoir {
     }
 }
 
+impl Visualize for RegolithReservoir {
+    /// The cave as each grain of sand from part one comes to rest.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let mut working = self.clone();
+        Box::new(std::iter::from_fn(move || {
+            working.drop_grain().map(|_| working.to_string())
+        }))
+    }
+}
+
+const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+
+#[aoc_problem(day = 14, title = "regolith reservoir", year = 2022, full_dataset = (1001, 27976))]
 impl Problem for RegolithReservoir {
-    const DAY: usize = 14;
-    const TITLE: &'static str = "regolith reservoir";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "24", "93")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -376,23 +405,15 @@ impl Problem for RegolithReservoir {
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = RegolithReservoir::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1001, 27976));
-    }
-
-    #[test]
-    fn example() {
-        let input = "498,4 -> 498,6 -> 496,6
-503,4 -> 502,4 -> 502,9 -> 494,9";
-        let solution = RegolithReservoir::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(24, 93));
+    fn frames() {
+        let inst = RegolithReservoir::instance(EXAMPLE).unwrap();
+        let frames = inst.frames().collect::<Vec<_>>();
+        assert_eq!(frames.len(), 24);
+        assert_eq!(frames[0].matches('o').count(), 1);
+        assert_eq!(frames[23].matches('o').count(), 24);
+        assert!(frames[23].lines().nth(2).unwrap().contains("..o.."));
     }
 }
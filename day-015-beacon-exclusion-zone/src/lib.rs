@@ -1,8 +1,12 @@
-use std::{collections::VecDeque, hash::Hash, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::bail;
-use aoc_helpers::generic::Bound2D;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem,
+    geometry::{self, Bounds, Point},
+    interval::{Interval, IntervalSet},
+    AocError, Example, Expected, Problem,
+};
 use nom::{
     bytes::complete::tag,
     character::complete::newline,
@@ -12,59 +16,6 @@ use nom::{
 };
 use rustc_hash::FxHashMap;
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-pub struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    pub fn manhattan_distance(&self, other: &Self) -> i64 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
-
-impl Hash for Point {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        state.write_i64(self.x * 4_000_000 + self.y)
-    }
-}
-
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
-pub struct Line {
-    sensor_id: usize,
-    positive_slope: bool,
-    y_intersect: i64,
-}
-
-impl Line {
-    pub fn intersection(&self, other: &Self) -> Option<Point> {
-        if self.sensor_id == other.sensor_id {
-            return None;
-        }
-
-        if self.positive_slope != other.positive_slope && self != other {
-            if self.positive_slope {
-                let delta = other.y_intersect - self.y_intersect;
-                if delta % 2 == 0 {
-                    let x = delta / 2;
-                    let y = x + self.y_intersect;
-                    return Some(Point { x, y });
-                }
-            } else {
-                let delta = self.y_intersect - other.y_intersect;
-                if delta % 2 == 0 {
-                    let x = delta / 2;
-                    let y = x + other.y_intersect;
-                    return Some(Point { x, y });
-                }
-            }
-        }
-
-        None
-    }
-}
-
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
 pub struct Sensor {
     location: Point,
@@ -82,42 +33,15 @@ impl Sensor {
 
         let spillover = self.dist_to_closest - delta;
 
-        Some(Segment {
-            start: self.location.x - spillover,
-            end: self.location.x + spillover,
-        })
+        Some(Segment::new(
+            self.location.x - spillover,
+            self.location.x + spillover,
+        ))
     }
 
-    /// Generate lines parallel to our sensor range but one unit outside of range
-    pub fn add_lines(&self, id: usize, lines: &mut Vec<Line>) {
-        let offset = self.dist_to_closest + 1;
-        let p1_x = self.location.x + offset;
-        let p1_y = self.location.y;
-        let a = p1_y - p1_x;
-        lines.push(Line {
-            sensor_id: id,
-            positive_slope: true,
-            y_intersect: a,
-        });
-        lines.push(Line {
-            sensor_id: id,
-            positive_slope: true,
-            y_intersect: a + 2 * offset,
-        });
-
-        let p1_x = self.location.x - offset;
-        let p1_y = self.location.y;
-        let a = p1_y + p1_x;
-        lines.push(Line {
-            sensor_id: id,
-            positive_slope: false,
-            y_intersect: a,
-        });
-        lines.push(Line {
-            sensor_id: id,
-            positive_slope: false,
-            y_intersect: a + 2 * offset,
-        });
+    /// The edges of the diamond just one unit outside of our sensor range.
+    pub fn border(&self) -> [geometry::Segment; 4] {
+        geometry::Segment::diamond_border(&self.location, self.dist_to_closest + 1)
     }
 }
 
@@ -151,35 +75,7 @@ fn parse_sensors(input: &str) -> IResult<&str, Vec<Sensor>> {
     separated_list1(newline, parse_sensor)(input)
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-pub struct Segment {
-    start: i64,
-    end: i64,
-}
-
-impl Segment {
-    pub fn overlaps(&self, other: &Self) -> bool {
-        self.start <= other.start && self.end >= other.start
-            || other.start <= self.start && other.end >= self.start
-            || self.start >= other.start && self.end <= other.end
-            || other.start >= self.start && other.end <= self.end
-    }
-
-    pub fn merge(&self, other: &Self) -> Option<Self> {
-        if !self.overlaps(other) {
-            None
-        } else {
-            Some(Self {
-                start: self.start.min(other.start),
-                end: self.end.max(other.end),
-            })
-        }
-    }
-
-    pub fn len(&self) -> i64 {
-        (self.end - self.start).abs()
-    }
-}
+pub type Segment = Interval<i64>;
 
 /// Generic over N and M so that we can run the example tests.
 ///
@@ -187,38 +83,21 @@ impl Segment {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct BeaconExclusionZoneGen<const N: i64, const M: i64> {
     sensors: Vec<Sensor>,
-    bounds: Bound2D<i64>,
+    bounds: Bounds,
 }
 
 impl<const N: i64, const M: i64> FromStr for BeaconExclusionZoneGen<N, M> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, mut sensors) = parse_sensors(s.trim()).map_err(|e| e.to_owned())?;
-
-        let mut bounds = Bound2D::minmax();
-
-        for s in sensors.iter() {
-            let adjusted_min_x = (s.location.x - s.dist_to_closest).min(s.closest_beacon.x);
-            let adjusted_max_x = (s.location.x + s.dist_to_closest).max(s.closest_beacon.x);
-            let adjusted_min_y = (s.location.y - s.dist_to_closest).min(s.closest_beacon.y);
-            let adjusted_max_y = (s.location.y + s.dist_to_closest).max(s.closest_beacon.y);
-            if adjusted_max_x > bounds.max_x {
-                bounds.max_x = adjusted_max_x;
-            }
-
-            if adjusted_min_x < bounds.min_x {
-                bounds.min_x = adjusted_min_x;
-            }
-
-            if adjusted_max_y > bounds.max_y {
-                bounds.max_y = adjusted_max_y;
-            }
+        let (_, mut sensors) = parse_sensors(s.trim()).map_err(|e| AocError::from_nom(s, e))?;
 
-            if adjusted_min_y < bounds.min_y {
-                bounds.min_y = adjusted_min_y;
-            }
-        }
+        // everything any sensor can see, which includes its closest beacon
+        let bounds = sensors
+            .iter()
+            .map(|s| Bounds::around(&s.location).expand(s.dist_to_closest))
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_default();
 
         sensors.sort_by(|a, b| a.location.x.cmp(&b.location.x));
 
@@ -226,67 +105,87 @@ impl<const N: i64, const M: i64> FromStr for BeaconExclusionZoneGen<N, M> {
     }
 }
 
+const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+#[aoc_problem(day = 15, title = "beacon exclusion zone")]
 impl<const N: i64, const M: i64> Problem for BeaconExclusionZoneGen<N, M> {
-    const DAY: usize = 15;
-    const TITLE: &'static str = "beacon exclusion zone";
-    const README: &'static str = include_str!("../README.md");
+    // the examples only make sense for the small version of the problem
+    const EXAMPLES: &'static [Example] = if N == 10 && M == 20 {
+        &[Example::new(EXAMPLE, "26", "56000011")]
+    } else {
+        &[]
+    };
+    // only the full-sized problem was solved for input.txt
+    const EXPECTED: Option<Expected> = if N == 2_000_000 && M == 4_000_000 {
+        Some(Expected::new("4873353", "11600823139120"))
+    } else {
+        None
+    };
 
     type ProblemError = anyhow::Error;
     type P1 = i64;
     type P2 = i64;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        // we know because of the limitations of the problem in part 2 that
-        // we don't have to worry aobut multiple candidates.
-        let mut segments = self
+        let covered = self
             .sensors
             .iter()
             .filter_map(|s| s.segment_for(N))
-            .collect::<VecDeque<_>>();
-
-        'reducer: loop {
-            if let Some(cur) = segments.pop_front() {
-                for i in 0..segments.len() {
-                    if let Some(merged) = cur.merge(&segments[i]) {
-                        segments[i] = merged;
-                        continue 'reducer;
-                    }
-                }
-                // we didn't find anything and we
-                // we need to put ourselves back
-                segments.push_back(cur);
+            .collect::<IntervalSet<_>>();
 
-                if segments.len() > 2 {
-                    continue 'reducer;
-                }
-            }
-            break;
-        }
+        // the known beacons on the target row are not positions where a
+        // beacon cannot be present
+        let mut beacons = self
+            .sensors
+            .iter()
+            .filter(|s| s.closest_beacon.y == N)
+            .map(|s| s.closest_beacon.x)
+            .collect::<Vec<_>>();
+        beacons.sort_unstable();
+        beacons.dedup();
 
-        let sum: i64 = segments.iter().map(|s| s.len()).sum();
-        Ok(sum)
+        Ok(covered.len() - beacons.len() as i64)
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        // there are 4 lines that lie just beyond the borders of every diamond
+        // there are 4 edges that lie just beyond the borders of every diamond
         // region the sensors can see. If we calculate the intersection of all
-        // of these lines, then we know the only possible points that could be
+        // of these edges, then we know the only possible points that could be
         // candidates for the beacon that satisfies the search criteria.
         //
-        // We know the beacon must lie on N of the lines because if it were
-        // possible for the beacon to not be on one of these lines, there would
+        // We know the beacon must lie on N of the edges because if it were
+        // possible for the beacon to not be on one of these edges, there would
         // be multiple solutions instead of a unique one.
-        let mut lines = Vec::with_capacity(self.sensors.len() * 4);
+        let mut edges = Vec::with_capacity(self.sensors.len() * 4);
         for (i, sensor) in self.sensors.iter().enumerate() {
-            sensor.add_lines(i, &mut lines);
+            edges.extend(sensor.border().into_iter().map(|edge| (i, edge)));
         }
 
-        // now we can find the intersections of all the lines
+        // now we can find the intersections of all the edges, other than the
+        // corners of the same sensor's diamond
+        let search = Bounds::new(Point::new(0, 0), Point::new(M, M));
         let mut intersections: FxHashMap<Point, i64> = FxHashMap::default();
-        while let Some(line) = lines.pop() {
-            'intersector: for other in lines.iter() {
-                if let Some(pt) = line.intersection(other) {
-                    if pt.x >= 0 && pt.x <= M && pt.y >= 0 && pt.y <= M {
+        while let Some((id, edge)) = edges.pop() {
+            'intersector: for (other_id, other) in edges.iter() {
+                if id == *other_id {
+                    continue;
+                }
+
+                if let Some(pt) = edge.intersection(other) {
+                    if search.contains(&pt) {
                         let e = intersections.entry(pt).or_default();
                         *e += 1;
 
@@ -318,37 +217,19 @@ impl<const N: i64, const M: i64> Problem for BeaconExclusionZoneGen<N, M> {
 /// We expose this type for the actual solver and such.
 pub type BeaconExclusionZone = BeaconExclusionZoneGen<2_000_000, 4_000_000>;
 
+aoc_plumbing::register!(BeaconExclusionZone, 2022);
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
     #[ignore]
     fn full_dataset() {
         let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = BeaconExclusionZone::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(4873353, 11600823139120));
+        let diff = BeaconExclusionZone::validate(&input).unwrap().diff();
+        assert!(diff.is_empty(), "wrong answer(s) for input.txt\n{}", diff);
     }
 
-    #[test]
-    fn example() {
-        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-Sensor at x=9, y=16: closest beacon is at x=10, y=16
-Sensor at x=13, y=2: closest beacon is at x=15, y=3
-Sensor at x=12, y=14: closest beacon is at x=10, y=16
-Sensor at x=10, y=20: closest beacon is at x=10, y=16
-Sensor at x=14, y=17: closest beacon is at x=10, y=16
-Sensor at x=8, y=7: closest beacon is at x=2, y=10
-Sensor at x=2, y=0: closest beacon is at x=2, y=10
-Sensor at x=0, y=11: closest beacon is at x=2, y=10
-Sensor at x=20, y=14: closest beacon is at x=25, y=17
-Sensor at x=17, y=20: closest beacon is at x=21, y=22
-Sensor at x=16, y=7: closest beacon is at x=15, y=3
-Sensor at x=14, y=3: closest beacon is at x=15, y=3
-Sensor at x=20, y=1: closest beacon is at x=15, y=3";
-        let solution = BeaconExclusionZoneGen::<10, 20>::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(26, 56000011));
-    }
+    aoc_plumbing::example_tests!(BeaconExclusionZoneGen<10, 20>);
 }
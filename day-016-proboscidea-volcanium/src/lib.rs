@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem, cancellation::checkpoint, memo::Memo, pathing::all_pairs_shortest_paths,
+    progress::tick, AocError, Example, Problem,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,6 +14,7 @@ use nom::{
     IResult,
 };
 use rustc_hash::FxHashMap;
+use tracing::{debug, instrument};
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct Edge {
@@ -163,7 +167,8 @@ pub struct ProboscideaVolcanium {
 }
 
 impl ProboscideaVolcanium {
-    pub fn optimal_path(&self, minutes: i64, cache: &mut FxHashMap<(usize, u64), i64>) -> i64 {
+    #[instrument(skip(self, cache))]
+    pub fn optimal_path(&self, minutes: i64, cache: &mut Memo<(usize, u64), i64>) -> i64 {
         let mut best = 0;
         let mut cur = Explore {
             cur: self.aa_index,
@@ -171,6 +176,7 @@ impl ProboscideaVolcanium {
             ..Default::default()
         };
         self.optimal_path_recur(&mut cur, 0, &mut best, cache);
+        debug!(best, cached = cache.len(), "explored paths");
         best
     }
 
@@ -179,7 +185,7 @@ impl ProboscideaVolcanium {
         cur: &Explore,
         cur_best: i64,
         best: &mut i64,
-        cache: &mut FxHashMap<(usize, u64), i64>,
+        cache: &mut Memo<(usize, u64), i64>,
     ) {
         if cur_best > *best {
             *best = cur_best;
@@ -190,14 +196,10 @@ impl ProboscideaVolcanium {
             return;
         }
 
-        if let Some(old) = cache.get(&(cur.cur, cur.seen)) {
-            if *old > cur_best {
-                return;
-            }
+        if !cache.keep_max((cur.cur, cur.seen), cur_best) {
+            return;
         }
 
-        cache.insert((cur.cur, cur.seen), cur_best);
-
         // we can pick a valve to move to, opening that valve in the process
         // we have to iterate this way to avoid the mutable/immutable borrow
         for v in self.nonzero_valves.iter() {
@@ -224,7 +226,8 @@ impl ProboscideaVolcanium {
         }
     }
 
-    pub fn find_best_disjoint_pair(&self, path_cache: &FxHashMap<(usize, u64), i64>) -> i64 {
+    #[instrument(skip_all, fields(paths = path_cache.len()))]
+    pub fn find_best_disjoint_pair(&self, path_cache: &Memo<(usize, u64), i64>) -> i64 {
         let mut best = i64::MIN;
 
         // there's a special case where we were able to open all the valves
@@ -235,7 +238,10 @@ impl ProboscideaVolcanium {
             .collect::<Vec<_>>();
         ordered.sort_by(|a, b| a.1.cmp(&b.1));
 
+        let paths = ordered.len();
         while let Some(((_, valve_map), total)) = ordered.pop() {
+            tick("paths", paths - ordered.len(), paths);
+
             // we know the list is sorted, so the total we have is the largest
             // total remaining, so if we (x2) can't beat the best score so far,
             // there is no point looking at the rest of the list.
@@ -255,6 +261,9 @@ impl ProboscideaVolcanium {
             }
         }
 
+        // the rest can't beat the best, so they're as good as done
+        tick("paths", paths, paths);
+
         best
     }
 }
@@ -263,7 +272,7 @@ impl FromStr for ProboscideaVolcanium {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, raw_valves) = parse_valves(s.trim()).map_err(|e| e.to_owned())?;
+        let (_, raw_valves) = parse_valves(s.trim()).map_err(|e| AocError::from_nom(s, e))?;
 
         // make a temporary name -> idx map and a list of the nonzero valves
         let mut valves_map = FxHashMap::default();
@@ -285,24 +294,13 @@ impl FromStr for ProboscideaVolcanium {
             .collect::<Result<Vec<_>, _>>()?;
 
         // calculate shortest paths to every node
-        let mut shortest_paths = vec![vec![i64::MAX / 4; valves.len()]; valves.len()];
-
+        let mut adjacency = vec![vec![i64::MAX; valves.len()]; valves.len()];
         for v in valves.iter() {
-            shortest_paths[v.index][v.index] = 0;
-
             for other in v.tunnels.iter() {
-                shortest_paths[v.index][*other] = 1;
-            }
-        }
-
-        for k in 0..valves.len() {
-            for i in 0..valves.len() {
-                for j in 0..valves.len() {
-                    shortest_paths[i][j] =
-                        shortest_paths[i][j].min(shortest_paths[i][k] + shortest_paths[k][j]);
-                }
+                adjacency[v.index][*other] = 1;
             }
         }
+        let shortest_paths = all_pairs_shortest_paths(adjacency);
 
         let mut all_open = 0;
         for v in nonzero_valves.iter() {
@@ -319,57 +317,49 @@ impl FromStr for ProboscideaVolcanium {
     }
 }
 
+const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+#[aoc_problem(day = 16, title = "proboscidea volcanium", year = 2022, full_dataset = (1376, 1933))]
 impl Problem for ProboscideaVolcanium {
-    const DAY: usize = 16;
-    const TITLE: &'static str = "proboscidea volcanium";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "1651", "1707")];
 
     type ProblemError = anyhow::Error;
     type P1 = i64;
     type P2 = i64;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        let mut cache = FxHashMap::default();
+        let mut cache = Memo::default();
         Ok(self.optimal_path(30, &mut cache))
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        let mut cache = FxHashMap::default();
+        let mut cache = Memo::default();
         self.optimal_path(26, &mut cache);
+        checkpoint()?;
         Ok(self.find_best_disjoint_pair(&cache))
     }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("valves", self.valves.len().to_string()),
+            ("nonzero valves", self.nonzero_valves.len().to_string()),
+        ]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = ProboscideaVolcanium::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1376, 1933));
-    }
-
-    #[test]
-    fn example() {
-        let input = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-Valve BB has flow rate=13; tunnels lead to valves CC, AA
-Valve CC has flow rate=2; tunnels lead to valves DD, BB
-Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
-Valve EE has flow rate=3; tunnels lead to valves FF, DD
-Valve FF has flow rate=0; tunnels lead to valves EE, GG
-Valve GG has flow rate=0; tunnels lead to valves FF, HH
-Valve HH has flow rate=22; tunnel leads to valve GG
-Valve II has flow rate=0; tunnels lead to valves AA, JJ
-Valve JJ has flow rate=21; tunnel leads to valve II";
-        let solution = ProboscideaVolcanium::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(1651, 1707));
-    }
-
     #[test]
     fn explore() {
         let mut e = Explore {
@@ -1,8 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
 use anyhow::bail;
-use aoc_plumbing::Problem;
-use rustc_hash::FxHashMap;
+use aoc_plumbing::{aoc_problem, cycle::CycleDetector, Example, Problem, Visualize};
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct Point {
@@ -44,6 +43,18 @@ const VERTICAL: [u8; 4] = [0b1, 0b1, 0b1, 0b1];
 
 const CORNER: [u8; 3] = [0b111, 0b001, 0b001];
 
+/// The order the rocks fall in.
+const SHAPES: [Shape; 5] = [
+    Shape::Horizontal,
+    Shape::Plus,
+    Shape::Corner,
+    Shape::Vertical,
+    Shape::Square,
+];
+
+/// How many of the top rows of the chamber are visualized.
+const VISIBLE_ROWS: usize = 40;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Shape {
     Plus,
@@ -212,61 +223,71 @@ pub struct Chamber {
 impl Chamber {
     pub fn drop_rocks(&mut self, num: usize, jets: &Vec<Jet>) -> usize {
         let mut highest = 0;
-        let mut shapes = [
-            Shape::Horizontal,
-            Shape::Plus,
-            Shape::Corner,
-            Shape::Vertical,
-            Shape::Square,
-        ]
-        .iter()
-        .cycle();
+        let mut shapes = SHAPES.iter().cycle();
         let mut jets_iter = jets.iter().cycle();
 
         for i in 0..num {
             let y = if i == 0 { 3 } else { highest + 4 };
-
-            let shape = *shapes.next().unwrap();
-            let location = Point {
-                x: 6 - shape.width() - 1,
-                y,
-            };
-            while self.rows.len() < location.y + shape.height() {
-                self.rows.push(0);
+            let candidate = self.drop_rock(*shapes.next().unwrap(), y, &mut jets_iter);
+            if candidate > highest {
+                highest = candidate;
             }
-            let mut rock = Rock::new(location, shape);
+        }
 
-            while let Some(jet) = jets_iter.next() {
-                rock.move_jet(jet, &self.rows);
-                // we can't move down because 0
-                if !rock.move_down(&self.rows) {
-                    let candidate = rock.add_points(&mut self.rows);
-                    if candidate > highest {
-                        highest = candidate;
-                    }
-                    break;
-                }
+        highest + 1
+    }
+
+    /// Drops a single rock from row `y` until it comes to rest, returning the
+    /// highest row it occupies.
+    pub fn drop_rock<'a>(
+        &mut self,
+        shape: Shape,
+        y: usize,
+        jets: &mut impl Iterator<Item = &'a Jet>,
+    ) -> usize {
+        let location = Point {
+            x: 6 - shape.width() - 1,
+            y,
+        };
+        while self.rows.len() < location.y + shape.height() {
+            self.rows.push(0);
+        }
+        let mut rock = Rock::new(location, shape);
+
+        for jet in jets {
+            rock.move_jet(jet, &self.rows);
+            // we can't move down because 0
+            if !rock.move_down(&self.rows) {
+                break;
             }
         }
 
-        highest + 1
+        rock.add_points(&mut self.rows)
+    }
+
+    /// Renders at most the top `num` non-empty rows.
+    pub fn render_top(&self, num: usize) -> String {
+        let occupied = self
+            .rows
+            .iter()
+            .rposition(|row| *row > 0)
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+
+        let mut out = String::with_capacity((num + 1) * 10);
+        for row in self.rows[..occupied].iter().rev().take(num) {
+            out.push_str(&render_row(*row));
+        }
+        out.push_str("+-------+\n");
+        out
     }
 
     pub fn detect_cycle(&mut self, jets: &Vec<Jet>) -> usize {
         let mut highest = 0;
-        let mut shapes = [
-            Shape::Horizontal,
-            Shape::Plus,
-            Shape::Corner,
-            Shape::Vertical,
-            Shape::Square,
-        ]
-        .iter()
-        .enumerate()
-        .cycle();
+        let mut shapes = SHAPES.iter().enumerate().cycle();
         let mut jets_iter = jets.iter().enumerate().cycle();
 
-        let mut states: FxHashMap<State, (usize, usize)> = FxHashMap::default();
+        let mut detector = CycleDetector::new();
         let mut i = 0;
         loop {
             let y = if i == 0 { 3 } else { highest + 4 };
@@ -293,36 +314,20 @@ impl Chamber {
                     if i > 16 {
                         let state = State::new(shape_idx, jet_idx, &self.rows);
 
-                        let e = states.entry(state).or_insert_with(|| (i, highest));
-                        // if we didn't just insert this entry
-                        if e.0 != i {
-                            // figure out how many iterations between now and
-                            // the previous time was saw this state. This should
-                            // be the period that we expect to see these states
-                            // again
-                            let period = i - e.0;
-
-                            // we want to make sure we're "aligned" with respect
-                            // to the total iterations, since the first full
-                            // period may not have begun at 0. This allows us
-                            // to have the property that if x % n == y % n, then
-                            // (max(x,y) - min(x,y)) % n == 0. This is maybe not
-                            // ideal, because we _could_ just set our state to
-                            // be where we'd end up then simulate the remainder
-                            // but I didn't want to have to deal with the edges
-                            // there.
-                            if 1_000_000_000_000 % period == i % period {
-                                // we know that the current height minus the
-                                // previously recorded height for this state is
-                                // the height gain per period
-                                let hg = highest - e.1;
-
-                                // we can do this because the above property
-                                // holds where 1_000_000_000_000 - i is evenly
-                                // divisible by the period.
-                                let rem = (1_000_000_000_000 - i) / period;
-                                return highest + rem * hg;
-                            }
+                        // we want to make sure we're "aligned" with respect
+                        // to the total iterations, since the first full
+                        // period may not have begun at 0, so the cycle can
+                        // only tell us the final height once the remaining
+                        // rocks are a whole number of periods. This is maybe
+                        // not ideal, because we _could_ just set our state to
+                        // be where we'd end up then simulate the remainder
+                        // but I didn't want to have to deal with the edges
+                        // there.
+                        if let Some(height) = detector
+                            .observe(state, i, highest)
+                            .and_then(|cycle| cycle.value_at(1_000_000_000_000))
+                        {
+                            return height;
                         }
                     }
 
@@ -338,25 +343,28 @@ impl Chamber {
 impl Display for Chamber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in self.rows.iter().rev() {
-            let mut buf = String::with_capacity(10);
-            buf.push('|');
-            for shift in (0..7).rev() {
-                let mask = 1 << shift;
-                if row & mask > 0 {
-                    buf.push('#');
-                } else {
-                    buf.push(' ');
-                }
-            }
-            buf.push('|');
-            buf.push('\n');
-
-            buf.fmt(f)?;
+            render_row(*row).fmt(f)?;
         }
         writeln!(f, "+-------+")
     }
 }
 
+fn render_row(row: u8) -> String {
+    let mut buf = String::with_capacity(10);
+    buf.push('|');
+    for shift in (0..7).rev() {
+        let mask = 1 << shift;
+        if row & mask > 0 {
+            buf.push('#');
+        } else {
+            buf.push(' ');
+        }
+    }
+    buf.push('|');
+    buf.push('\n');
+    buf
+}
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
 pub struct State {
     shape_idx: usize,
@@ -403,10 +411,11 @@ impl FromStr for PyroclasticFlow {
     }
 }
 
+const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+#[aoc_problem(day = 17, title = "pyroclastic flow", year = 2022, full_dataset = (3166, 1577207977186))]
 impl Problem for PyroclasticFlow {
-    const DAY: usize = 17;
-    const TITLE: &'static str = "pyroclastic flow";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "3068", "1514285714288")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -423,26 +432,38 @@ impl Problem for PyroclasticFlow {
         let highest = working.detect_cycle(&self.jets);
         Ok(highest)
     }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![("jets", self.jets.len().to_string())]
+    }
+}
+
+impl Visualize for PyroclasticFlow {
+    /// The top of the chamber as each of the part one rocks comes to rest.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let mut chamber = self.chamber.clone();
+        let mut shapes = SHAPES.iter().cycle();
+        let mut jets = self.jets.iter().cycle();
+        let mut highest = 0;
+
+        Box::new((0..2022).map(move |i| {
+            let y = if i == 0 { 3 } else { highest + 4 };
+            highest = highest.max(chamber.drop_rock(*shapes.next().unwrap(), y, &mut jets));
+            chamber.render_top(VISIBLE_ROWS)
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = PyroclasticFlow::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(3166, 1577207977186));
-    }
-
-    #[test]
-    fn example() {
-        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-        let solution = PyroclasticFlow::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(3068, 1514285714288));
+    fn frames() {
+        let flow = PyroclasticFlow::instance(EXAMPLE).unwrap();
+        let frames = flow.frames().collect::<Vec<_>>();
+        assert_eq!(frames.len(), 2022);
+        assert_eq!(frames[0], "|  #### |\n+-------+\n");
+        assert_eq!(frames[1].lines().count(), 5);
     }
 }
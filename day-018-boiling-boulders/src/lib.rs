@@ -1,7 +1,17 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
-use nom::{character::complete::multispace1, multi::separated_list1, sequence::tuple, IResult};
+use aoc_plumbing::{
+    aoc_problem,
+    geometry::Point3,
+    parsing::{separated_lines, signed},
+    pathing::bfs_distances,
+    Alternatives, AocError, Example, Implementation, Problem,
+};
+use nom::{
+    character::complete::{char, multispace0},
+    sequence::{preceded, tuple},
+    IResult,
+};
 use rustc_hash::FxHashSet;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
@@ -26,7 +36,7 @@ impl Bounds {
         }
     }
 
-    pub fn does_not_contain(&self, cube: &Cube) -> bool {
+    pub fn does_not_contain(&self, cube: &Point3) -> bool {
         cube.x > self.max_x
             || cube.x < self.min_x
             || cube.y > self.max_y
@@ -36,52 +46,20 @@ impl Bounds {
     }
 }
 
-const NEIGHBORS: [(i64, i64, i64); 6] = [
-    (0, 0, 1),
-    (0, 1, 0),
-    (1, 0, 0),
-    (0, 0, -1),
-    (0, -1, 0),
-    (-1, 0, 0),
-];
+fn parse_cube(input: &str) -> IResult<&str, Point3> {
+    let (input, (x, _, y, _, z)) = tuple((signed, char(','), signed, char(','), signed))(input)?;
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
-pub struct Cube {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl Cube {
-    pub fn neighbors(&self) -> impl Iterator<Item = Cube> + '_ {
-        NEIGHBORS.iter().map(|(dx, dy, dz)| Cube {
-            x: self.x + dx,
-            y: self.y + dy,
-            z: self.z + dz,
-        })
-    }
+    Ok((input, Point3::new(x, y, z)))
 }
 
-fn parse_cube(input: &str) -> IResult<&str, Cube> {
-    let (input, (x, _, y, _, z)) = tuple((
-        nom::character::complete::i64,
-        nom::character::complete::char(','),
-        nom::character::complete::i64,
-        nom::character::complete::char(','),
-        nom::character::complete::i64,
-    ))(input)?;
-
-    Ok((input, Cube { x, y, z }))
-}
-
-fn parse_cubes(input: &str) -> IResult<&str, Vec<Cube>> {
-    separated_list1(multispace1, parse_cube)(input)
+fn parse_cubes(input: &str) -> IResult<&str, Vec<Point3>> {
+    preceded(multispace0, separated_lines(parse_cube))(input)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct BoilingBoulders {
     // this is going to be slow
-    cubes: FxHashSet<Cube>,
+    cubes: FxHashSet<Point3>,
     bounds: Bounds,
 }
 
@@ -89,7 +67,7 @@ impl FromStr for BoilingBoulders {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, raw_cubes) = parse_cubes(s).map_err(|e| e.to_owned())?;
+        let (_, raw_cubes) = parse_cubes(s).map_err(|e| AocError::from_nom(s, e))?;
 
         let mut bounds = Bounds::minmax();
         let mut cubes = FxHashSet::default();
@@ -134,12 +112,29 @@ impl FromStr for BoilingBoulders {
 
 impl BoilingBoulders {
     pub fn outer_surface(&self) -> usize {
+        // flood the air around the droplet from a corner of the bounds, which
+        // are padded so the air reaches every side, then count the faces
+        // touching it
+        let start = Point3::new(self.bounds.min_x, self.bounds.min_y, self.bounds.min_z);
+        let outside = bfs_distances([start], |cube| {
+            cube.orthogonal_neighbors()
+                .filter(|n| !self.bounds.does_not_contain(n) && !self.cubes.contains(n))
+                .collect::<Vec<_>>()
+        });
+
+        self.cubes
+            .iter()
+            .map(|cube| {
+                cube.orthogonal_neighbors()
+                    .filter(|n| outside.contains_key(n))
+                    .count()
+            })
+            .sum()
+    }
+
+    pub fn outer_surface_recursive(&self) -> usize {
         // pick a place on the bounds and bfs to the other corner
-        let start = Cube {
-            x: self.bounds.min_x,
-            y: self.bounds.min_y,
-            z: self.bounds.min_z,
-        };
+        let start = Point3::new(self.bounds.min_x, self.bounds.min_y, self.bounds.min_z);
 
         let mut fringe = Vec::default();
         let mut seen = FxHashSet::default();
@@ -149,12 +144,12 @@ impl BoilingBoulders {
         self.surface_recur(fringe, &mut seen)
     }
 
-    pub fn surface_recur(&self, fringe: Vec<Cube>, seen: &mut FxHashSet<Cube>) -> usize {
+    pub fn surface_recur(&self, fringe: Vec<Point3>, seen: &mut FxHashSet<Point3>) -> usize {
         let mut sum = 0;
         let mut next_fringe = Vec::with_capacity(fringe.len());
 
         for cube in fringe.iter() {
-            for neighbor in cube.neighbors() {
+            for neighbor in cube.orthogonal_neighbors() {
                 if self.bounds.does_not_contain(&neighbor) {
                     continue;
                 }
@@ -185,11 +180,7 @@ impl BoilingBoulders {
     // this was a test, and it doesn't improve performance with the given input
     pub fn outer_surface_iterative(&self) -> usize {
         // pick a place on the bounds and bfs to the other corner
-        let start = Cube {
-            x: self.bounds.min_x,
-            y: self.bounds.min_y,
-            z: self.bounds.min_z,
-        };
+        let start = Point3::new(self.bounds.min_x, self.bounds.min_y, self.bounds.min_z);
 
         let mut fringe = Vec::default();
         let mut seen = FxHashSet::default();
@@ -201,7 +192,7 @@ impl BoilingBoulders {
         loop {
             let mut next_fringe = Vec::with_capacity(fringe.len());
             for cube in fringe.iter() {
-                for neighbor in cube.neighbors() {
+                for neighbor in cube.orthogonal_neighbors() {
                     if self.bounds.does_not_contain(&neighbor) {
                         continue;
                     }
@@ -229,10 +220,23 @@ impl BoilingBoulders {
     }
 }
 
+const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+#[aoc_problem(day = 18, title = "boiling boulders", year = 2022, full_dataset = (4536, 2606))]
 impl Problem for BoilingBoulders {
-    const DAY: usize = 18;
-    const TITLE: &'static str = "boiling boulders";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "64", "58")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -242,7 +246,11 @@ impl Problem for BoilingBoulders {
         let sum = self
             .cubes
             .iter()
-            .map(|cube| cube.neighbors().filter(|n| !self.cubes.contains(n)).count())
+            .map(|cube| {
+                cube.orthogonal_neighbors()
+                    .filter(|n| !self.cubes.contains(n))
+                    .count()
+            })
             .sum::<usize>();
 
         Ok(sum)
@@ -251,38 +259,55 @@ impl Problem for BoilingBoulders {
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         Ok(self.outer_surface())
     }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        // the bounds are padded by one on every side when parsing
+        let b = &self.bounds;
+        vec![
+            ("cubes", self.cubes.len().to_string()),
+            ("x", format!("{}..={}", b.min_x + 1, b.max_x - 1)),
+            ("y", format!("{}..={}", b.min_y + 1, b.max_y - 1)),
+            ("z", format!("{}..={}", b.min_z + 1, b.max_z - 1)),
+        ]
+    }
+}
+
+impl Alternatives for BoilingBoulders {
+    const IMPLEMENTATIONS: &'static [Implementation<Self>] = &[
+        Implementation::new("flood fill", Self::part_one, Self::part_two),
+        Implementation::new("recursive", Self::part_one, |b| {
+            Ok(b.outer_surface_recursive())
+        }),
+        Implementation::new("iterative", Self::part_one, |b| {
+            Ok(b.outer_surface_iterative())
+        }),
+    ];
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = BoilingBoulders::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(4536, 2606));
+    fn alternatives() {
+        for imp in BoilingBoulders::IMPLEMENTATIONS {
+            let mut boulders = BoilingBoulders::instance(EXAMPLE).unwrap();
+            assert_eq!((imp.part_one)(&mut boulders).unwrap(), 64, "{}", imp.name);
+            assert_eq!((imp.part_two)(&mut boulders).unwrap(), 58, "{}", imp.name);
+        }
     }
 
     #[test]
-    fn example() {
-        let input = "2,2,2
-1,2,2
-3,2,2
-2,1,2
-2,3,2
-2,2,1
-2,2,3
-2,2,4
-2,2,6
-1,2,5
-3,2,5
-2,1,5
-2,3,5";
-        let solution = BoilingBoulders::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(64, 58));
+    fn summary() {
+        let boulders = BoilingBoulders::instance(EXAMPLE).unwrap();
+        assert_eq!(
+            boulders.summary(),
+            vec![
+                ("cubes", "13".to_string()),
+                ("x", "1..=3".to_string()),
+                ("y", "1..=3".to_string()),
+                ("z", "1..=6".to_string()),
+            ]
+        );
     }
 }
@@ -1,6 +1,11 @@
-use std::{collections::BinaryHeap, hash::Hash, str::FromStr};
+use std::{
+    collections::BinaryHeap,
+    hash::Hash,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, cancellation, progress::tick, AocError, Example, Problem};
 use nom::{
     bytes::complete::tag,
     character::complete::{newline, space0},
@@ -9,6 +14,7 @@ use nom::{
     IResult,
 };
 use rayon::prelude::*;
+use tracing::{debug, instrument};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Mineral {
@@ -257,6 +263,7 @@ pub struct Blueprint {
 }
 
 impl Blueprint {
+    #[instrument(skip(self), fields(blueprint = self.id))]
     pub fn most_geodes_in_time(&self, minutes: i64) -> i64 {
         let mut heap = BinaryHeap::new();
 
@@ -266,8 +273,10 @@ impl Blueprint {
         });
 
         let mut best = i64::MIN;
+        let mut explored = 0_usize;
 
         while let Some(state) = heap.pop() {
+            explored += 1;
             if state.theoretical_best <= best {
                 continue;
             }
@@ -300,6 +309,8 @@ impl Blueprint {
             }
         }
 
+        debug!(best, explored, "searched blueprint");
+
         best
     }
 }
@@ -344,55 +355,56 @@ impl FromStr for NotEnoughMinerals {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, blueprints) = parse_blueprints(s.trim()).map_err(|e| e.to_owned())?;
+        let (_, blueprints) = parse_blueprints(s.trim()).map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { blueprints })
     }
 }
 
+const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+
+#[aoc_problem(day = 19, title = "not enough minerals", year = 2022, full_dataset = (1624, 12628))]
 impl Problem for NotEnoughMinerals {
-    const DAY: usize = 19;
-    const TITLE: &'static str = "not enough minerals";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "33", "3472")];
 
     type ProblemError = anyhow::Error;
     type P1 = i64;
     type P2 = i64;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
+        let done = AtomicUsize::new(0);
+        // the rayon threads don't share this thread's token
+        let token = cancellation::current().unwrap_or_default();
         Ok(self
             .blueprints
             .par_iter()
-            .map(|b| b.most_geodes_in_time(24) * b.id)
-            .sum())
+            .map(|b| {
+                token.check()?;
+                let geodes = b.most_geodes_in_time(24);
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                tick("blueprints", done, self.blueprints.len());
+                Ok(geodes * b.id)
+            })
+            .sum::<Result<i64, cancellation::Cancelled>>()?)
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        Ok(self.blueprints[0..(3.min(self.blueprints.len()))]
+        let blueprints = &self.blueprints[0..(3.min(self.blueprints.len()))];
+        let done = AtomicUsize::new(0);
+        let token = cancellation::current().unwrap_or_default();
+        Ok(blueprints
             .par_iter()
-            .map(|b| b.most_geodes_in_time(32))
-            .product())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = NotEnoughMinerals::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(1624, 12628));
+            .map(|b| {
+                token.check()?;
+                let geodes = b.most_geodes_in_time(32);
+                let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                tick("blueprints", done, blueprints.len());
+                Ok(geodes)
+            })
+            .product::<Result<i64, cancellation::Cancelled>>()?)
     }
 
-    #[test]
-    fn example() {
-        let input = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
-Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
-        let solution = NotEnoughMinerals::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(33, 3472));
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![("blueprints", self.blueprints.len().to_string())]
     }
 }
@@ -1,8 +1,16 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, Read},
+    str::FromStr,
+};
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
-use nom::{character::complete::newline, multi::separated_list1, IResult};
+use aoc_plumbing::{
+    aoc_problem, circular::CircularList, Alternatives, AocError, Example, Implementation, Problem,
+};
+use nom::{
+    character::complete::newline, combinator::all_consuming, multi::separated_list1, IResult,
+};
 
 pub const DECRYPTION_KEY: i64 = 811589153;
 
@@ -68,21 +76,53 @@ impl GrovePositioningSystem {
 
         Ok(one + two + three)
     }
+
+    // the natural fit, but chasing links around the list ends up being a lot
+    // slower than moving the values around in the deque
+    pub fn mix_linked(&self, iterations: usize, decryption_key: i64) -> Result<i64, anyhow::Error> {
+        let mut working: CircularList<i64> =
+            self.numbers.iter().map(|v| *v * decryption_key).collect();
+        // the handles stay in the original order, however the list is mixed
+        let order = working.handles().collect::<Vec<_>>();
+
+        for _ in 0..iterations {
+            for handle in order.iter() {
+                working.shift(*handle, working[*handle]);
+            }
+        }
+
+        let zero = order
+            .iter()
+            .find(|handle| working[**handle] == 0)
+            .ok_or_else(|| anyhow!("no zero to start from"))?;
+
+        Ok([1000, 2000, 3000]
+            .into_iter()
+            .map(|n| working[working.nth(*zero, n)])
+            .sum())
+    }
 }
 
 impl FromStr for GrovePositioningSystem {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, numbers) = parse_numbers(s.trim()).map_err(|e| e.to_owned())?;
+        let (_, numbers) = parse_numbers(s.trim()).map_err(|e| AocError::from_nom(s, e))?;
         Ok(Self { numbers })
     }
 }
 
+const EXAMPLE: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+#[aoc_problem(day = 20, title = "grove positioning system", year = 2022, full_dataset = (13967, 1790365671518))]
 impl Problem for GrovePositioningSystem {
-    const DAY: usize = 20;
-    const TITLE: &'static str = "grove positioning system";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "3", "1623178306")];
 
     type ProblemError = anyhow::Error;
     type P1 = i64;
@@ -95,32 +135,66 @@ impl Problem for GrovePositioningSystem {
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         self.mix(10, DECRYPTION_KEY)
     }
+
+    fn instance_reader<R: Read>(reader: R) -> Result<Self, Self::ProblemError> {
+        let mut numbers = Vec::new();
+
+        for (idx, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+
+            let (_, number) = all_consuming(nom::character::complete::i64)(trimmed)
+                .map_err(|e| AocError::from_nom(&line, e).on_line(idx + 1))?;
+            numbers.push(number);
+        }
+
+        Ok(Self { numbers })
+    }
+}
+
+impl Alternatives for GrovePositioningSystem {
+    const IMPLEMENTATIONS: &'static [Implementation<Self>] = &[
+        Implementation::new("deque", Self::part_one, Self::part_two),
+        Implementation::new(
+            "linked",
+            |g| g.mix_linked(1, 1),
+            |g| g.mix_linked(10, DECRYPTION_KEY),
+        ),
+    ];
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = GrovePositioningSystem::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(13967, 1790365671518));
+    fn alternatives() {
+        for imp in GrovePositioningSystem::IMPLEMENTATIONS {
+            let mut gps = GrovePositioningSystem::instance(EXAMPLE).unwrap();
+            assert_eq!((imp.part_one)(&mut gps).unwrap(), 3, "{}", imp.name);
+            assert_eq!(
+                (imp.part_two)(&mut gps).unwrap(),
+                1623178306,
+                "{}",
+                imp.name
+            );
+        }
     }
 
     #[test]
-    fn example() {
-        let input = "1
-2
--3
-3
--2
-0
-4";
-        let solution = GrovePositioningSystem::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(3, 1623178306));
+    fn reader() {
+        assert_eq!(
+            GrovePositioningSystem::solve_reader(EXAMPLE.as_bytes()).unwrap(),
+            GrovePositioningSystem::solve(EXAMPLE).unwrap()
+        );
+
+        let err = GrovePositioningSystem::instance_reader("1\n2\n3x\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to parse line 3, column 2 (End of file): `3x`"
+        );
     }
 }
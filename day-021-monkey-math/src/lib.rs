@@ -3,13 +3,10 @@
 //! more difficult to solve. I verified with my input that this assumption holds
 //! and I'm going to assume it has to hold for all inputs (or it may have ended
 //! up being wildly unfair).
-use std::{
-    ops::{Add, Div, Mul, Sub},
-    str::FromStr,
-};
+use std::ops::{Add, Div, Mul, Sub};
 
 use anyhow::{anyhow, bail};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, AocError, BorrowedProblem, Example};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -184,323 +181,180 @@ impl Div<Value> for Value {
     }
 }
 
-// So having these "raw" versions works around the limitation in the Problem
-// trait not having lifetime support, by first storing &str then converting
-// later. We're going to convert to int indicies of course, since that'll cut
-// down on potential hash lookup time.
+// The names of the monkeys borrow from the input, so they're looked up as
+// they are rather than being converted to indices first.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum RawJob<'a> {
+pub enum Job<'a> {
     Sum { left: &'a str, right: &'a str },
     Sub { left: &'a str, right: &'a str },
     Mul { left: &'a str, right: &'a str },
     Div { left: &'a str, right: &'a str },
     Yell { value: i64 },
-}
-
-impl<'a> RawJob<'a> {
-    pub fn to_job(&self, name_hash: &FxHashMap<&str, usize>) -> Result<Job, anyhow::Error> {
-        let j = match self {
-            Self::Sum { left, right } => Job::Sum {
-                left: *name_hash
-                    .get(left)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", left))?,
-                right: *name_hash
-                    .get(right)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", right))?,
-            },
-            Self::Sub { left, right } => Job::Sub {
-                left: *name_hash
-                    .get(left)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", left))?,
-                right: *name_hash
-                    .get(right)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", right))?,
-            },
-            Self::Mul { left, right } => Job::Mul {
-                left: *name_hash
-                    .get(left)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", left))?,
-                right: *name_hash
-                    .get(right)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", right))?,
-            },
-            Self::Div { left, right } => Job::Div {
-                left: *name_hash
-                    .get(left)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", left))?,
-                right: *name_hash
-                    .get(right)
-                    .ok_or_else(|| anyhow!("missing monkey: {}", right))?,
-            },
-            Self::Yell { value } => Job::Yell { value: *value },
-        };
-
-        Ok(j)
-    }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Job {
-    Sum { left: usize, right: usize },
-    Sub { left: usize, right: usize },
-    Mul { left: usize, right: usize },
-    Div { left: usize, right: usize },
-    Yell { value: i64 },
     Human,
 }
 
-impl Job {
+impl<'a> Job<'a> {
     /// So this is a direct solve without the extra `Value` overhead
-    pub fn output(&self, monkeys: &[Monkey]) -> Result<i64, anyhow::Error> {
+    pub fn output(&self, monkeys: &Monkeys<'a>) -> Result<i64, anyhow::Error> {
         match self {
-            Self::Sum { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.output(monkeys)? + r.output(monkeys)?)
-            }
-            Self::Sub { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.output(monkeys)? - r.output(monkeys)?)
-            }
-            Self::Mul { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.output(monkeys)? * r.output(monkeys)?)
-            }
-            Self::Div { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.output(monkeys)? / r.output(monkeys)?)
-            }
+            Self::Sum { left, right } => Ok(monkeys.output(left)? + monkeys.output(right)?),
+            Self::Sub { left, right } => Ok(monkeys.output(left)? - monkeys.output(right)?),
+            Self::Mul { left, right } => Ok(monkeys.output(left)? * monkeys.output(right)?),
+            Self::Div { left, right } => Ok(monkeys.output(left)? / monkeys.output(right)?),
             Self::Yell { value } => Ok(*value),
             Self::Human => bail!("Cannot solve with human unless using `value_output`"),
         }
     }
 
     /// This pays the `Value` penalty to allow the solver to work.
-    pub fn value_output(&self, monkeys: &[Monkey]) -> Result<Value, anyhow::Error> {
+    pub fn value_output(&self, monkeys: &Monkeys<'a>) -> Result<Value, anyhow::Error> {
         match self {
             Self::Sum { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.value_output(monkeys)? + r.value_output(monkeys)?)
+                Ok(monkeys.value_output(left)? + monkeys.value_output(right)?)
             }
             Self::Sub { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.value_output(monkeys)? - r.value_output(monkeys)?)
+                Ok(monkeys.value_output(left)? - monkeys.value_output(right)?)
             }
             Self::Mul { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.value_output(monkeys)? * r.value_output(monkeys)?)
+                Ok(monkeys.value_output(left)? * monkeys.value_output(right)?)
             }
             Self::Div { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("Unknown monkey: {}", left))?;
-                Ok(l.value_output(monkeys)? / r.value_output(monkeys)?)
+                Ok(monkeys.value_output(left)? / monkeys.value_output(right)?)
             }
             Self::Yell { value } => Ok(Value::Num { value: *value }),
             Self::Human => Ok(Value::Var),
         }
     }
+
+    pub fn left_and_right(&self) -> Result<(&'a str, &'a str), anyhow::Error> {
+        match self {
+            Self::Yell { .. } | Self::Human => bail!("cannot get left and right on Yell"),
+            Self::Sum { left, right }
+            | Self::Sub { left, right }
+            | Self::Mul { left, right }
+            | Self::Div { left, right } => Ok((left, right)),
+        }
+    }
 }
 
-fn parse_sum<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_sum<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     let (input, (left, right)) = separated_pair(alpha1, tag(" + "), alpha1)(input)?;
-    Ok((input, RawJob::Sum { left, right }))
+    Ok((input, Job::Sum { left, right }))
 }
 
-fn parse_sub<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_sub<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     let (input, (left, right)) = separated_pair(alpha1, tag(" - "), alpha1)(input)?;
-    Ok((input, RawJob::Sub { left, right }))
+    Ok((input, Job::Sub { left, right }))
 }
 
-fn parse_mul<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_mul<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     let (input, (left, right)) = separated_pair(alpha1, tag(" * "), alpha1)(input)?;
-    Ok((input, RawJob::Mul { left, right }))
+    Ok((input, Job::Mul { left, right }))
 }
 
-fn parse_div<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_div<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     let (input, (left, right)) = separated_pair(alpha1, tag(" / "), alpha1)(input)?;
-    Ok((input, RawJob::Div { left, right }))
+    Ok((input, Job::Div { left, right }))
 }
 
-fn parse_yell<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_yell<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     let (input, value) = nom_i64(input)?;
-    Ok((input, RawJob::Yell { value }))
+    Ok((input, Job::Yell { value }))
 }
 
-fn parse_job<'a>(input: &'a str) -> IResult<&'a str, RawJob<'a>> {
+fn parse_job<'a>(input: &'a str) -> IResult<&'a str, Job<'a>> {
     alt((parse_sum, parse_sub, parse_mul, parse_div, parse_yell))(input)
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct RawMonkey<'a> {
-    name: &'a str,
-    job: RawJob<'a>,
+fn parse_monkey<'a>(input: &'a str) -> IResult<&'a str, (&'a str, Job<'a>)> {
+    separated_pair(alpha1, tag(": "), parse_job)(input)
 }
 
-fn parse_monkey<'a>(input: &'a str) -> IResult<&'a str, RawMonkey<'a>> {
-    let (input, (name, job)) = separated_pair(alpha1, tag(": "), parse_job)(input)?;
-    Ok((input, RawMonkey { name, job }))
-}
-
-fn parse_monkeys<'a>(input: &'a str) -> IResult<&'a str, Vec<RawMonkey<'a>>> {
+fn parse_monkeys<'a>(input: &'a str) -> IResult<&'a str, Vec<(&'a str, Job<'a>)>> {
     separated_list1(newline, parse_monkey)(input)
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct Monkey {
-    id: usize,
-    job: Job,
+/// Every monkey's job, by name.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Monkeys<'a> {
+    jobs: FxHashMap<&'a str, Job<'a>>,
 }
 
-impl Monkey {
+impl<'a> Monkeys<'a> {
+    pub fn get(&self, name: &str) -> Result<&Job<'a>, anyhow::Error> {
+        self.jobs
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown monkey: {}", name))
+    }
+
     // So these recursive functions did have caches, before, but it turns out
     // that my input never had cache hits. The caches were removed for
     // performance reasons
-    pub fn output(&self, monkeys: &[Monkey]) -> Result<i64, anyhow::Error> {
-        self.job.output(monkeys)
+    pub fn output(&self, name: &str) -> Result<i64, anyhow::Error> {
+        self.get(name)?.output(self)
     }
 
-    pub fn value_output(&self, monkeys: &[Monkey]) -> Result<Value, anyhow::Error> {
-        self.job.value_output(monkeys)
-    }
-
-    pub fn left_and_right<'a>(
-        &self,
-        monkeys: &'a [Monkey],
-    ) -> Result<(&'a Monkey, &'a Monkey), anyhow::Error> {
-        match &self.job {
-            Job::Yell { .. } | Job::Human => bail!("cannot get left and right on Yell"),
-            Job::Sum { left, right }
-            | Job::Sub { left, right }
-            | Job::Mul { left, right }
-            | Job::Div { left, right } => {
-                let l = monkeys
-                    .get(*left)
-                    .ok_or_else(|| anyhow!("unknown monkey: {}", left))?;
-                let r = monkeys
-                    .get(*right)
-                    .ok_or_else(|| anyhow!("unknown monkey: {}", right))?;
-                Ok((l, r))
-            }
-        }
+    pub fn value_output(&self, name: &str) -> Result<Value, anyhow::Error> {
+        self.get(name)?.value_output(self)
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct MonkeyMath {
-    monkeys: Vec<Monkey>,
-    root_id: usize,
-    human_id: usize,
-}
+const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
 
-impl FromStr for MonkeyMath {
-    type Err = anyhow::Error;
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct MonkeyMath;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, raw_monkeys) = parse_monkeys(s).map_err(|e| e.to_owned())?;
+#[aoc_problem(day = 21, title = "monkey math", year = 2022, full_dataset = (49288254556480, 3558714869436))]
+impl BorrowedProblem for MonkeyMath {
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "152", "301")];
 
-        // this seems like some nonsense, but it's a huge later on savings to
-        // not have to deal with strings and looking those up from hashes
-        let mut monkeys = Vec::with_capacity(raw_monkeys.len());
-        let mut monkey_name_hash: FxHashMap<&str, usize> =
-            FxHashMap::with_capacity_and_hasher(raw_monkeys.len(), Default::default());
+    type ProblemError = anyhow::Error;
+    type Instance<'a> = Monkeys<'a>;
+    type P1 = i64;
+    type P2 = i64;
 
-        let mut count = 0;
-        for m in raw_monkeys.iter() {
-            monkey_name_hash.insert(m.name, count);
-            count += 1;
-        }
+    fn instance(raw_input: &str) -> Result<Self::Instance<'_>, Self::ProblemError> {
+        let (_, jobs) = parse_monkeys(raw_input).map_err(|e| AocError::from_nom(raw_input, e))?;
+        let monkeys = Monkeys {
+            jobs: jobs.into_iter().collect(),
+        };
 
-        let root_id = *monkey_name_hash
-            .get("root")
-            .ok_or_else(|| anyhow!("no root monkey"))?;
-        let human_id = *monkey_name_hash
-            .get("humn")
-            .ok_or_else(|| anyhow!("no human"))?;
-
-        for m in raw_monkeys {
-            let monkey = Monkey {
-                id: monkeys.len(),
-                job: m.job.to_job(&monkey_name_hash)?,
-            };
-            monkeys.push(monkey);
+        if !monkeys.jobs.contains_key("root") {
+            bail!("no root monkey");
+        }
+        if !monkeys.jobs.contains_key("humn") {
+            bail!("no human");
         }
 
-        Ok(Self {
-            monkeys,
-            root_id,
-            human_id,
-        })
+        Ok(monkeys)
     }
-}
-
-impl Problem for MonkeyMath {
-    const DAY: usize = 21;
-    const TITLE: &'static str = "monkey math";
-    const README: &'static str = include_str!("../README.md");
 
-    type ProblemError = anyhow::Error;
-    type P1 = i64;
-    type P2 = i64;
-
-    fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        let root = self
-            .monkeys
-            .get(self.root_id)
-            .ok_or_else(|| anyhow!("no monkey named root"))?;
-        root.output(&self.monkeys)
+    fn part_one(monkeys: &mut Self::Instance<'_>) -> Result<Self::P1, Self::ProblemError> {
+        monkeys.output("root")
     }
 
-    fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        self.monkeys[self.human_id].job = Job::Human;
-        let root = self
-            .monkeys
-            .get(self.root_id)
-            .ok_or_else(|| anyhow!("no monkey named root"))?;
-
-        let (left, right) = root.left_and_right(&self.monkeys)?;
+    fn part_two(monkeys: &mut Self::Instance<'_>) -> Result<Self::P2, Self::ProblemError> {
+        monkeys.jobs.insert("humn", Job::Human);
+        let (left, right) = monkeys.get("root")?.left_and_right()?;
 
         // we can compute each side independently without implementing == for
         // root
-        let l = left.value_output(&self.monkeys)?;
-        let r = right.value_output(&self.monkeys)?;
+        let l = monkeys.value_output(left)?;
+        let r = monkeys.value_output(right)?;
 
         // figure out which side is us and the actual value of the other side
         let (us, them) = {
@@ -517,39 +371,3 @@ impl Problem for MonkeyMath {
         us.solve(them)
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = MonkeyMath::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(49288254556480, 3558714869436));
-    }
-
-    #[test]
-    fn example() {
-        let input = "root: pppw + sjmn
-dbpl: 5
-cczh: sllz + lgvd
-zczc: 2
-ptdq: humn - dvpt
-dvpt: 3
-lfqf: 4
-humn: 5
-ljgn: 2
-sjmn: drzm * dbpl
-sllz: 4
-pppw: cczh / lfqf
-lgvd: ljgn * ptdq
-drzm: hmdt - zczc
-hmdt: 32";
-        let solution = MonkeyMath::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(152, 301));
-    }
-}
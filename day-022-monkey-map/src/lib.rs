@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use anyhow::{anyhow, bail};
 use aoc_helpers::generic::{Grid, Location};
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    aoc_problem, grid::render, parsing::char_grid, AocError, Example, Problem, Visualize,
+};
 use nom::{branch::alt, multi::many1, IResult};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
@@ -12,6 +14,16 @@ pub enum Tile {
     Wall,
 }
 
+impl Tile {
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Void => ' ',
+            Self::Open => '.',
+            Self::Wall => '#',
+        }
+    }
+}
+
 /// Instead of up/down/whatever, let's just use compass directions to not get
 /// confused.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -23,6 +35,16 @@ pub enum Facing {
 }
 
 impl Facing {
+    /// The arrow the puzzle uses to draw a path moving this way.
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+            Self::North => '^',
+        }
+    }
+
     fn left(&self) -> Self {
         match self {
             Self::North => Self::West,
@@ -426,14 +448,7 @@ impl Person {
         match instruction {
             Instruction::Dist(dist) => {
                 for _ in 0..*dist {
-                    let moved = match self.facing {
-                        Facing::North => self.move_north(map),
-                        Facing::South => self.move_south(map),
-                        Facing::East => self.move_east(map),
-                        Facing::West => self.move_west(map),
-                    };
-
-                    if !moved {
+                    if !self.step(map) {
                         break;
                     }
                 }
@@ -442,6 +457,17 @@ impl Person {
         }
     }
 
+    /// Move one tile in the direction we're facing, returning false if there
+    /// was a wall in the way.
+    pub fn step(&mut self, map: &MonkeyMap) -> bool {
+        match self.facing {
+            Facing::North => self.move_north(map),
+            Facing::South => self.move_south(map),
+            Facing::East => self.move_east(map),
+            Facing::West => self.move_west(map),
+        }
+    }
+
     pub fn turn(&mut self, turn: &Turn) {
         match turn {
             Turn::Left => self.facing = self.facing.left(),
@@ -531,8 +557,8 @@ pub struct MonkeyMap {
 }
 
 impl MonkeyMap {
-    pub fn password(&self) -> Result<usize, anyhow::Error> {
-        // start facing right and in the first non-void open tile
+    /// Where we start, facing right in the first non-void open tile.
+    fn start(&self) -> Result<Person, anyhow::Error> {
         let mut start_col = self.lr_edges[0].0;
 
         // handle case where first tile is a wall
@@ -543,10 +569,14 @@ impl MonkeyMap {
             }
         }
 
-        let mut cur = Person {
+        Ok(Person {
             location: (0, start_col).into(),
             facing: Facing::East,
-        };
+        })
+    }
+
+    pub fn password(&self) -> Result<usize, anyhow::Error> {
+        let mut cur = self.start()?;
 
         for inst in self.instructions.iter() {
             cur.follow(&self, inst);
@@ -556,20 +586,10 @@ impl MonkeyMap {
     }
 
     pub fn cube_password(&self) -> Result<usize, anyhow::Error> {
-        // start facing right and in the first non-void open tile
-        let mut start_col = self.lr_edges[0].0;
-
-        // handle case where first tile is a wall
-        while self.grid.locations[0][start_col] != Tile::Open {
-            start_col += 1;
-            if start_col > self.lr_edges[0].1 {
-                bail!("First row does not have an open tile");
-            }
-        }
-
+        let start = self.start()?;
         let mut cur: CubePerson<50> = CubePerson {
-            location: (0, start_col).into(),
-            facing: Facing::East,
+            location: start.location,
+            facing: start.facing,
         };
 
         for inst in self.instructions.iter() {
@@ -586,27 +606,27 @@ impl FromStr for MonkeyMap {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split("\n\n");
         let raw_map = parts.next().ok_or_else(|| anyhow!("Input missing map"))?;
-        let lines = raw_map.lines().collect::<Vec<_>>();
-        let width = lines
-            .iter()
-            .map(|line| line.len())
-            .max()
+        let raw_grid = char_grid(raw_map.lines(), Tile::Void, |ch| match ch {
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            ' ' => Ok(Tile::Void),
+            _ => Err("invalid tile"),
+        })?;
+        let height = raw_grid.len();
+        let width = raw_grid
+            .first()
+            .map(|row| row.len())
             .ok_or_else(|| anyhow!("map is empty"))?;
-        let height = lines.len();
 
-        let mut raw_grid = vec![vec![Tile::Void; width]; height];
         let mut lr_edges = Vec::with_capacity(height);
         let mut tb_edges = Vec::with_capacity(width);
 
-        for (row, line) in raw_map.lines().enumerate() {
+        for row in raw_grid.iter() {
             let mut min = width;
             let mut max = 0;
-            for (col, ch) in line.chars().enumerate() {
-                match ch {
-                    '.' => raw_grid[row][col] = Tile::Open,
-                    '#' => raw_grid[row][col] = Tile::Wall,
-                    ' ' => continue,
-                    _ => bail!("Invalid char: {}", ch),
+            for (col, tile) in row.iter().enumerate() {
+                if *tile == Tile::Void {
+                    continue;
                 }
 
                 if col < min {
@@ -645,7 +665,7 @@ impl FromStr for MonkeyMap {
                 .next()
                 .ok_or_else(|| anyhow!("missing instructions"))?,
         )
-        .map_err(|e| e.to_owned())?;
+        .map_err(|e| AocError::from_nom(s, e))?;
 
         Ok(Self {
             grid: Grid::new(raw_grid),
@@ -656,10 +676,64 @@ impl FromStr for MonkeyMap {
     }
 }
 
+impl Visualize for MonkeyMap {
+    /// The map with the path walked so far in part one drawn on it, after
+    /// each instruction.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let mut person = match self.start() {
+            Ok(person) => person,
+            Err(_) => return Box::new(std::iter::empty()),
+        };
+        let mut canvas = self
+            .grid
+            .locations
+            .iter()
+            .map(|row| row.iter().map(Tile::as_char).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        Box::new(self.instructions.iter().map(move |inst| {
+            match inst {
+                Instruction::Dist(dist) => {
+                    for _ in 0..*dist {
+                        canvas[person.location.row][person.location.col] = person.facing.as_char();
+                        if !person.step(self) {
+                            break;
+                        }
+                    }
+                }
+                Instruction::Turn(turn) => person.turn(turn),
+            }
+
+            canvas[person.location.row][person.location.col] = person.facing.as_char();
+            render(&canvas, |ch| *ch)
+        }))
+    }
+}
+
+const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+            ";
+
+#[aoc_problem(day = 22, title = "monkey map", year = 2022, full_dataset = (75254, 108311))]
 impl Problem for MonkeyMap {
-    const DAY: usize = 22;
-    const TITLE: &'static str = "monkey map";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("6032"),
+        // the cube folding only handles the layout of the real input
+        part_two: None,
+    }];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -676,38 +750,28 @@ impl Problem for MonkeyMap {
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = MonkeyMap::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(75254, 108311));
-    }
-
     // this is only a test for part one of the example input, on account of how
     // different the real input is laid out
     #[test]
     fn example() {
-        let input = "        ...#
-        .#..
-        #...
-        ....
-...#.......#
-........#...
-..#....#....
-..........#.
-        ...#....
-        .....#..
-        .#......
-        ......#.
-
-10R5L5R10L4R5L5
-            ";
+        let input = EXAMPLE;
         let mut inst = MonkeyMap::instance(input).unwrap();
         assert_eq!(inst.part_one().unwrap(), 6032);
     }
+
+    #[test]
+    fn frames() {
+        let inst = MonkeyMap::instance(EXAMPLE).unwrap();
+        let frames = inst.frames().collect::<Vec<_>>();
+        assert_eq!(frames.len(), 13);
+        // the rows are padded out to the widest one
+        assert_eq!(frames[0].lines().next(), Some("        >>>#    "));
+        assert_eq!(frames[1].lines().next(), Some("        >>v#    "));
+
+        // the password's row 6, column 8, facing right
+        let last = frames[12].lines().nth(5).unwrap();
+        assert_eq!(last.chars().nth(7), Some('>'));
+    }
 }
@@ -1,14 +1,12 @@
-use std::{collections::VecDeque, hash::Hash, str::FromStr};
+use std::{collections::VecDeque, str::FromStr};
 
-use aoc_helpers::generic::Bound2D;
-use aoc_plumbing::Problem;
-use rustc_hash::FxHashSet;
+use aoc_plumbing::{aoc_problem, geometry::Point, grid::SparseGrid, Example, Problem, Visualize};
 
 const N_NE_NW: usize = 0b10010100;
 const S_SE_SW: usize = 0b00101001;
 const W_NW_SW: usize = 0b00000111;
 const E_NE_SE: usize = 0b11100000;
-const NEIGHBORS: [(i16, i16); 8] = [
+const NEIGHBORS: [(i64, i64); 8] = [
     (-1, -1),
     (-1, 0),
     (-1, 1),
@@ -32,12 +30,6 @@ const WEST_CHUNKS: [usize; 4] = [3, 3, 1, 1];
 const EAST_ORDER: [usize; 8] = [5, 6, 7, 2, 4, 0, 3, 1];
 const EAST_CHUNKS: [usize; 4] = [3, 2, 2, 1];
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
-pub struct Point {
-    x: i16,
-    y: i16,
-}
-
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Choice {
     North,
@@ -78,40 +70,28 @@ impl Choice {
         match self {
             Self::North => {
                 if neighbors & N_NE_NW == 0 {
-                    Some(Point {
-                        x: elf.x,
-                        y: elf.y + 1,
-                    })
+                    Some(Point::new(elf.x, elf.y + 1))
                 } else {
                     None
                 }
             }
             Self::South => {
                 if neighbors & S_SE_SW == 0 {
-                    Some(Point {
-                        x: elf.x,
-                        y: elf.y - 1,
-                    })
+                    Some(Point::new(elf.x, elf.y - 1))
                 } else {
                     None
                 }
             }
             Self::West => {
                 if neighbors & W_NW_SW == 0 {
-                    Some(Point {
-                        x: elf.x - 1,
-                        y: elf.y,
-                    })
+                    Some(Point::new(elf.x - 1, elf.y))
                 } else {
                     None
                 }
             }
             Self::East => {
                 if neighbors & E_NE_SE == 0 {
-                    Some(Point {
-                        x: elf.x + 1,
-                        y: elf.y,
-                    })
+                    Some(Point::new(elf.x + 1, elf.y))
                 } else {
                     None
                 }
@@ -122,190 +102,128 @@ impl Choice {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct UnstableDiffusion {
-    elves: FxHashSet<Point>,
+    elves: SparseGrid<()>,
 }
 
 impl UnstableDiffusion {
-    pub fn rounds(&mut self, num: usize) -> i16 {
-        let mut choices = VecDeque::with_capacity(4);
-        choices.push_back(Choice::North);
-        choices.push_back(Choice::South);
-        choices.push_back(Choice::West);
-        choices.push_back(Choice::East);
+    pub fn rounds(&mut self, num: usize) -> i64 {
+        let mut choices = Self::initial_choices();
 
         for _ in 0..num {
-            let mut next_elves =
-                FxHashSet::with_capacity_and_hasher(self.elves.len(), Default::default());
-            let order = choices[0].order_when_first();
-            let chunks = choices[0].chunks_when_first();
-
-            for elf in self.elves.iter() {
-                let mut choice_idxs = order.iter();
-                let mut chunks = chunks.iter();
-                let mut prop: Option<Point> = None;
-                let mut found_neighbors = 0;
-
-                for choice in choices.iter() {
-                    for _ in 0..*chunks.next().unwrap() {
-                        let n_idx = *choice_idxs.next().unwrap();
-                        let (dx, dy) = NEIGHBORS[n_idx];
-                        let n = Point {
-                            x: elf.x + dx,
-                            y: elf.y + dy,
-                        };
-                        if self.elves.contains(&n) {
-                            found_neighbors |= 1 << n_idx;
-                        }
-                    }
-
-                    if prop.is_none() {
-                        if let Some(dest) = choice.propose(elf, found_neighbors) {
-                            prop = Some(dest);
-                            // we can only break early when we make a choice if
-                            // we've found at least one neighbor, because this
-                            // might have been the first choice and we need
-                            // to check for others
-                            if found_neighbors > 0 {
-                                break;
-                            }
-                        }
-                    } else if found_neighbors > 0 {
-                        // we have already made a choice in a previous iteration
-                        // and we've found a neighbor, so break early
-                        break;
-                    }
-                }
-
-                // add the proposal
-                if found_neighbors > 0 {
-                    if let Some(dest) = prop {
-                        if !next_elves.insert(dest) {
-                            next_elves.remove(&dest);
-                            next_elves.insert(*elf);
-                            next_elves.insert(Point {
-                                x: dest.x * 2 - elf.x,
-                                y: dest.y * 2 - elf.y,
-                            });
-                        }
-                        continue;
-                    }
-                }
-
-                next_elves.insert(*elf);
-            }
-
-            let first = choices.pop_front().unwrap();
-            choices.push_back(first);
-
-            self.elves = next_elves;
+            self.round(&mut choices);
         }
 
-        let mut bounds: Bound2D<i16> = Bound2D::minmax();
-        for elf in self.elves.iter() {
-            if elf.x < bounds.min_x {
-                bounds.min_x = elf.x;
-            }
-
-            if elf.x > bounds.max_x {
-                bounds.max_x = elf.x;
-            }
+        let area = self.elves.bounds().map(|b| b.area()).unwrap_or_default();
+        area - self.elves.len() as i64
+    }
 
-            if elf.y < bounds.min_y {
-                bounds.min_y = elf.y;
-            }
+    pub fn rounds_until_no_moves(&mut self) -> usize {
+        let mut choices = Self::initial_choices();
 
-            if elf.y > bounds.max_y {
-                bounds.max_y = elf.y;
-            }
+        let mut count = 1;
+        while self.round(&mut choices) {
+            count += 1;
         }
 
-        bounds.width() * bounds.height() - self.elves.len() as i16
+        count
     }
 
-    pub fn rounds_until_no_moves(&mut self) -> usize {
+    fn initial_choices() -> VecDeque<Choice> {
         let mut choices = VecDeque::with_capacity(4);
         choices.push_back(Choice::North);
         choices.push_back(Choice::South);
         choices.push_back(Choice::West);
         choices.push_back(Choice::East);
+        choices
+    }
 
-        let mut count = 0;
-
-        loop {
-            count += 1;
-            let mut moved = 0;
-            let mut next_elves =
-                FxHashSet::with_capacity_and_hasher(self.elves.len(), Default::default());
-            let order = choices[0].order_when_first();
-            let chunks = choices[0].chunks_when_first();
-
-            for elf in self.elves.iter() {
-                let mut choice_idxs = order.iter();
-                let mut chunks = chunks.iter();
-                let mut prop: Option<Point> = None;
-                let mut found_neighbors = 0;
-
-                for choice in choices.iter() {
-                    for _ in 0..*chunks.next().unwrap() {
-                        let n_idx = *choice_idxs.next().unwrap();
-                        let (dx, dy) = NEIGHBORS[n_idx];
-                        let n = Point {
-                            x: elf.x + dx,
-                            y: elf.y + dy,
-                        };
-                        if self.elves.contains(&n) {
-                            found_neighbors |= 1 << n_idx;
-                        }
+    /// Performs a single round, rotating the choices afterwards. Returns
+    /// whether or not any elf moved.
+    pub fn round(&mut self, choices: &mut VecDeque<Choice>) -> bool {
+        let mut moved = 0;
+        let mut next_elves = SparseGrid::with_capacity(self.elves.len());
+        let order = choices[0].order_when_first();
+        let chunks = choices[0].chunks_when_first();
+
+        for elf in self.elves.points() {
+            let mut choice_idxs = order.iter();
+            let mut chunks = chunks.iter();
+            let mut prop: Option<Point> = None;
+            let mut found_neighbors = 0;
+
+            for choice in choices.iter() {
+                for _ in 0..*chunks.next().unwrap() {
+                    let n_idx = *choice_idxs.next().unwrap();
+                    let (dx, dy) = NEIGHBORS[n_idx];
+                    let n = Point::new(elf.x + dx, elf.y + dy);
+                    if self.elves.contains(&n) {
+                        found_neighbors |= 1 << n_idx;
                     }
+                }
 
-                    if prop.is_none() {
-                        if let Some(dest) = choice.propose(elf, found_neighbors) {
-                            prop = Some(dest);
-                            // we can only break early when we make a choice if
-                            // we've found at least one neighbor, because this
-                            // might have been the first choice and we need
-                            // to check for others
-                            if found_neighbors > 0 {
-                                break;
-                            }
+                if prop.is_none() {
+                    if let Some(dest) = choice.propose(elf, found_neighbors) {
+                        prop = Some(dest);
+                        // we can only break early when we make a choice if
+                        // we've found at least one neighbor, because this
+                        // might have been the first choice and we need
+                        // to check for others
+                        if found_neighbors > 0 {
+                            break;
                         }
-                    } else if found_neighbors > 0 {
-                        // we have already made a choice in a previous iteration
-                        // and we've found a neighbor, so break early
-                        break;
                     }
+                } else if found_neighbors > 0 {
+                    // we have already made a choice in a previous iteration
+                    // and we've found a neighbor, so break early
+                    break;
                 }
+            }
 
-                // add the proposal
-                if found_neighbors > 0 {
-                    if let Some(dest) = prop {
-                        if !next_elves.insert(dest) {
-                            next_elves.remove(&dest);
-                            next_elves.insert(*elf);
-                            next_elves.insert(Point {
-                                x: dest.x * 2 - elf.x,
-                                y: dest.y * 2 - elf.y,
-                            });
-                            moved -= 1;
-                        } else {
-                            moved += 1;
-                        }
-                        continue;
+            // add the proposal
+            if found_neighbors > 0 {
+                if let Some(dest) = prop {
+                    if next_elves.insert(dest, ()).is_some() {
+                        next_elves.remove(&dest);
+                        next_elves.insert(*elf, ());
+                        next_elves.insert(Point::new(dest.x * 2 - elf.x, dest.y * 2 - elf.y), ());
+                        moved -= 1;
+                    } else {
+                        moved += 1;
                     }
+                    continue;
                 }
-
-                next_elves.insert(*elf);
             }
 
-            if moved == 0 {
-                break count;
-            }
+            next_elves.insert(*elf, ());
+        }
 
-            self.elves = next_elves;
+        let first = choices.pop_front().unwrap();
+        choices.push_back(first);
 
-            let first = choices.pop_front().unwrap();
-            choices.push_back(first);
+        self.elves = next_elves;
+
+        moved != 0
+    }
+
+    /// Renders the smallest rectangle containing every elf.
+    pub fn render(&self) -> String {
+        let bounds = match self.elves.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+        let mut out = String::new();
+        // y increases going north, so the top row is the max
+        for y in (bounds.min.y..=bounds.max.y).rev() {
+            for x in bounds.min.x..=bounds.max.x {
+                if self.elves.contains(&Point::new(x, y)) {
+                    out.push('#');
+                } else {
+                    out.push('.');
+                }
+            }
+            out.push('\n');
         }
+        out
     }
 }
 
@@ -313,15 +231,12 @@ impl FromStr for UnstableDiffusion {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut elves = FxHashSet::default();
+        let mut elves = SparseGrid::new();
 
         for (y, line) in s.trim().lines().rev().enumerate() {
             for (x, ch) in line.chars().enumerate() {
                 if ch == '#' {
-                    elves.insert(Point {
-                        x: x as i16,
-                        y: y as i16,
-                    });
+                    elves.insert(Point::new(x as i64, y as i64), ());
                 }
             }
         }
@@ -329,13 +244,25 @@ impl FromStr for UnstableDiffusion {
     }
 }
 
+const EXAMPLE: &str = "..............
+..............
+.......#......
+.....###.#....
+...#...#.#....
+....#...##....
+...#.###......
+...##.#.##....
+....#..#......
+..............
+..............
+..............";
+
+#[aoc_problem(day = 23, title = "unstable diffusion", year = 2022, full_dataset = (3788, 921))]
 impl Problem for UnstableDiffusion {
-    const DAY: usize = 23;
-    const TITLE: &'static str = "unstable diffusion";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "110", "20")];
 
     type ProblemError = anyhow::Error;
-    type P1 = i16;
+    type P1 = i64;
     type P2 = usize;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
@@ -349,35 +276,36 @@ impl Problem for UnstableDiffusion {
     }
 }
 
+impl Visualize for UnstableDiffusion {
+    /// The elves after each round, until none of them move.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let mut working = self.clone();
+        let mut choices = Self::initial_choices();
+        let mut done = false;
+
+        Box::new(
+            std::iter::once(working.render()).chain(std::iter::from_fn(move || {
+                if done {
+                    return None;
+                }
+                done = !working.round(&mut choices);
+                Some(working.render())
+            })),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = UnstableDiffusion::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(3788, 921));
-    }
-
-    #[test]
-    fn example() {
-        let input = "..............
-..............
-.......#......
-.....###.#....
-...#...#.#....
-....#...##....
-...#.###......
-...##.#.##....
-....#..#......
-..............
-..............
-..............";
-        let solution = UnstableDiffusion::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(110, 20));
+    fn frames() {
+        let diffusion = UnstableDiffusion::instance(EXAMPLE).unwrap();
+        let frames = diffusion.frames().collect::<Vec<_>>();
+        // the initial state, then one for each round
+        assert_eq!(frames.len(), 21);
+        assert!(frames[0].starts_with("....#..\n"));
+        assert_eq!(frames[19], frames[20]);
     }
 }
@@ -1,10 +1,19 @@
-use std::{collections::BinaryHeap, fmt::Display, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
-use anyhow::{anyhow, bail};
+use anyhow::bail;
 use aoc_helpers::generic::{prelude::GridLike, Grid, Location};
-use aoc_plumbing::Problem;
-use num::integer::lcm;
-use rustc_hash::FxHashMap;
+use aoc_plumbing::{
+    aoc_problem,
+    cancellation::checkpoint,
+    grid::Render,
+    math::lcm,
+    memo::{Dims, IndexedMemo},
+    parsing::char_grid,
+    pathing::try_astar_with,
+    progress::tick,
+    Example, Problem, Visualize,
+};
+use tracing::{debug, instrument};
 
 const NORTH: u8 = 0b1;
 const SOUTH: u8 = 0b10;
@@ -28,19 +37,13 @@ pub struct Snapshot {
 
 impl Display for Snapshot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.locations.iter() {
-            let mut s = String::with_capacity(self.grid.cols);
-            for tile in row.iter() {
-                let ch = match tile {
-                    Tile::Wall => '#',
-                    Tile::Empty => '.',
-                    Tile::Blizzard(_) => 'B',
-                    _ => unreachable!(),
-                };
-                s.push(ch);
-            }
-            writeln!(f, "{}", s)?;
-        }
+        let render = Render::new(&self.grid.locations, |tile| match tile {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+            Tile::Blizzard(_) => 'B',
+            _ => unreachable!(),
+        });
+        write!(f, "{}", render)?;
 
         writeln!(f, "-----------")
     }
@@ -199,29 +202,6 @@ impl Timeline {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
-pub struct State {
-    location: Location,
-    minute: usize,
-    cost: usize,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| other.minute.cmp(&self.minute))
-            .then_with(|| self.location.cmp(&other.location))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct BlizzardBasin {
     grid: Grid<Tile>,
@@ -231,7 +211,8 @@ pub struct BlizzardBasin {
 }
 
 impl BlizzardBasin {
-    // pretty starndard dijkstra, haven't decided on a cost fn yet to make it A*
+    // A* over (location, minute), since the blizzards move with time
+    #[instrument(skip(self, timeline))]
     pub fn best_time(
         &self,
         start_time: usize,
@@ -239,88 +220,58 @@ impl BlizzardBasin {
         end: &Location,
         timeline: &mut Timeline,
     ) -> Result<usize, anyhow::Error> {
-        let mut cache: FxHashMap<(Location, usize), usize> = FxHashMap::default();
-
-        let mut heap = BinaryHeap::new();
-
-        let start = State {
-            location: *start,
-            minute: start_time,
-            cost: 0,
-        };
-
-        cache.insert((self.start, start_time), 0);
-        heap.push(start);
-
-        while let Some(State {
-            location,
-            minute,
-            cost,
-        }) = heap.pop()
-        {
-            if location == *end {
-                return Ok(minute);
-            }
-
-            if cost > *cache.get(&(location, minute)).unwrap_or(&usize::MAX) {
-                continue;
-            }
-
-            // let's see what it would look like on the next step
-            timeline.simulate_to(minute + 1, &self.next_template);
-            // we know this exists now if it didn't before
-            let snapshot = timeline.get(minute + 1).unwrap();
-
-            if let Some(loc) = location.north() {
-                self.check_location(loc, minute, cost, snapshot, &mut heap, &mut cache);
-            }
-
-            if let Some(loc) = location.south() {
-                self.check_location(loc, minute, cost, snapshot, &mut heap, &mut cache);
-            }
-
-            if let Some(loc) = location.east() {
-                self.check_location(loc, minute, cost, snapshot, &mut heap, &mut cache);
-            }
-
-            if let Some(loc) = location.west() {
-                self.check_location(loc, minute, cost, snapshot, &mut heap, &mut cache);
-            }
-
-            // we can only wait if our current location would be open for the
-            // next minute
-            if snapshot.is_open(&location) {
-                self.check_location(location, minute, cost, snapshot, &mut heap, &mut cache);
-            }
-        }
-
-        bail!("Could not find a path")
-    }
-
-    fn check_location(
-        &self,
-        location: Location,
-        minute: usize,
-        cost: usize,
-        snapshot: &Snapshot,
-        heap: &mut BinaryHeap<State>,
-        cache: &mut FxHashMap<(Location, usize), usize>,
-    ) {
-        if snapshot.is_open(&location) {
-            let next = State {
-                location,
-                minute: minute + 1,
-                cost: cost + 1,
-            };
+        // the blizzards repeat, so being somewhere at the same point in the
+        // cycle again later is never any better
+        let cycle = timeline.lcm;
+        let dims = Dims::new([cycle, self.grid.rows, self.grid.cols]);
+        let mut best = IndexedMemo::new(dims.size(), |(location, minute): &(Location, usize)| {
+            dims.index([minute % cycle, location.row, location.col])
+        });
+
+        let mut steps = 0_usize;
+        let found = try_astar_with(
+            (*start, start_time),
+            |(location, minute)| {
+                steps += 1;
+                if steps % 4096 == 0 {
+                    checkpoint()?;
+                }
 
-            if next.cost
-                < *cache
-                    .get(&(next.location, next.minute))
-                    .unwrap_or(&usize::MAX)
-            {
-                cache.insert((location, next.minute), next.cost);
-                heap.push(next);
+                // let's see what it would look like on the next step
+                timeline.simulate_to(minute + 1, &self.next_template);
+                // we know this exists now if it didn't before
+                let snapshot = timeline.get(minute + 1).unwrap();
+
+                let moves = [
+                    location.north(),
+                    location.south(),
+                    location.east(),
+                    location.west(),
+                    // waiting, as long as where we are stays open
+                    Some(*location),
+                ];
+
+                Ok::<_, anyhow::Error>(
+                    moves
+                        .into_iter()
+                        .flatten()
+                        .filter(|loc| snapshot.is_open(loc))
+                        .map(|loc| ((loc, minute + 1), 1_usize))
+                        .collect::<Vec<_>>(),
+                )
+            },
+            // we can't get there any faster than walking straight there
+            |(location, _)| location.manhattan_dist(end),
+            |(location, _)| location == end,
+            &mut best,
+        )?;
+
+        match found {
+            Some(((_, minute), _)) => {
+                debug!(minute, steps, "reached the end");
+                Ok(minute)
             }
+            None => bail!("Could not find a path"),
         }
     }
 }
@@ -329,37 +280,37 @@ impl FromStr for BlizzardBasin {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<_> = s.trim().lines().collect();
-        let height = lines.len();
-        let width = lines
-            .get(0)
-            .ok_or_else(|| anyhow!("Input has no lines"))?
-            .chars()
-            .count();
-
-        let mut tiles = vec![vec![Tile::Empty; width]; height];
-        let mut template = vec![vec![Tile::Empty; width]; height];
-
-        for (row, line) in lines.iter().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
-                let t = match ch {
-                    '#' => Tile::Wall,
-                    '.' => Tile::Empty,
-                    '>' => Tile::Blizzard(EAST),
-                    '<' => Tile::Blizzard(WEST),
-                    '^' => Tile::Blizzard(NORTH),
-                    'v' => Tile::Blizzard(SOUTH),
-                    _ => bail!("Invalid char: {}", ch),
-                };
-
-                tiles[row][col] = t;
-
-                if t == Tile::Wall {
-                    template[row][col] = t;
-                }
-            }
+        // anything missing from a short row is as good as a wall
+        let tiles = char_grid(s.trim().lines(), Tile::Wall, |ch| match ch {
+            '#' => Ok(Tile::Wall),
+            '.' => Ok(Tile::Empty),
+            '>' => Ok(Tile::Blizzard(EAST)),
+            '<' => Ok(Tile::Blizzard(WEST)),
+            '^' => Ok(Tile::Blizzard(NORTH)),
+            'v' => Ok(Tile::Blizzard(SOUTH)),
+            _ => Err("invalid tile"),
+        })?;
+
+        if tiles.is_empty() {
+            bail!("Input has no lines");
         }
 
+        // the walls never move, so every minute starts from just those
+        let template = tiles
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|t| {
+                        if *t == Tile::Wall {
+                            Tile::Wall
+                        } else {
+                            Tile::Empty
+                        }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+
         let mut start = Location::default();
         let mut end = Location::default();
 
@@ -390,10 +341,16 @@ impl FromStr for BlizzardBasin {
     }
 }
 
+const EXAMPLE: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+#[aoc_problem(day = 24, title = "blizzard basin", year = 2022, full_dataset = (343, 960))]
 impl Problem for BlizzardBasin {
-    const DAY: usize = 24;
-    const TITLE: &'static str = "blizzard basin";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE, "18", "54")];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -407,34 +364,24 @@ impl Problem for BlizzardBasin {
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         let mut timeline = Timeline::new(&self.grid);
         let t = self.best_time(0, &self.start, &self.end, &mut timeline)?;
+        tick("trips", 1, 3);
         let t2 = self.best_time(t, &self.end, &self.start, &mut timeline)?;
-        self.best_time(t2, &self.start, &self.end, &mut timeline)
+        tick("trips", 2, 3);
+        let t3 = self.best_time(t2, &self.start, &self.end, &mut timeline)?;
+        tick("trips", 3, 3);
+        Ok(t3)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = BlizzardBasin::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(343, 960));
-    }
+impl Visualize for BlizzardBasin {
+    /// The blizzards for each minute of a full cycle.
+    fn frames(&self) -> Box<dyn Iterator<Item = String> + '_> {
+        let cycle = lcm(self.grid.rows - 2, self.grid.cols - 2);
+        let snapshots =
+            std::iter::successors(Some(Snapshot::from_initial_grid(&self.grid)), |snapshot| {
+                Some(snapshot.next(&self.next_template))
+            });
 
-    #[test]
-    fn example() {
-        let input = "#.######
-#>>.<^<#
-#.<..<<#
-#>v.><>#
-#<^v^^>#
-######.#";
-        let solution = BlizzardBasin::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(18, 54));
+        Box::new(snapshots.take(cycle).map(|snapshot| snapshot.to_string()))
     }
 }
@@ -1,55 +1,8 @@
-use std::{collections::VecDeque, fmt::Display, str::FromStr};
-
-use anyhow::bail;
-use aoc_plumbing::Problem;
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum Digit {
-    Zero,
-    One,
-    Two,
-    Minus,
-    DoubleMinus,
-}
+use std::str::FromStr;
 
-impl Digit {
-    pub fn val(&self) -> i64 {
-        match self {
-            Self::Zero => 0,
-            Self::One => 1,
-            Self::Two => 2,
-            Self::Minus => -1,
-            Self::DoubleMinus => -2,
-        }
-    }
+use aoc_plumbing::{aoc_problem, Example, NoAnswer, Problem};
 
-    pub fn to_char(&self) -> char {
-        match self {
-            Self::Zero => '0',
-            Self::One => '1',
-            Self::Two => '2',
-            Self::Minus => '-',
-            Self::DoubleMinus => '=',
-        }
-    }
-}
-
-impl TryFrom<char> for Digit {
-    type Error = anyhow::Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '0' => Ok(Self::Zero),
-            '1' => Ok(Self::One),
-            '2' => Ok(Self::Two),
-            '-' => Ok(Self::Minus),
-            '=' => Ok(Self::DoubleMinus),
-            _ => bail!("invalid digit char: {}", value),
-        }
-    }
-}
-
-const BASE: i64 = 5;
+pub use aoc_plumbing::radix::Snafu;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct State {
@@ -73,71 +26,30 @@ impl PartialOrd for State {
     }
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq)]
-pub struct Snafu {
-    digits: VecDeque<Digit>,
-}
-
-impl Snafu {
-    fn to_decimal(&self) -> i64 {
-        let mut sum = 0;
-
-        for (idx, digit) in self.digits.iter().rev().enumerate() {
-            sum += BASE.pow(idx as u32) * digit.val();
-        }
-
-        sum
-    }
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FullOfHotAir {
+    numbers: Vec<Snafu>,
 }
 
-impl From<i64> for Snafu {
-    fn from(value: i64) -> Self {
-        let mut snafu = Snafu::default();
-
-        let mut working = value;
-        while working > 0 {
-            let digit = match working % 5 {
-                0 => Digit::Zero,
-                1 => Digit::One,
-                2 => Digit::Two,
-                3 => Digit::DoubleMinus,
-                4 => Digit::Minus,
-                _ => unreachable!(),
-            };
-
-            snafu.digits.push_front(digit);
-            working -= digit.val();
-            working /= 5;
-        }
-
-        snafu
+impl FullOfHotAir {
+    pub fn numbers(&self) -> &[Snafu] {
+        &self.numbers
     }
-}
 
-impl Display for Snafu {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s: String = self.digits.iter().map(|d| d.to_char()).collect();
-        s.fmt(f)
+    /// The sum of all the numbers, computed digit-wise so it can't overflow.
+    pub fn total(&self) -> Snafu {
+        self.numbers.iter().sum()
     }
 }
 
-impl FromStr for Snafu {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let digits = s
-            .chars()
-            .map(|ch| Digit::try_from(ch))
-            .collect::<Result<VecDeque<_>, _>>()?;
-        Ok(Self { digits })
+impl FromIterator<Snafu> for FullOfHotAir {
+    fn from_iter<T: IntoIterator<Item = Snafu>>(iter: T) -> Self {
+        Self {
+            numbers: iter.into_iter().collect(),
+        }
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct FullOfHotAir {
-    numbers: Vec<Snafu>,
-}
-
 impl FromStr for FullOfHotAir {
     type Err = anyhow::Error;
 
@@ -152,56 +64,93 @@ impl FromStr for FullOfHotAir {
     }
 }
 
+const EXAMPLE: &str = "1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+
+#[aoc_problem(day = 25, title = "full of hot air", year = 2022, full_dataset = ("2=112--220-=-00=-=20", ""))]
 impl Problem for FullOfHotAir {
-    const DAY: usize = 25;
-    const TITLE: &'static str = "full of hot air";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: Some("2=-1=0"),
+        part_two: None,
+    }];
 
     type ProblemError = anyhow::Error;
     type P1 = String;
-    type P2 = i64;
+    type P2 = NoAnswer;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        let sum: i64 = self.numbers.iter().map(|n| n.to_decimal()).sum();
-        Ok(Snafu::from(sum).to_string())
+        Ok(self.total().to_string())
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
         // no part two on day 25
-        Ok(0)
+        Ok(NoAnswer)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
-
     use super::*;
 
     #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = FullOfHotAir::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new("2=112--220-=-00=-=20".into(), 0));
+    fn arithmetic() {
+        let a = Snafu::from_str("1=-0-2").unwrap();
+        let b = Snafu::from_str("12111").unwrap();
+
+        assert_eq!((&a + &b).to_decimal(), 1747 + 906);
+        assert_eq!((&a - &b).to_decimal(), 1747 - 906);
+        assert_eq!((&b - &a).to_decimal(), 906 - 1747);
+        assert_eq!((-&a).to_decimal(), -1747);
+        assert_eq!(-(-a.clone()), a);
+        assert_eq!(a.clone() - a.clone(), Snafu::default());
+
+        let total = (1..1000).fold(Snafu::default(), |acc, n| acc + Snafu::from(n));
+        assert_eq!(total.to_decimal(), 999 * 1000 / 2);
+        assert_eq!(total, Snafu::from(999 * 1000 / 2));
+
+        // well beyond i64::MAX
+        let big = Snafu::from_str("2222222222222222222222222222").unwrap();
+        let bigger = &big + &big;
+        assert_eq!(bigger.to_string(), format!("1{}-", "0".repeat(27)));
+        assert_eq!(&bigger - &big, big);
     }
 
     #[test]
-    fn example() {
-        let input = "1=-0-2
-12111
-2=0=
-21
-2=01
-111
-20012
-112
-1=-1=
-1-12
-12
-1=
-122";
-        let solution = FullOfHotAir::solve(input).unwrap();
-        assert_eq!(solution, Solution::new("2=-1=0".into(), 0));
+    fn round_trip() {
+        for n in -1000..1000 {
+            let snafu = Snafu::from(n);
+            assert_eq!(snafu.to_decimal(), n as i128);
+            assert_eq!(Snafu::from_str(&snafu.to_string()).unwrap(), snafu);
+            assert_eq!(-snafu, Snafu::from(-n));
+        }
+
+        assert_eq!(Snafu::from(0).to_string(), "0");
+        assert_eq!(Snafu::from(-1).to_string(), "-");
+        assert_eq!(Snafu::from(-10).to_string(), "=0");
+    }
+
+    #[test]
+    fn total() {
+        let hot_air: FullOfHotAir = (1..=100).map(Snafu::from).collect();
+        assert_eq!(hot_air.numbers().len(), 100);
+        assert_eq!(hot_air.total().to_decimal(), 5050);
+
+        let sum: Snafu = hot_air.numbers().iter().cloned().sum();
+        assert_eq!(sum, hot_air.total());
+
+        let empty: FullOfHotAir = std::iter::empty().collect();
+        assert_eq!(empty.total().to_string(), "0");
     }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use aoc_plumbing::Problem;
+use aoc_plumbing::{aoc_problem, Example, Problem};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct {{project-name|upper_camel_case}};
@@ -13,10 +13,15 @@ impl FromStr for {{project-name|upper_camel_case}} {
     }
 }
 
+const EXAMPLE: &str = "";
+
+#[aoc_problem(day = {{day}}, title = "{{project-name|title_case|downcase}}", year = {{year}})]
 impl Problem for {{project-name|upper_camel_case}} {
-    const DAY: usize = {{day}};
-    const TITLE: &'static str = "{{project-name|title_case|downcase}}";
-    const README: &'static str = include_str!("../README.md");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part_one: None,
+        part_two: None,
+    }];
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
@@ -31,24 +36,3 @@ impl Problem for {{project-name|upper_camel_case}} {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use aoc_plumbing::Solution;
-
-    use super::*;
-
-    #[test]
-    #[ignore]
-    fn full_dataset() {
-        let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = {{project-name|upper_camel_case}}::solve(&input).unwrap();
-        assert_eq!(solution, Solution::new(0, 0));
-    }
-
-    #[test]
-    fn example() {
-        let input = "";
-        let solution = {{project-name|upper_camel_case}}::solve(input).unwrap();
-        assert_eq!(solution, Solution::new(0, 0));
-    }
-}